@@ -0,0 +1,440 @@
+package main
+
+import (
+	"errors"
+	"flag"
+	"fmt"
+	"io/fs"
+	"log/slog"
+	"os"
+	"path"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/rpc"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+// Environment variables consulted by LoadConfig.
+const (
+	envConfigPath  = "NOTE_DAEMON_CONFIG"
+	envVaultPath   = "NOTE_DAEMON_VAULT"
+	envPrimary     = "NOTE_DAEMON_PRIMARY_VAULT"
+	envLogLevel    = "NOTE_DAEMON_LOG_LEVEL"
+	envLogNoColor  = "NOTE_DAEMON_LOG_NO_COLOR"
+	envDailyPath   = "NOTE_DAEMON_DAILY_PATH"
+	envIgnore      = "NOTE_DAEMON_IGNORE"
+	envSlowRequest = "NOTE_DAEMON_SLOW_REQUEST"
+	envExtensions  = "NOTE_DAEMON_EXT"
+	envTemplate    = "NOTE_DAEMON_DAILY_TEMPLATE"
+	envLogTasks    = "NOTE_DAEMON_LOG_TASKS"
+	envTaskIDs     = "NOTE_DAEMON_TASK_ID_PATTERN"
+	envSocket      = "NOTE_DAEMON_SOCKET"
+	envNoSymlinks  = "NOTE_DAEMON_NO_SYMLINKS"
+	envContentLRU  = "NOTE_DAEMON_CONTENT_CACHE"
+	envReadOnly    = "NOTE_DAEMON_READ_ONLY"
+	envArchivePath = "NOTE_DAEMON_ARCHIVE_PATH"
+	envMaxRequest  = "NOTE_DAEMON_MAX_REQUEST_BYTES"
+	envExactTags   = "NOTE_DAEMON_EXACT_TAGS"
+)
+
+// vaultNameRE matches the name part of a "name=path" vault spec.
+var vaultNameRE = regexp.MustCompile(`^[A-Za-z0-9_-]+$`)
+
+// NamedVault is a vault root and the name requests select it by.
+type NamedVault struct {
+	Name string
+	Path string
+}
+
+// Config holds the daemon settings.
+type Config struct {
+	// Vaults lists the vaults to serve, primary first.
+	Vaults         []NamedVault
+	LogLevel       slog.Level
+	DailyTemplate  vault.DailyTemplate
+	IgnorePatterns []string
+	SlowRequest    time.Duration
+	Extensions     []string
+	// DailyNote is the content of the daily note template file, or empty to
+	// use the default template.
+	DailyNote string
+	// LogTasks indexes checkbox bullets in Log sections as tasks.
+	LogTasks bool
+	// LogNoColor disables coloring log levels even when stderr is a
+	// terminal.
+	LogNoColor bool
+	// ContentCache bounds how many notes' content is kept in memory; zero
+	// keeps all of it.
+	ContentCache int
+	// NoSymlinks skips symlinked files and directories when scanning vaults.
+	NoSymlinks bool
+	// TaskIDs is the task ID shape; the zero value keeps the parser default.
+	TaskIDs parser.TaskIDPattern
+	// Socket is the path of a Unix domain socket to serve clients on
+	// instead of stdin/stdout, or empty.
+	Socket string
+	// ReadOnly refuses every request that would write a vault's notes.
+	ReadOnly bool
+	// ArchivePath is the vault-relative note completed tasks are archived
+	// to, or empty for the domain default.
+	ArchivePath string
+	// MaxRequestBytes is the longest request line the server accepts; zero
+	// accepts any length.
+	MaxRequestBytes int
+	// ExactTags compares tags verbatim instead of ignoring case and Unicode
+	// normalization form.
+	ExactTags bool
+}
+
+// settings are the raw, unvalidated values being layered by LoadConfig.
+type settings struct {
+	vaults      []string
+	primary     string
+	logLevel    string
+	logNoColor  string
+	dailyPath   string
+	ignore      []string
+	slowRequest string
+	extensions  []string
+	template    string
+	logTasks    string
+	taskIDs     string
+	socket      string
+	noSymlinks  string
+	contentLRU  string
+	readOnly    string
+	archivePath string
+	maxRequest  string
+	exactTags   string
+}
+
+// LoadConfig resolves the daemon configuration from command-line args
+// (excluding the program name), the environment (read through getenv) and a
+// TOML config file. Precedence is flags > environment > file > defaults.
+//
+// The config file is taken from --config, then NOTE_DAEMON_CONFIG, then
+// $XDG_CONFIG_HOME/note-daemon/config.toml (~/.config by default). Only an
+// explicitly named file is required to exist.
+func LoadConfig(args []string, getenv func(string) string) (Config, error) {
+	flags := flag.NewFlagSet("note-daemon", flag.ContinueOnError)
+	configPath := flags.String("config", "", "path to a TOML config file (env "+envConfigPath+")")
+	var vaults []string
+	flags.Func("vault", "path to a Markdown vault, or name=path; repeat to serve several (env "+envVaultPath+", comma-separated)", func(value string) error {
+		vaults = append(vaults, value)
+		return nil
+	})
+	primary := flags.String("primary-vault", "", "name of the vault requests target by default, default the first (env "+envPrimary+")")
+	logLevel := flags.String("log-level", "", "log level: debug, info, warn or error (env "+envLogLevel+")")
+	logNoColor := flags.Bool("log-no-color", false, "never color log output, even on a terminal (env "+envLogNoColor+")")
+	dailyPath := flags.String("daily-path", "", "daily note path relative to the vault, e.g. journal/{year}/{date}.md (env "+envDailyPath+")")
+	ignore := flags.String("ignore", "", "comma-separated glob patterns of vault paths to skip (env "+envIgnore+")")
+	slowRequest := flags.String("slow-request", "", "log requests slower than this duration at warn, e.g. 250ms; 0 disables (env "+envSlowRequest+")")
+	extensions := flags.String("ext", "", "comma-separated file extensions read as notes, default md (env "+envExtensions+")")
+	template := flags.String("daily-template", "", "file whose contents seed new daily notes (env "+envTemplate+")")
+	taskIDs := flags.String("task-id-pattern", "", "regular expression matching a task ID, default "+parser.DefaultTaskIDPattern+" (env "+envTaskIDs+")")
+	socket := flags.String("socket", "", "serve clients on this Unix domain socket instead of stdin/stdout (env "+envSocket+")")
+	contentCache := flags.Int("content-cache", 0, "keep the content of at most this many notes in memory, reading others from disk; 0 keeps all (env "+envContentLRU+")")
+	noSymlinks := flags.Bool("no-symlinks", false, "skip symlinked files and directories when scanning vaults (env "+envNoSymlinks+")")
+	archivePath := flags.String("archive-path", "", "vault-relative note that core.archive_completed moves tasks to, default "+domain.DefaultArchivePath+" (env "+envArchivePath+")")
+	maxRequest := flags.Int("max-request-bytes", rpc.DefaultMaxRequestBytes, "reject request lines longer than this many bytes with a parse error; 0 disables the limit (env "+envMaxRequest+")")
+	readOnly := flags.Bool("read-only", false, "refuse requests that would write to a vault (env "+envReadOnly+")")
+	logTasks := flags.Bool("log-tasks", false, "also index checkbox bullets in Log sections as tasks (env "+envLogTasks+")")
+	exactTags := flags.Bool("exact-tags", false, "treat tags differing in case or Unicode form as distinct (env "+envExactTags+")")
+	if err := flags.Parse(args); err != nil {
+		return Config{}, err
+	}
+
+	s := settings{
+		logLevel:    "info",
+		dailyPath:   vault.DefaultDailyPattern,
+		slowRequest: rpc.DefaultSlowThreshold.String(),
+		maxRequest:  strconv.Itoa(rpc.DefaultMaxRequestBytes),
+	}
+
+	file, explicit := *configPath, true
+	if file == "" {
+		file = getenv(envConfigPath)
+	}
+	if file == "" {
+		file, explicit = defaultConfigPath(getenv), false
+	}
+	if file != "" {
+		if err := s.applyFile(file, explicit); err != nil {
+			return Config{}, err
+		}
+	}
+
+	s.applyEnv(getenv)
+
+	flags.Visit(func(f *flag.Flag) {
+		switch f.Name {
+		case "vault":
+			s.vaults = vaults
+		case "primary-vault":
+			s.primary = *primary
+		case "log-level":
+			s.logLevel = *logLevel
+		case "log-no-color":
+			s.logNoColor = strconv.FormatBool(*logNoColor)
+		case "daily-path":
+			s.dailyPath = *dailyPath
+		case "ignore":
+			s.ignore = splitList(*ignore)
+		case "slow-request":
+			s.slowRequest = *slowRequest
+		case "ext":
+			s.extensions = splitList(*extensions)
+		case "daily-template":
+			s.template = *template
+		case "log-tasks":
+			s.logTasks = strconv.FormatBool(*logTasks)
+		case "task-id-pattern":
+			s.taskIDs = *taskIDs
+		case "socket":
+			s.socket = *socket
+		case "no-symlinks":
+			s.noSymlinks = strconv.FormatBool(*noSymlinks)
+		case "content-cache":
+			s.contentLRU = strconv.Itoa(*contentCache)
+		case "read-only":
+			s.readOnly = strconv.FormatBool(*readOnly)
+		case "archive-path":
+			s.archivePath = *archivePath
+		case "max-request-bytes":
+			s.maxRequest = strconv.Itoa(*maxRequest)
+		case "exact-tags":
+			s.exactTags = strconv.FormatBool(*exactTags)
+		}
+	})
+
+	return s.resolve(getenv)
+}
+
+func defaultConfigPath(getenv func(string) string) string {
+	if dir := getenv("XDG_CONFIG_HOME"); dir != "" {
+		return filepath.Join(dir, "note-daemon", "config.toml")
+	}
+	if home := getenv("HOME"); home != "" {
+		return filepath.Join(home, ".config", "note-daemon", "config.toml")
+	}
+	return ""
+}
+
+// applyFile layers the values from the config file at file. A missing file
+// is only an error when it was named explicitly.
+func (s *settings) applyFile(file string, explicit bool) error {
+	data, err := os.ReadFile(file)
+	if errors.Is(err, fs.ErrNotExist) && !explicit {
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("read config: %w", err)
+	}
+
+	fc, err := decodeConfigFile(string(data))
+	if err != nil {
+		return fmt.Errorf("%s: %w", file, err)
+	}
+	fc.apply(s)
+	return nil
+}
+
+func (s *settings) applyEnv(getenv func(string) string) {
+	if v := getenv(envVaultPath); v != "" {
+		s.vaults = splitList(v)
+	}
+	if v := getenv(envPrimary); v != "" {
+		s.primary = v
+	}
+	if v := getenv(envLogLevel); v != "" {
+		s.logLevel = v
+	}
+	if v := getenv(envLogNoColor); v != "" {
+		s.logNoColor = v
+	}
+	if v := getenv(envDailyPath); v != "" {
+		s.dailyPath = v
+	}
+	if v := getenv(envIgnore); v != "" {
+		s.ignore = splitList(v)
+	}
+	if v := getenv(envSlowRequest); v != "" {
+		s.slowRequest = v
+	}
+	if v := getenv(envExtensions); v != "" {
+		s.extensions = splitList(v)
+	}
+	if v := getenv(envTemplate); v != "" {
+		s.template = v
+	}
+	if v := getenv(envLogTasks); v != "" {
+		s.logTasks = v
+	}
+	if v := getenv(envTaskIDs); v != "" {
+		s.taskIDs = v
+	}
+	if v := getenv(envSocket); v != "" {
+		s.socket = v
+	}
+	if v := getenv(envNoSymlinks); v != "" {
+		s.noSymlinks = v
+	}
+	if v := getenv(envContentLRU); v != "" {
+		s.contentLRU = v
+	}
+	if v := getenv(envReadOnly); v != "" {
+		s.readOnly = v
+	}
+	if v := getenv(envArchivePath); v != "" {
+		s.archivePath = v
+	}
+	if v := getenv(envMaxRequest); v != "" {
+		s.maxRequest = v
+	}
+	if v := getenv(envExactTags); v != "" {
+		s.exactTags = v
+	}
+}
+
+// resolve validates the layered settings into a Config.
+func (s *settings) resolve(getenv func(string) string) (Config, error) {
+	cfg := Config{
+		IgnorePatterns: s.ignore,
+		Extensions:     s.extensions,
+	}
+	if s.socket != "" {
+		cfg.Socket = expandHome(s.socket, getenv)
+	}
+	if s.archivePath != "" {
+		if !filepath.IsLocal(filepath.FromSlash(s.archivePath)) {
+			return Config{}, fmt.Errorf("invalid archive path %q: must be relative and inside the vault", s.archivePath)
+		}
+		cfg.ArchivePath = s.archivePath
+	}
+	vaults, err := resolveVaults(s.vaults, s.primary, getenv)
+	if err != nil {
+		return Config{}, err
+	}
+	cfg.Vaults = vaults
+	if err := cfg.LogLevel.UnmarshalText([]byte(s.logLevel)); err != nil {
+		return Config{}, fmt.Errorf("invalid log level %q: %w", s.logLevel, err)
+	}
+	template, err := vault.ParseDailyTemplate(s.dailyPath)
+	if err != nil {
+		return Config{}, err
+	}
+	cfg.DailyTemplate = template
+	if cfg.SlowRequest, err = time.ParseDuration(s.slowRequest); err != nil {
+		return Config{}, fmt.Errorf("invalid slow request threshold %q: %w", s.slowRequest, err)
+	}
+	if s.logNoColor != "" {
+		if cfg.LogNoColor, err = strconv.ParseBool(s.logNoColor); err != nil {
+			return Config{}, fmt.Errorf("invalid log no-color setting %q: %w", s.logNoColor, err)
+		}
+	}
+	if s.logTasks != "" {
+		if cfg.LogTasks, err = strconv.ParseBool(s.logTasks); err != nil {
+			return Config{}, fmt.Errorf("invalid log tasks setting %q: %w", s.logTasks, err)
+		}
+	}
+	if s.contentLRU != "" {
+		if cfg.ContentCache, err = strconv.Atoi(s.contentLRU); err != nil || cfg.ContentCache < 0 {
+			return Config{}, fmt.Errorf("invalid content cache size %q: must be a non-negative integer", s.contentLRU)
+		}
+	}
+	if s.noSymlinks != "" {
+		if cfg.NoSymlinks, err = strconv.ParseBool(s.noSymlinks); err != nil {
+			return Config{}, fmt.Errorf("invalid no-symlinks setting %q: %w", s.noSymlinks, err)
+		}
+	}
+	if cfg.MaxRequestBytes, err = strconv.Atoi(s.maxRequest); err != nil || cfg.MaxRequestBytes < 0 {
+		return Config{}, fmt.Errorf("invalid max request size %q: must be a non-negative integer", s.maxRequest)
+	}
+	if s.readOnly != "" {
+		if cfg.ReadOnly, err = strconv.ParseBool(s.readOnly); err != nil {
+			return Config{}, fmt.Errorf("invalid read-only setting %q: %w", s.readOnly, err)
+		}
+	}
+	if s.exactTags != "" {
+		if cfg.ExactTags, err = strconv.ParseBool(s.exactTags); err != nil {
+			return Config{}, fmt.Errorf("invalid exact tags setting %q: %w", s.exactTags, err)
+		}
+	}
+	if s.taskIDs != "" {
+		if cfg.TaskIDs, err = parser.CompileTaskIDPattern(s.taskIDs); err != nil {
+			return Config{}, err
+		}
+	}
+	if s.template != "" {
+		data, err := os.ReadFile(expandHome(s.template, getenv))
+		if err != nil {
+			return Config{}, fmt.Errorf("read daily template: %w", err)
+		}
+		cfg.DailyNote = string(data)
+	}
+	for _, pattern := range cfg.IgnorePatterns {
+		if _, err := path.Match(pattern, ""); err != nil {
+			return Config{}, fmt.Errorf("invalid ignore pattern %q: %w", pattern, err)
+		}
+	}
+	return cfg, nil
+}
+
+// resolveVaults parses "path" and "name=path" vault specs and moves the
+// primary vault to the front. An unnamed vault is called
+// rpc.DefaultVaultName; without a primary named, the first vault is primary.
+func resolveVaults(specs []string, primary string, getenv func(string) string) ([]NamedVault, error) {
+	if len(specs) == 0 {
+		return nil, errors.New("vault path is required (--vault, " + envVaultPath + " or vault_path in the config file)")
+	}
+	vaults := make([]NamedVault, 0, len(specs))
+	seen := map[string]bool{}
+	for _, spec := range specs {
+		v := NamedVault{Name: rpc.DefaultVaultName, Path: spec}
+		if name, p, ok := strings.Cut(spec, "="); ok && vaultNameRE.MatchString(name) {
+			v = NamedVault{Name: name, Path: p}
+		}
+		v.Path = expandHome(v.Path, getenv)
+		if v.Path == "" {
+			return nil, fmt.Errorf("vault %q has no path", v.Name)
+		}
+		if seen[v.Name] {
+			return nil, fmt.Errorf("duplicate vault name %q; name vaults with --vault name=path", v.Name)
+		}
+		seen[v.Name] = true
+		vaults = append(vaults, v)
+	}
+	if primary == "" {
+		return vaults, nil
+	}
+	i := slices.IndexFunc(vaults, func(v NamedVault) bool { return v.Name == primary })
+	if i < 0 {
+		return nil, fmt.Errorf("primary vault %q is not configured", primary)
+	}
+	first := vaults[i]
+	return append([]NamedVault{first}, slices.Delete(vaults, i, i+1)...), nil
+}
+
+func expandHome(p string, getenv func(string) string) string {
+	if home := getenv("HOME"); home != "" && (p == "~" || strings.HasPrefix(p, "~/")) {
+		return filepath.Join(home, p[1:])
+	}
+	return p
+}
+
+func splitList(value string) []string {
+	var items []string
+	for _, item := range strings.Split(value, ",") {
+		if item = strings.TrimSpace(item); item != "" {
+			items = append(items, item)
+		}
+	}
+	return items
+}
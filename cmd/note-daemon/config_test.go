@@ -0,0 +1,131 @@
+package main
+
+import (
+	"log/slog"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+	"time"
+)
+
+// env returns a getenv reading from vars.
+func env(vars map[string]string) func(string) string {
+	return func(key string) string { return vars[key] }
+}
+
+// writeConfig writes content to a config file in a fresh directory and
+// returns its path.
+func writeConfig(t *testing.T, content string) string {
+	t.Helper()
+	path := filepath.Join(t.TempDir(), "config.toml")
+	if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	return path
+}
+
+func TestLoadConfigLayersFileEnvAndFlags(t *testing.T) {
+	file := writeConfig(t, `
+vault_path = "/vaults/file"
+log_level = "debug"
+slow_request = "2s"
+extensions = [
+  "md",  # notes
+  "txt",
+]
+ignore = ["archive"]
+`)
+	getenv := env(map[string]string{
+		envConfigPath:  file,
+		envLogLevel:    "warn",
+		envSlowRequest: "500ms",
+	})
+
+	cfg, err := LoadConfig([]string{"--slow-request", "1s"}, getenv)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(cfg.Vaults) != 1 || cfg.Vaults[0].Path != "/vaults/file" {
+		t.Errorf("vaults = %+v, want the file's", cfg.Vaults)
+	}
+	if cfg.LogLevel != slog.LevelWarn {
+		t.Errorf("log level = %v, want the environment's warn", cfg.LogLevel)
+	}
+	if cfg.SlowRequest != time.Second {
+		t.Errorf("slow request = %v, want the flag's 1s", cfg.SlowRequest)
+	}
+	if !slices.Equal(cfg.Extensions, []string{"md", "txt"}) || !slices.Equal(cfg.IgnorePatterns, []string{"archive"}) {
+		t.Errorf("extensions %v, ignore %v, want the file's", cfg.Extensions, cfg.IgnorePatterns)
+	}
+
+	cfg, err = LoadConfig([]string{"--config", file, "--vault", "/vaults/flag"}, env(map[string]string{envVaultPath: "/vaults/env"}))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(cfg.Vaults) != 1 || cfg.Vaults[0].Path != "/vaults/flag" {
+		t.Errorf("vaults = %+v, want the flag's", cfg.Vaults)
+	}
+	if cfg.LogLevel != slog.LevelDebug {
+		t.Errorf("log level = %v, want the file's debug", cfg.LogLevel)
+	}
+}
+
+func TestLoadConfigDefaultFileIsOptional(t *testing.T) {
+	getenv := env(map[string]string{"XDG_CONFIG_HOME": t.TempDir(), envVaultPath: "/vaults/env"})
+	cfg, err := LoadConfig(nil, getenv)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if cfg.LogLevel != slog.LevelInfo || cfg.Vaults[0].Path != "/vaults/env" {
+		t.Errorf("config = %+v, want defaults with the environment's vault", cfg)
+	}
+
+	missing := filepath.Join(t.TempDir(), "missing.toml")
+	if _, err := LoadConfig([]string{"--config", missing}, getenv); err == nil {
+		t.Error("a missing explicit config file was accepted")
+	}
+}
+
+func TestLoadConfigRejectsBadFiles(t *testing.T) {
+	for name, content := range map[string]string{
+		"unknown key":  `vault_path = "/v"` + "\ncolour = true\n",
+		"wrong type":   `vault_path = "/v"` + "\nlog_tasks = \"yes\"\n",
+		"bad duration": `vault_path = "/v"` + "\nslow_request = \"soon\"\n",
+		"table":        `vault_path = "/v"` + "\n[server]\nsocket = \"/tmp/s\"\n",
+		"float":        `vault_path = "/v"` + "\ncontent_cache = 1.5\n",
+	} {
+		if _, err := LoadConfig([]string{"--config", writeConfig(t, content)}, env(nil)); err == nil {
+			t.Errorf("%s: config accepted", name)
+		}
+	}
+}
+
+func TestLoadConfigExtensionsFlag(t *testing.T) {
+	cfg, err := LoadConfig([]string{"--vault", "/v", "--ext", "markdown, txt"}, env(nil))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !slices.Equal(cfg.Extensions, []string{"markdown", "txt"}) {
+		t.Errorf("extensions = %v, want [markdown txt]", cfg.Extensions)
+	}
+}
+
+func TestLoadConfigExactTags(t *testing.T) {
+	file := writeConfig(t, `vault_path = "/v"`+"\nexact_tags = true\n")
+	cfg, err := LoadConfig([]string{"--config", file}, env(nil))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !cfg.ExactTags {
+		t.Error("exact_tags = true in the file was ignored")
+	}
+
+	cfg, err = LoadConfig([]string{"--config", file}, env(map[string]string{envExactTags: "false"}))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if cfg.ExactTags {
+		t.Error("the environment did not override the file's exact_tags")
+	}
+}
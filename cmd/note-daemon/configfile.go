@@ -0,0 +1,98 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+
+	"github.com/BurntSushi/toml"
+)
+
+// fileConfig is the layout of the TOML config file. Pointer fields tell a
+// key set to its zero value apart from one left out.
+type fileConfig struct {
+	VaultPath       *string  `toml:"vault_path"`
+	Vaults          []string `toml:"vaults"`
+	PrimaryVault    *string  `toml:"primary_vault"`
+	LogLevel        *string  `toml:"log_level"`
+	LogNoColor      *bool    `toml:"log_no_color"`
+	DailyPath       *string  `toml:"daily_path"`
+	Ignore          []string `toml:"ignore"`
+	SlowRequest     *string  `toml:"slow_request"`
+	Extensions      []string `toml:"extensions"`
+	DailyTemplate   *string  `toml:"daily_template"`
+	LogTasks        *bool    `toml:"log_tasks"`
+	TaskIDPattern   *string  `toml:"task_id_pattern"`
+	Socket          *string  `toml:"socket"`
+	ContentCache    *int64   `toml:"content_cache"`
+	NoSymlinks      *bool    `toml:"no_symlinks"`
+	ReadOnly        *bool    `toml:"read_only"`
+	ArchivePath     *string  `toml:"archive_path"`
+	MaxRequestBytes *int64   `toml:"max_request_bytes"`
+	ExactTags       *bool    `toml:"exact_tags"`
+}
+
+// decodeConfigFile decodes the config file content data. Keys the daemon
+// does not know, including any inside a table, are rejected so that
+// misspelled or misplaced settings are reported instead of ignored.
+func decodeConfigFile(data string) (fileConfig, error) {
+	var fc fileConfig
+	meta, err := toml.Decode(data, &fc)
+	if err != nil {
+		return fileConfig{}, err
+	}
+	if undecoded := meta.Undecoded(); len(undecoded) > 0 {
+		return fileConfig{}, fmt.Errorf("unknown key %q", undecoded[0].String())
+	}
+	return fc, nil
+}
+
+// apply layers the keys set in fc onto s.
+func (fc fileConfig) apply(s *settings) {
+	var vaults []string
+	if fc.VaultPath != nil {
+		vaults = append(vaults, *fc.VaultPath)
+	}
+	vaults = append(vaults, fc.Vaults...)
+	if len(vaults) > 0 {
+		s.vaults = vaults
+	}
+	if fc.Ignore != nil {
+		s.ignore = fc.Ignore
+	}
+	if fc.Extensions != nil {
+		s.extensions = fc.Extensions
+	}
+	setString(&s.primary, fc.PrimaryVault)
+	setString(&s.logLevel, fc.LogLevel)
+	setString(&s.dailyPath, fc.DailyPath)
+	setString(&s.slowRequest, fc.SlowRequest)
+	setString(&s.template, fc.DailyTemplate)
+	setString(&s.taskIDs, fc.TaskIDPattern)
+	setString(&s.socket, fc.Socket)
+	setString(&s.archivePath, fc.ArchivePath)
+	setBool(&s.logNoColor, fc.LogNoColor)
+	setBool(&s.logTasks, fc.LogTasks)
+	setBool(&s.noSymlinks, fc.NoSymlinks)
+	setBool(&s.readOnly, fc.ReadOnly)
+	setBool(&s.exactTags, fc.ExactTags)
+	setInt(&s.contentLRU, fc.ContentCache)
+	setInt(&s.maxRequest, fc.MaxRequestBytes)
+}
+
+func setString(dst *string, v *string) {
+	if v != nil {
+		*dst = *v
+	}
+}
+
+func setBool(dst *string, v *bool) {
+	if v != nil {
+		*dst = strconv.FormatBool(*v)
+	}
+}
+
+func setInt(dst *string, v *int64) {
+	if v != nil {
+		*dst = strconv.FormatInt(*v, 10)
+	}
+}
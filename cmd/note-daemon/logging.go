@@ -0,0 +1,110 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"log/slog"
+	"os"
+	"runtime"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+)
+
+// stderrHandler is a slog.Handler writing "[timestamp][LEVEL][component]
+// message" lines followed by any attributes as key=value pairs. The
+// component is the package that logged the record. Groups are flattened.
+type stderrHandler struct {
+	mu    *sync.Mutex
+	w     io.Writer
+	level slog.Leveler
+	color bool
+	attrs []slog.Attr
+}
+
+// levelColors are the ANSI SGR codes used for each level when coloring.
+var levelColors = map[slog.Level]string{
+	slog.LevelDebug: "90",
+	slog.LevelInfo:  "32",
+	slog.LevelWarn:  "33",
+	slog.LevelError: "31",
+}
+
+// newLogger returns a logger writing records at or above level to w. With
+// color set, the level is highlighted with ANSI escapes.
+func newLogger(w io.Writer, level slog.Leveler, color bool) *slog.Logger {
+	return slog.New(&stderrHandler{mu: &sync.Mutex{}, w: w, level: level, color: color})
+}
+
+// isTerminal reports whether f is a character device such as a terminal,
+// as opposed to a file or pipe.
+func isTerminal(f *os.File) bool {
+	info, err := f.Stat()
+	return err == nil && info.Mode()&os.ModeCharDevice != 0
+}
+
+// Enabled implements slog.Handler.
+func (h *stderrHandler) Enabled(_ context.Context, level slog.Level) bool {
+	return level >= h.level.Level()
+}
+
+// Handle implements slog.Handler.
+func (h *stderrHandler) Handle(_ context.Context, r slog.Record) error {
+	var b strings.Builder
+	level := r.Level.String()
+	if code, ok := levelColors[r.Level]; ok && h.color {
+		level = "\x1b[" + code + "m" + level + "\x1b[0m"
+	}
+	fmt.Fprintf(&b, "[%s][%s]", r.Time.Format(time.RFC3339), level)
+	if component := recordComponent(r); component != "" {
+		fmt.Fprintf(&b, "[%s]", component)
+	}
+	fmt.Fprintf(&b, " %s", r.Message)
+	for _, attr := range h.attrs {
+		writeAttr(&b, attr)
+	}
+	r.Attrs(func(attr slog.Attr) bool {
+		writeAttr(&b, attr)
+		return true
+	})
+	b.WriteByte('\n')
+
+	h.mu.Lock()
+	defer h.mu.Unlock()
+	_, err := io.WriteString(h.w, b.String())
+	return err
+}
+
+// WithAttrs implements slog.Handler.
+func (h *stderrHandler) WithAttrs(attrs []slog.Attr) slog.Handler {
+	clone := *h
+	clone.attrs = append(slices.Clip(h.attrs), attrs...)
+	return &clone
+}
+
+// WithGroup implements slog.Handler.
+func (h *stderrHandler) WithGroup(string) slog.Handler {
+	return h
+}
+
+// recordComponent returns the name of the package that logged r, e.g. "rpc"
+// or "main", or "" when the caller is unknown.
+func recordComponent(r slog.Record) string {
+	if r.PC == 0 {
+		return ""
+	}
+	frame, _ := runtime.CallersFrames([]uintptr{r.PC}).Next()
+	// Function names look like "github.com/weakphish/yapper/internal/rpc.(*Server).logRequest".
+	name := frame.Function
+	if i := strings.LastIndex(name, "/"); i >= 0 {
+		name = name[i+1:]
+	}
+	pkg, _, _ := strings.Cut(name, ".")
+	return pkg
+}
+
+func writeAttr(b *strings.Builder, attr slog.Attr) {
+	fmt.Fprintf(b, " %s=%v", attr.Key, attr.Value.Resolve())
+}
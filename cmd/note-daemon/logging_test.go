@@ -0,0 +1,42 @@
+package main
+
+import (
+	"bytes"
+	"log/slog"
+	"strings"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/rpc"
+)
+
+func TestLogLinesNameTheirComponent(t *testing.T) {
+	var out bytes.Buffer
+	logger := newLogger(&out, slog.LevelDebug, false)
+
+	logger.Info("starting", "vault", "/v")
+	// A domain without an index manager makes the server log a panic.
+	rpc.NewServer(domain.New(nil), logger).HandleRequest([]byte(`{"jsonrpc":"2.0","id":1,"method":"core.ping"}`))
+
+	lines := strings.Split(strings.TrimSuffix(out.String(), "\n"), "\n")
+	if len(lines) < 2 {
+		t.Fatalf("log output:\n%s", out.String())
+	}
+	if !strings.Contains(lines[0], "][INFO][main] starting vault=/v") {
+		t.Errorf("main line = %q", lines[0])
+	}
+	if !strings.Contains(out.String(), "][ERROR][rpc] handler panicked") {
+		t.Errorf("no rpc line in:\n%s", out.String())
+	}
+	if strings.Contains(out.String(), "\x1b[") {
+		t.Errorf("uncolored output has escapes:\n%s", out.String())
+	}
+}
+
+func TestLogLevelsAreColoredOnRequest(t *testing.T) {
+	var out bytes.Buffer
+	newLogger(&out, slog.LevelInfo, true).Warn("slow")
+	if !strings.Contains(out.String(), "[\x1b[33mWARN\x1b[0m][main] slow") {
+		t.Errorf("colored line = %q", out.String())
+	}
+}
@@ -1,10 +1,130 @@
 package main
 
 import (
-	"log"
+	"context"
+	"errors"
+	"flag"
+	"fmt"
+	"log/slog"
+	"os"
+	"os/signal"
+	"runtime/debug"
+	"syscall"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/index"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/rpc"
+	"github.com/weakphish/yapper/internal/vault"
 )
 
-// main is a placeholder entrypoint that will eventually host the JSON-RPC server.
+// main indexes the configured vaults and serves JSON-RPC over stdin/stdout,
+// or to clients of a Unix domain socket when one is configured.
 func main() {
-	log.Println("note-daemon initialized (phase 1 scaffolding)")
+	cfg, err := LoadConfig(os.Args[1:], os.Getenv)
+	if errors.Is(err, flag.ErrHelp) {
+		return
+	}
+	if err != nil {
+		fmt.Fprintln(os.Stderr, "note-daemon:", err)
+		os.Exit(2)
+	}
+
+	logger := newLogger(os.Stderr, cfg.LogLevel, !cfg.LogNoColor && isTerminal(os.Stderr))
+	slog.SetDefault(logger)
+
+	var domainOpts []domain.Option
+	if cfg.DailyNote != "" {
+		if missing := parser.MissingSections(cfg.DailyNote); len(missing) > 0 {
+			logger.Warn("daily note template lacks section headings; its tasks and log entries will not be indexed", "missing", missing)
+		}
+		domainOpts = append(domainOpts, domain.WithDailyNoteTemplate(cfg.DailyNote))
+	}
+	if cfg.ArchivePath != "" {
+		domainOpts = append(domainOpts, domain.WithArchivePath(cfg.ArchivePath))
+	}
+
+	vaultOpts := []vault.Option{
+		vault.WithDailyTemplate(cfg.DailyTemplate),
+		vault.WithIgnorePatterns(cfg.IgnorePatterns),
+		vault.WithExtensions(cfg.Extensions),
+		vault.WithFollowSymlinks(!cfg.NoSymlinks),
+	}
+	parserOpts := []parser.Option{
+		parser.WithLogTasks(cfg.LogTasks),
+		parser.WithTaskIDPattern(cfg.TaskIDs),
+	}
+	var storeOpts []index.StoreOption
+	if cfg.ContentCache > 0 {
+		storeOpts = append(storeOpts, index.WithContentCache(cfg.ContentCache))
+	}
+	if cfg.ExactTags {
+		storeOpts = append(storeOpts, index.WithTagNormalizer(index.ExactTag))
+	}
+	serverOpts := []rpc.ServerOption{
+		rpc.WithSlowThreshold(cfg.SlowRequest),
+		rpc.WithVersion(daemonVersion()),
+		rpc.WithPrimaryVault(cfg.Vaults[0].Name),
+		rpc.WithReadOnly(cfg.ReadOnly),
+		rpc.WithMaxRequestBytes(cfg.MaxRequestBytes),
+	}
+	domains := make([]*domain.Domain, len(cfg.Vaults))
+	for i, named := range cfg.Vaults {
+		d, report, err := domain.OpenInMemory(named.Path, vaultOpts, parserOpts, storeOpts, domainOpts...)
+		if err != nil {
+			logger.Error("initial reindex failed", "vault", named.Name, "err", err)
+			os.Exit(1)
+		}
+		for _, failed := range report.Errors {
+			logger.Warn("note skipped", "vault", named.Name, "path", failed.Path, "err", failed.Error)
+		}
+		for _, lossy := range report.Lossy {
+			logger.Warn("note is not valid UTF-8; invalid bytes replaced", "vault", named.Name, "path", lossy)
+		}
+		domains[i] = d
+		if i > 0 {
+			serverOpts = append(serverOpts, rpc.WithVault(named.Name, domains[i]))
+		}
+		logger.Info("vault indexed", "vault", named.Name, "path", named.Path, "notes", report.Indexed, "skipped", len(report.Errors))
+	}
+	logger.Info("note-daemon ready", "vaults", len(cfg.Vaults))
+
+	server := rpc.NewServer(domains[0], logger, serverOpts...)
+	if cfg.Socket != "" {
+		err = serveSocket(server, cfg.Socket, logger)
+	} else {
+		err = server.Serve(os.Stdin, os.Stdout)
+	}
+	if err != nil {
+		logger.Error("server stopped", "err", err)
+		os.Exit(1)
+	}
+}
+
+// serveSocket serves clients on the Unix domain socket at path until the
+// daemon is interrupted or terminated, then removes the socket.
+func serveSocket(server *rpc.Server, path string, logger *slog.Logger) error {
+	ln, err := listenSocket(path)
+	if err != nil {
+		return err
+	}
+	ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+	defer stop()
+	go func() {
+		<-ctx.Done()
+		ln.Close()
+	}()
+	logger.Info("listening", "socket", path)
+	err = server.ServeListener(ln)
+	ln.Close()
+	return err
+}
+
+// daemonVersion reports the module version the binary was built from, or
+// "dev" for builds from a working tree.
+func daemonVersion() string {
+	if info, ok := debug.ReadBuildInfo(); ok && info.Main.Version != "" && info.Main.Version != "(devel)" {
+		return info.Main.Version
+	}
+	return "dev"
 }
@@ -0,0 +1,84 @@
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"errors"
+	"io"
+	"io/fs"
+	"log/slog"
+	"net"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/rpc"
+)
+
+func TestSocketServesPing(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "note.sock")
+	// Leave a stale socket file behind, as a crashed daemon would.
+	stale, err := net.Listen("unix", path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	stale.(*net.UnixListener).SetUnlinkOnClose(false)
+	stale.Close()
+
+	ln, err := listenSocket(path)
+	if err != nil {
+		t.Fatalf("listen over a stale socket: %v", err)
+	}
+	d, _, err := domain.OpenInMemory(t.TempDir(), nil, nil, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	served := make(chan error, 1)
+	go func() {
+		served <- rpc.NewServer(d, slog.New(slog.NewTextHandler(io.Discard, nil))).ServeListener(ln)
+	}()
+
+	if _, err := listenSocket(path); err == nil {
+		t.Error("a second daemon listened on a socket in use")
+	}
+	conn, err := net.Dial("unix", path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer conn.Close()
+	if _, err := io.WriteString(conn, `{"jsonrpc":"2.0","id":1,"method":"core.ping"}`+"\n"); err != nil {
+		t.Fatal(err)
+	}
+	line, err := bufio.NewReader(conn).ReadBytes('\n')
+	if err != nil {
+		t.Fatal(err)
+	}
+	var resp struct {
+		Result rpc.PingResult `json:"result"`
+	}
+	if err := json.Unmarshal(line, &resp); err != nil || !resp.Result.Pong {
+		t.Errorf("ping response = %s (%v), want pong", line, err)
+	}
+
+	ln.Close()
+	if err := <-served; err != nil {
+		t.Errorf("ServeListener = %v", err)
+	}
+	if _, err := os.Lstat(path); !errors.Is(err, fs.ErrNotExist) {
+		t.Errorf("socket file left behind: %v", err)
+	}
+}
+
+func TestListenSocketRefusesOtherFiles(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "notes.md")
+	if err := os.WriteFile(path, []byte("# Keep me\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := listenSocket(path); err == nil {
+		t.Fatal("listened over a regular file")
+	}
+	if raw, err := os.ReadFile(path); err != nil || string(raw) != "# Keep me\n" {
+		t.Errorf("file = %q, %v, want it untouched", raw, err)
+	}
+}
@@ -0,0 +1,137 @@
+package domain
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+// DefaultArchivePath is the vault-relative note that ArchiveCompleted moves
+// tasks to, unless WithArchivePath says otherwise.
+const DefaultArchivePath = "archive.md"
+
+// defaultArchiveNote is the content of a newly created archive note.
+const defaultArchiveNote = "# Archive\n\n## Tasks\n"
+
+// ArchiveCompleted moves every done task completed before the calendar day
+// before out of its note and into the archive note, creating the archive if
+// needed. Each task's line and description block move together, keeping the
+// task's ID, and its completion date is written out if the line lacked one.
+// Tasks already in the archive stay put, as do tasks read from Log-section
+// bullets (see parser.WithLogTasks), which belong to their log entry.
+//
+// All task lines are checked before anything is written, so a stale index,
+// or a source or archive note that is not valid UTF-8 (ErrLossy), fails the
+// call rather than moving part of the selection. Each note is
+// replaced atomically, the archive first, so a failure part way leaves
+// tasks duplicated rather than lost. Every touched note is reindexed.
+func (d *Domain) ArchiveCompleted(before time.Time) (model.ArchiveReport, error) {
+	archivePath, err := d.notePath(d.archivePath)
+	if err != nil {
+		return model.ArchiveReport{}, err
+	}
+	report := model.ArchiveReport{Archive: d.vault().NoteID(archivePath), Sources: []model.ArchiveSource{}}
+
+	cutoff := time.Date(before.Year(), before.Month(), before.Day(), 0, 0, 0, 0, time.UTC)
+	bySource := map[model.NoteID][]model.Task{}
+	for task := range d.store().Tasks() {
+		if task.Status != model.TaskStatusDone || task.CompletedAt == nil || !task.CompletedAt.Before(cutoff) {
+			continue
+		}
+		if task.NoteID == report.Archive || d.inLogSection(task) {
+			continue
+		}
+		bySource[task.NoteID] = append(bySource[task.NoteID], task)
+	}
+	if len(bySource) == 0 {
+		return report, nil
+	}
+
+	sources := make([]model.NoteID, 0, len(bySource))
+	for id := range bySource {
+		sources = append(sources, id)
+	}
+	slices.Sort(sources)
+
+	rewritten := map[model.NoteID]string{}
+	var archived []string
+	for _, id := range sources {
+		note, ok := d.store().GetNote(id)
+		if !ok {
+			return model.ArchiveReport{}, fmt.Errorf("note %s: %w", id, ErrNotFound)
+		}
+		if err := rewritable(note); err != nil {
+			return model.ArchiveReport{}, err
+		}
+		tasks := bySource[id]
+		slices.SortFunc(tasks, func(a, b model.Task) int { return a.Line - b.Line })
+
+		lines := strings.Split(note.Content, "\n")
+		source := model.ArchiveSource{NoteID: id}
+		// Remove blocks bottom-up so earlier line numbers stay valid.
+		blocks := make([][]string, len(tasks))
+		for i := len(tasks) - 1; i >= 0; i-- {
+			task := tasks[i]
+			n, ok := parser.TaskBlockLen(lines, task.Line-1, task.ID)
+			if !ok {
+				return model.ArchiveReport{}, fmt.Errorf("task %s: not found on line %d of %s; reindex and retry", task.ID, task.Line, id)
+			}
+			blocks[i] = parser.ArchivedTaskBlock(lines[task.Line-1:task.Line-1+n], task.CompletedAt)
+			lines = slices.Delete(lines, task.Line-1, task.Line-1+n)
+		}
+		for i, task := range tasks {
+			archived = append(archived, blocks[i]...)
+			source.Tasks = append(source.Tasks, task.ID)
+		}
+		rewritten[id] = strings.Join(lines, "\n")
+		report.Moved += len(tasks)
+		report.Sources = append(report.Sources, source)
+	}
+
+	content := defaultArchiveNote
+	if note, err := d.vault().ReadNote(archivePath); err == nil {
+		if err := rewritable(note); err != nil {
+			return model.ArchiveReport{}, err
+		}
+		content = note.Content
+	} else if !errors.Is(err, fs.ErrNotExist) {
+		return model.ArchiveReport{}, err
+	}
+	if err := d.vault().WriteNote(archivePath, parser.AppendToTasks(content, archived)); err != nil {
+		return model.ArchiveReport{}, fmt.Errorf("write archive: %w", err)
+	}
+	for _, id := range sources {
+		path, err := d.notePath(string(id))
+		if err != nil {
+			return model.ArchiveReport{}, err
+		}
+		if err := d.vault().WriteNote(path, rewritten[id]); err != nil {
+			return model.ArchiveReport{}, err
+		}
+	}
+
+	for _, id := range append(sources, report.Archive) {
+		path, err := d.notePath(string(id))
+		if err != nil {
+			return model.ArchiveReport{}, err
+		}
+		if _, err := d.reindexNote(path); err != nil {
+			return model.ArchiveReport{}, err
+		}
+	}
+	return report, nil
+}
+
+// inLogSection reports whether task was read from a checkbox bullet in its
+// note's Log section. Such a line is also indexed as a log entry.
+func (d *Domain) inLogSection(task model.Task) bool {
+	return slices.ContainsFunc(d.store().GetLogEntriesForNote(task.NoteID), func(entry model.LogEntry) bool {
+		return entry.Line == task.Line
+	})
+}
@@ -0,0 +1,94 @@
+package domain
+
+import (
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+func TestArchiveCompletedFromTwoNotes(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"2025-03-01.md": "# Saturday\n\n## Tasks\n\n" +
+			"- [x] [T-1] Ship it ✅2025-03-01\n" +
+			"  Notes on the release and the rollout checklist for it\n" +
+			"- [ ] [T-2] Still open\n",
+		"2025-03-02.md": "# Sunday\n\n## Tasks\n\n" +
+			"- [ ] [T-4] Open\n" +
+			"- [x] [T-3] Done later ✅2025-03-20\n" +
+			"- [x] [T-5] Done ✅2025-03-02\n",
+	})
+	d := openVault(t, root)
+
+	report, err := d.ArchiveCompleted(time.Date(2025, time.March, 10, 0, 0, 0, 0, time.UTC))
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := model.ArchiveReport{Archive: "archive.md", Moved: 2, Sources: []model.ArchiveSource{
+		{NoteID: "2025-03-01.md", Tasks: []model.TaskID{"T-1"}},
+		{NoteID: "2025-03-02.md", Tasks: []model.TaskID{"T-5"}},
+	}}
+	if report.Archive != want.Archive || report.Moved != want.Moved || !slices.EqualFunc(report.Sources, want.Sources, func(a, b model.ArchiveSource) bool {
+		return a.NoteID == b.NoteID && slices.Equal(a.Tasks, b.Tasks)
+	}) {
+		t.Errorf("report = %+v, want %+v", report, want)
+	}
+
+	files := map[string]string{
+		"2025-03-01.md": "# Saturday\n\n## Tasks\n\n- [ ] [T-2] Still open\n",
+		"2025-03-02.md": "# Sunday\n\n## Tasks\n\n- [ ] [T-4] Open\n- [x] [T-3] Done later ✅2025-03-20\n",
+		"archive.md":    "# Archive\n\n## Tasks\n" +
+			"- [x] [T-1] Ship it ✅2025-03-01\n" +
+			"  Notes on the release and the rollout checklist for it\n" +
+			"- [x] [T-5] Done ✅2025-03-02\n",
+	}
+	for rel, content := range files {
+		if got := readFile(t, root, rel); got != content {
+			t.Errorf("%s =\n%s\nwant\n%s", rel, got, content)
+		}
+	}
+
+	for _, id := range []model.TaskID{"T-1", "T-5"} {
+		task, ok := d.store().GetTask(id)
+		if !ok || task.NoteID != "archive.md" || task.CompletedAt == nil {
+			t.Errorf("%s = %+v, %v, want it indexed in archive.md with its completion date", id, task, ok)
+		}
+	}
+	if task, _ := d.store().GetTask("T-1"); task.Description != "Notes on the release and the rollout checklist for it" {
+		t.Errorf("archived T-1 description = %q", task.Description)
+	}
+}
+
+func TestArchiveCompletedLeavesLogTasks(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"2025-03-01.md": "# Saturday\n\n## Tasks\n\n" +
+			"- [x] [T-1] Ship it ✅2025-03-01\n" +
+			"- [ ] [T-3] Open\n\n" +
+			"## Log\n\n" +
+			"- [x] called the vendor [T-2] ✅2025-03-01\n",
+	})
+	d, _, err := OpenInMemory(root, nil, []parser.Option{parser.WithLogTasks(true)}, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if task, ok := d.store().GetTask("T-2"); !ok || task.Status != model.TaskStatusDone {
+		t.Fatalf("log task T-2 = %+v, %v, want it indexed as done", task, ok)
+	}
+
+	report, err := d.ArchiveCompleted(time.Date(2025, time.March, 10, 0, 0, 0, 0, time.UTC))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if report.Moved != 1 || len(report.Sources) != 1 || !slices.Equal(report.Sources[0].Tasks, []model.TaskID{"T-1"}) {
+		t.Errorf("report = %+v, want only T-1 moved", report)
+	}
+	want := "# Saturday\n\n## Tasks\n\n- [ ] [T-3] Open\n\n## Log\n\n- [x] called the vendor [T-2] ✅2025-03-01\n"
+	if got := readFile(t, root, "2025-03-01.md"); got != want {
+		t.Errorf("source =\n%s\nwant\n%s", got, want)
+	}
+	if task, _ := d.store().GetTask("T-2"); task.NoteID != "2025-03-01.md" {
+		t.Errorf("T-2 moved to %s", task.NoteID)
+	}
+}
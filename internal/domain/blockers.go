@@ -0,0 +1,47 @@
+package domain
+
+import (
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// BlockersOf returns the tasks named in the BlockedBy list of the task id,
+// in the order listed, skipping IDs that are not indexed. It reports false
+// when id is not indexed.
+func (d *Domain) BlockersOf(id model.TaskID) ([]model.Task, bool) {
+	task, ok := d.store().GetTask(id)
+	if !ok {
+		return nil, false
+	}
+	blockers := []model.Task{}
+	for _, blocker := range task.BlockedBy {
+		if t, ok := d.store().GetTask(blocker); ok {
+			blockers = append(blockers, t)
+		}
+	}
+	return blockers, true
+}
+
+// BlockedByTransitive returns every task the task id waits on, directly or
+// through other blockers, breadth first. Each task appears once and the task
+// itself never does, so dependency cycles end the walk rather than loop it.
+// It reports false when id is not indexed.
+func (d *Domain) BlockedByTransitive(id model.TaskID) ([]model.Task, bool) {
+	queue, ok := d.BlockersOf(id)
+	if !ok {
+		return nil, false
+	}
+	seen := map[model.TaskID]bool{id: true}
+	blockers := []model.Task{}
+	for len(queue) > 0 {
+		task := queue[0]
+		queue = queue[1:]
+		if seen[task.ID] {
+			continue
+		}
+		seen[task.ID] = true
+		blockers = append(blockers, task)
+		next, _ := d.BlockersOf(task.ID)
+		queue = append(queue, next...)
+	}
+	return blockers, true
+}
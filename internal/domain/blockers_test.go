@@ -0,0 +1,48 @@
+package domain
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestBlockers(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{"deps.md": "# Deps\n\n## Tasks\n\n" +
+		"- [ ] [T-1] Launch blocked-by:T-2,T-9\n" +
+		"- [!] [T-2] Deploy blocked-by:T-3\n" +
+		"- [!] [T-3] Review blocked-by:T-4\n" +
+		"- [!] [T-4] Sign off blocked-by:T-2,T-1\n" +
+		"- [ ] [T-5] Free\n"}))
+	ids := func(tasks []model.Task) []model.TaskID {
+		var got []model.TaskID
+		for _, task := range tasks {
+			got = append(got, task.ID)
+		}
+		return got
+	}
+
+	direct, ok := d.BlockersOf("T-1")
+	if !ok || !slices.Equal(ids(direct), []model.TaskID{"T-2"}) {
+		t.Errorf("blockers of T-1 = %v, %v, want [T-2]", ids(direct), ok)
+	}
+	if free, ok := d.BlockersOf("T-5"); !ok || len(free) != 0 {
+		t.Errorf("blockers of T-5 = %v, %v, want none", ids(free), ok)
+	}
+
+	// T-4 leads back to T-2 and T-1, closing a cycle.
+	transitive, ok := d.BlockedByTransitive("T-1")
+	if !ok || !slices.Equal(ids(transitive), []model.TaskID{"T-2", "T-3", "T-4"}) {
+		t.Errorf("transitive blockers of T-1 = %v, %v, want [T-2 T-3 T-4]", ids(transitive), ok)
+	}
+	if transitive, _ := d.BlockedByTransitive("T-3"); !slices.Equal(ids(transitive), []model.TaskID{"T-4", "T-2", "T-1"}) {
+		t.Errorf("transitive blockers of T-3 = %v, want [T-4 T-2 T-1]", ids(transitive))
+	}
+
+	if _, ok := d.BlockersOf("T-404"); ok {
+		t.Error("blockers of a missing task were found")
+	}
+	if _, ok := d.BlockedByTransitive("T-404"); ok {
+		t.Error("transitive blockers of a missing task were found")
+	}
+}
@@ -0,0 +1,76 @@
+package domain
+
+import (
+	"slices"
+	"sync"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// ChangeListener is called with the notes and tasks affected each time the
+// domain changes the index.
+type ChangeListener func(model.ChangeSet)
+
+// subscribers holds the registered change listeners. Any number of
+// listeners may be registered, one per interested client.
+type subscribers struct {
+	mu        sync.Mutex
+	next      int
+	listeners map[int]ChangeListener
+}
+
+// Subscribe registers fn to be called after every write, daily note
+// creation and reindex. The returned function removes the subscription.
+// Listeners are called synchronously, before the changing call returns.
+func (d *Domain) Subscribe(fn ChangeListener) (unsubscribe func()) {
+	d.subs.mu.Lock()
+	defer d.subs.mu.Unlock()
+	id := d.subs.next
+	d.subs.next++
+	if d.subs.listeners == nil {
+		d.subs.listeners = map[int]ChangeListener{}
+	}
+	d.subs.listeners[id] = fn
+	return func() {
+		d.subs.mu.Lock()
+		defer d.subs.mu.Unlock()
+		delete(d.subs.listeners, id)
+	}
+}
+
+func (d *Domain) notify(changes model.ChangeSet) {
+	d.subs.mu.Lock()
+	listeners := make([]ChangeListener, 0, len(d.subs.listeners))
+	for _, fn := range d.subs.listeners {
+		listeners = append(listeners, fn)
+	}
+	d.subs.mu.Unlock()
+
+	for _, fn := range listeners {
+		fn(changes)
+	}
+}
+
+// indexedTaskIDs returns the IDs of the tasks defined in note, or in any note
+// when note is empty.
+func (d *Domain) indexedTaskIDs(note model.NoteID) []model.TaskID {
+	var ids []model.TaskID
+	for task := range d.store().Tasks() {
+		if note == "" || task.NoteID == note {
+			ids = append(ids, task.ID)
+		}
+	}
+	return ids
+}
+
+// changeSet builds a sorted, duplicate-free ChangeSet.
+func changeSet(notes []model.NoteID, tasks []model.TaskID) model.ChangeSet {
+	notes = slices.Clone(notes)
+	tasks = slices.Clone(tasks)
+	slices.Sort(notes)
+	slices.Sort(tasks)
+	return model.ChangeSet{
+		NoteIDs: append([]model.NoteID{}, slices.Compact(notes)...),
+		TaskIDs: append([]model.TaskID{}, slices.Compact(tasks)...),
+	}
+}
@@ -0,0 +1,87 @@
+package domain
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestOpenDailyFindsFrontmatterDatedNote(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"daily-entry.md": "---\ndate: 2025-04-01T08:15\n---\n# Tuesday\n",
+		"2025-01-01.md":  "---\ndate: 2025-02-02\n---\n# Misfiled\n",
+	})
+	d := openVault(t, root)
+
+	note, err := d.OpenDaily(time.Date(2025, time.April, 1, 0, 0, 0, 0, time.UTC))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if note.ID != "daily-entry.md" {
+		t.Errorf("OpenDaily = %s, want daily-entry.md", note.ID)
+	}
+	if _, err := os.Stat(filepath.Join(root, "2025-04-01.md")); !os.IsNotExist(err) {
+		t.Errorf("2025-04-01.md was created alongside daily-entry.md: %v", err)
+	}
+
+	misfiled, ok := d.ReadNote("2025-01-01.md")
+	if want := time.Date(2025, time.February, 2, 0, 0, 0, 0, time.UTC); !ok || misfiled.Date == nil || !misfiled.Date.Equal(want) {
+		t.Errorf("2025-01-01.md dated %v, want the frontmatter's %v", misfiled.Date, want)
+	}
+}
+
+const richDailyTemplate = "---\ndate: {date}\n---\n# {weekday} {date} ({iso_week})\n\n## Notes\n\n## Tasks\n\n## Log\n"
+
+func TestRenderDailyNoteSubstitutesPlaceholders(t *testing.T) {
+	got := RenderDailyNote(richDailyTemplate, time.Date(2024, time.December, 30, 0, 0, 0, 0, time.UTC))
+	want := "---\ndate: 2024-12-30\n---\n# Monday 2024-12-30 (2025-W01)\n\n## Notes\n\n## Tasks\n\n## Log\n"
+	if got != want {
+		t.Errorf("RenderDailyNote =\n%s\nwant\n%s", got, want)
+	}
+}
+
+func TestOpenDailyUsesConfiguredTemplate(t *testing.T) {
+	root := writeVault(t, nil)
+	d := openVault(t, root, WithDailyNoteTemplate(richDailyTemplate))
+	if _, err := d.OpenDaily(testTime); err != nil {
+		t.Fatal(err)
+	}
+	if got, want := readFile(t, root, "2025-04-01.md"), RenderDailyNote(richDailyTemplate, testTime); got != want {
+		t.Errorf("daily note =\n%s\nwant\n%s", got, want)
+	}
+}
+
+func TestOpenWeeklyAcrossYearBoundary(t *testing.T) {
+	root := writeVault(t, nil)
+	d := openVault(t, root)
+	monday := time.Date(2024, time.December, 30, 0, 0, 0, 0, time.UTC)
+
+	note, err := d.OpenWeekly(time.Date(2025, time.January, 5, 0, 0, 0, 0, time.UTC))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if note.ID != "2025-W01.md" {
+		t.Fatalf("weekly note = %q, want 2025-W01.md", note.ID)
+	}
+	if note.Date == nil || !note.Date.Equal(monday) {
+		t.Errorf("weekly note date = %v, want %v", note.Date, monday)
+	}
+	if again, err := d.OpenWeekly(monday); err != nil || again.ID != note.ID {
+		t.Errorf("reopening from Monday = %q, %v, want %q", again.ID, err, note.ID)
+	}
+	if next, err := d.OpenWeekly(monday.AddDate(0, 0, 7)); err != nil || next.ID != "2025-W02.md" {
+		t.Errorf("the following Monday opened %q, %v, want 2025-W02.md", next.ID, err)
+	}
+
+	day := model.DateRange{Start: monday, End: monday}
+	if got := noteIDs(d.NotesInRange(day, false)); !slices.Equal(got, []model.NoteID{"2025-W01.md"}) {
+		t.Errorf("notes on %s = %v, want [2025-W01.md]", monday.Format(time.DateOnly), got)
+	}
+	if daily, ok := d.Daily(monday); ok {
+		t.Errorf("Daily(%s) = %q, want the weekly note skipped", monday.Format(time.DateOnly), daily.ID)
+	}
+}
@@ -0,0 +1,134 @@
+package domain
+
+import (
+	"maps"
+	"slices"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// diffParsedNotes compares two parses of the same note.
+func diffParsedNotes(before, after model.ParsedNote) model.WriteDiff {
+	diff := model.WriteDiff{
+		NoteID:            after.Note.ID,
+		AddedTasks:        []model.TaskID{},
+		RemovedTasks:      []model.TaskID{},
+		ChangedTasks:      []model.TaskID{},
+		AddedLogEntries:   []string{},
+		RemovedLogEntries: []string{},
+	}
+
+	oldTasks := make(map[model.TaskID]model.Task, len(before.Tasks))
+	for _, task := range before.Tasks {
+		oldTasks[task.ID] = task
+	}
+	newTasks := make(map[model.TaskID]model.Task, len(after.Tasks))
+	for _, task := range after.Tasks {
+		newTasks[task.ID] = task
+	}
+	for id, task := range newTasks {
+		old, ok := oldTasks[id]
+		switch {
+		case !ok:
+			diff.AddedTasks = append(diff.AddedTasks, id)
+		case taskChanged(old, task):
+			diff.ChangedTasks = append(diff.ChangedTasks, id)
+		}
+	}
+	for id := range oldTasks {
+		if _, ok := newTasks[id]; !ok {
+			diff.RemovedTasks = append(diff.RemovedTasks, id)
+		}
+	}
+	slices.Sort(diff.AddedTasks)
+	slices.Sort(diff.RemovedTasks)
+	slices.Sort(diff.ChangedTasks)
+
+	diff.AddedLogEntries, diff.RemovedLogEntries = multisetDelta(logContents(before), logContents(after))
+	diff.AddedTags, diff.RemovedTags = setDelta(noteTags(before), noteTags(after))
+	return diff
+}
+
+// taskChanged compares the fields a write can change. Line numbers and
+// UpdatedAt are ignored since they shift with unrelated edits.
+func taskChanged(a, b model.Task) bool {
+	return a.Title != b.Title ||
+		a.Description != b.Description ||
+		a.DescriptionMD != b.DescriptionMD ||
+		a.Status != b.Status ||
+		!slices.Equal(a.Tags, b.Tags) ||
+		!slices.Equal(a.BlockedBy, b.BlockedBy) ||
+		!a.CreatedAt.Equal(b.CreatedAt) ||
+		!timesEqual(a.CompletedAt, b.CompletedAt) ||
+		!timesEqual(a.DueDate, b.DueDate)
+}
+
+func timesEqual(a, b *time.Time) bool {
+	if a == nil || b == nil {
+		return a == b
+	}
+	return a.Equal(*b)
+}
+
+func logContents(parsed model.ParsedNote) []string {
+	contents := make([]string, len(parsed.LogEntries))
+	for i, entry := range parsed.LogEntries {
+		contents[i] = entry.Content
+	}
+	return contents
+}
+
+func noteTags(parsed model.ParsedNote) map[string]bool {
+	tags := map[string]bool{}
+	for _, task := range parsed.Tasks {
+		for _, tag := range task.Tags {
+			tags[tag] = true
+		}
+	}
+	for _, entry := range parsed.LogEntries {
+		for _, tag := range entry.Tags {
+			tags[tag] = true
+		}
+	}
+	return tags
+}
+
+// multisetDelta returns the values gained and lost going from before to
+// after, counting duplicates.
+func multisetDelta(before, after []string) (added, removed []string) {
+	counts := map[string]int{}
+	for _, v := range before {
+		counts[v]--
+	}
+	for _, v := range after {
+		counts[v]++
+	}
+	added, removed = []string{}, []string{}
+	for _, v := range slices.Sorted(maps.Keys(counts)) {
+		for n := counts[v]; n > 0; n-- {
+			added = append(added, v)
+		}
+		for n := counts[v]; n < 0; n++ {
+			removed = append(removed, v)
+		}
+	}
+	return added, removed
+}
+
+func setDelta(before, after map[string]bool) (added, removed []string) {
+	added, removed = []string{}, []string{}
+	for v := range after {
+		if !before[v] {
+			added = append(added, v)
+		}
+	}
+	for v := range before {
+		if !after[v] {
+			removed = append(removed, v)
+		}
+	}
+	slices.Sort(added)
+	slices.Sort(removed)
+	return added, removed
+}
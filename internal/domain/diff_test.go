@@ -0,0 +1,47 @@
+package domain
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestPreviewWriteReportsChanges(t *testing.T) {
+	const before = "# Plan\n\n## Tasks\n\n- [ ] [T-1] Write #work\n- [ ] [T-2] Review\n\n## Log\n\n- drafted\n"
+	const after = "# Plan\n\n## Tasks\n\n- [ ] [T-1] Write the final draft #work\n- [ ] [T-3] Book travel #home\n\n## Log\n\n- drafted\n- reviewed\n"
+	root := writeVault(t, map[string]string{"plan.md": before})
+	d := openVault(t, root)
+
+	diff, err := d.PreviewWrite("plan.md", after)
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := model.WriteDiff{
+		NoteID:            "plan.md",
+		AddedTasks:        []model.TaskID{"T-3"},
+		RemovedTasks:      []model.TaskID{"T-2"},
+		ChangedTasks:      []model.TaskID{"T-1"},
+		AddedLogEntries:   []string{"reviewed"},
+		RemovedLogEntries: []string{},
+		AddedTags:         []string{"home"},
+		RemovedTags:       []string{},
+	}
+	if diff.NoteID != want.NoteID ||
+		!slices.Equal(diff.AddedTasks, want.AddedTasks) ||
+		!slices.Equal(diff.RemovedTasks, want.RemovedTasks) ||
+		!slices.Equal(diff.ChangedTasks, want.ChangedTasks) ||
+		!slices.Equal(diff.AddedLogEntries, want.AddedLogEntries) ||
+		!slices.Equal(diff.RemovedLogEntries, want.RemovedLogEntries) ||
+		!slices.Equal(diff.AddedTags, want.AddedTags) ||
+		!slices.Equal(diff.RemovedTags, want.RemovedTags) {
+		t.Errorf("diff = %+v\nwant %+v", diff, want)
+	}
+
+	if got := readFile(t, root, "plan.md"); got != before {
+		t.Errorf("preview wrote the note:\n%s", got)
+	}
+	if _, ok := d.TaskDetail("T-2"); !ok {
+		t.Error("preview changed the index")
+	}
+}
@@ -1,3 +1,2 @@
-// Package domain will host the UI-agnostic domain/query layer.
+// Package domain hosts the UI-agnostic domain/query layer.
 package domain
-
@@ -0,0 +1,653 @@
+package domain
+
+import (
+	"cmp"
+	"errors"
+	"fmt"
+	"io/fs"
+	"path/filepath"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/index"
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+var (
+	// ErrNotFound is returned when an operation targets a note or task that
+	// is not in the index.
+	ErrNotFound = errors.New("not found")
+	// ErrExists is returned when creating a note at a path already in use.
+	ErrExists = errors.New("already exists")
+	// ErrInvalidPath is returned for note paths that are not relative paths
+	// inside the vault.
+	ErrInvalidPath = errors.New("invalid note path")
+	// ErrInvalidTag is returned for tag names that would not parse back as
+	// the same tag.
+	ErrInvalidTag = errors.New("invalid tag")
+	// ErrInvalidTaskID is returned when a minted task ID would not parse
+	// back as a task ID under the parser's task ID pattern.
+	ErrInvalidTaskID = errors.New("invalid task ID")
+	// ErrLossy is returned when an operation would rewrite a note that is
+	// not valid UTF-8. Its content was read with the invalid bytes replaced,
+	// so writing it back would destroy them.
+	ErrLossy = errors.New("note is not valid UTF-8")
+)
+
+// DefaultDailyNoteTemplate is the skeleton written when a daily note is
+// created, unless WithDailyNoteTemplate says otherwise.
+const DefaultDailyNoteTemplate = "# {date}\n\n## Tasks\n\n## Log\n"
+
+// DefaultWeeklyNoteTemplate is the skeleton written when a weekly note is
+// created, unless WithWeeklyNoteTemplate says otherwise.
+const DefaultWeeklyNoteTemplate = "# {iso_week}\n\n## Goals\n\n## Tasks\n\n## Log\n"
+
+// Domain exposes the high-level, UI-agnostic operations frontends rely on.
+//
+// A Domain does no locking of its own. Methods that only query may run
+// concurrently with each other, but a method that writes notes, reindexes,
+// imports or changes the scratch buffer must run alone. Callers sharing a
+// Domain between goroutines guard it with a sync.RWMutex, as rpc.Server
+// does per vault.
+type Domain struct {
+	manager     *index.VaultIndexManager
+	subs        subscribers
+	dailyNote   string
+	weeklyNote  string
+	archivePath string
+	scratch     []string
+}
+
+// Option configures a Domain.
+type Option func(*Domain)
+
+// WithDailyNoteTemplate sets the content written when a daily note is
+// created. The placeholders {date} (2006-01-02), {weekday} (Monday) and
+// {iso_week} (2006-W01) are replaced with the note's date.
+func WithDailyNoteTemplate(template string) Option {
+	return func(d *Domain) {
+		d.dailyNote = template
+	}
+}
+
+// WithWeeklyNoteTemplate sets the content written when a weekly note is
+// created. It takes the same placeholders as WithDailyNoteTemplate, with
+// {date} and {weekday} standing for the Monday of the week.
+func WithWeeklyNoteTemplate(template string) Option {
+	return func(d *Domain) {
+		d.weeklyNote = template
+	}
+}
+
+// WithArchivePath sets the vault-relative, slash-separated path of the note
+// ArchiveCompleted moves tasks to.
+func WithArchivePath(rel string) Option {
+	return func(d *Domain) {
+		d.archivePath = rel
+	}
+}
+
+// New returns a Domain operating on the vault and index behind manager.
+func New(manager *index.VaultIndexManager, opts ...Option) *Domain {
+	d := &Domain{
+		manager:     manager,
+		dailyNote:   DefaultDailyNoteTemplate,
+		weeklyNote:  DefaultWeeklyNoteTemplate,
+		archivePath: DefaultArchivePath,
+	}
+	for _, opt := range opts {
+		opt(d)
+	}
+	return d
+}
+
+func (d *Domain) vault() vault.Vault {
+	return d.manager.Vault()
+}
+
+// RootPath returns the root directory of the vault.
+func (d *Domain) RootPath() string {
+	return d.vault().RootPath()
+}
+
+func (d *Domain) store() index.IndexStore {
+	return d.manager.Store()
+}
+
+// ReindexAll rebuilds the index from the vault, reporting the notes it had
+// to skip. Subscribers are told about every note and task indexed before or
+// after the rebuild.
+func (d *Domain) ReindexAll() (model.ReindexReport, error) {
+	return d.ReindexAllWithProgress(nil)
+}
+
+// ReindexAllWithProgress is ReindexAll reporting progress after each note.
+func (d *Domain) ReindexAllWithProgress(progress index.ProgressFunc) (model.ReindexReport, error) {
+	notes, tasks := d.store().NoteIDs(), d.indexedTaskIDs("")
+	report, err := d.manager.FullReindexWithProgress(progress)
+	if err != nil {
+		return model.ReindexReport{}, err
+	}
+	notes = append(notes, d.store().NoteIDs()...)
+	tasks = append(tasks, d.indexedTaskIDs("")...)
+	d.notify(changeSet(notes, tasks))
+	return report, nil
+}
+
+// OpenDaily returns the daily note for date. An indexed note dated date is
+// reused wherever it lives, whether its date comes from its path or its
+// frontmatter; otherwise the note at the vault's daily-note location is created
+// from the daily note template if needed and indexed. Weekly notes, dated
+// their week's Monday, are never taken for daily notes.
+func (d *Domain) OpenDaily(date time.Time) (model.Note, error) {
+	if note, ok := d.Daily(date); ok {
+		return note, nil
+	}
+	path, err := d.notePath(string(d.vault().NoteID(d.vault().DailyNotePath(date))))
+	if err != nil {
+		return model.Note{}, err
+	}
+	if _, err := d.vault().ReadNote(path); err != nil {
+		if !errors.Is(err, fs.ErrNotExist) {
+			return model.Note{}, err
+		}
+		content := RenderDailyNote(d.dailyNote, date)
+		if err := d.vault().WriteNote(path, content); err != nil {
+			return model.Note{}, fmt.Errorf("create daily note: %w", err)
+		}
+	}
+	return d.reindexNote(path)
+}
+
+// Daily returns the indexed daily note for date, as OpenDaily would find it,
+// without touching the vault. Notes spanning several days are not daily
+// notes.
+func (d *Domain) Daily(date time.Time) (model.Note, bool) {
+	day := model.DateRange{Start: date, End: date}
+	for _, meta := range d.store().ListNotesByDate(day) {
+		if _, weekly := vault.WeeklyNoteDate(string(meta.Path)); weekly {
+			continue
+		}
+		if meta.DateRange != nil && !meta.DateRange.Start.Equal(meta.DateRange.End) {
+			continue
+		}
+		if note, ok := d.store().GetNote(meta.ID); ok {
+			return note, true
+		}
+	}
+	return model.Note{}, false
+}
+
+// OpenWeekly returns the weekly note for the ISO week containing date, a
+// YYYY-Www.md file at the vault root, creating it from the weekly note
+// template if needed and indexing it.
+func (d *Domain) OpenWeekly(date time.Time) (model.Note, error) {
+	if note, ok := d.Weekly(date); ok {
+		return note, nil
+	}
+	path, err := d.notePath(string(d.vault().NoteID(d.vault().WeeklyNotePath(date))))
+	if err != nil {
+		return model.Note{}, err
+	}
+	if _, err := d.vault().ReadNote(path); err != nil {
+		if !errors.Is(err, fs.ErrNotExist) {
+			return model.Note{}, err
+		}
+		content := RenderDailyNote(d.weeklyNote, weekStart(date))
+		if err := d.vault().WriteNote(path, content); err != nil {
+			return model.Note{}, fmt.Errorf("create weekly note: %w", err)
+		}
+	}
+	return d.reindexNote(path)
+}
+
+// Weekly returns the indexed weekly note for the ISO week containing date,
+// without touching the vault.
+func (d *Domain) Weekly(date time.Time) (model.Note, bool) {
+	return d.store().GetNote(d.vault().NoteID(d.vault().WeeklyNotePath(date)))
+}
+
+// weekStart returns the Monday of the week containing the calendar day of t.
+func weekStart(t time.Time) time.Time {
+	day := time.Date(t.Year(), t.Month(), t.Day(), 0, 0, 0, 0, time.UTC)
+	return day.AddDate(0, 0, -((int(day.Weekday()) + 6) % 7))
+}
+
+// reindexNote reindexes the note at path and notifies subscribers of the
+// note and the tasks it defined before and after.
+func (d *Domain) reindexNote(path string) (model.Note, error) {
+	before := d.indexedTaskIDs(d.vault().NoteID(path))
+	note, err := d.manager.ReindexNote(path)
+	if err != nil {
+		return model.Note{}, err
+	}
+	d.notify(changeSet([]model.NoteID{note.ID}, append(before, d.indexedTaskIDs(note.ID)...)))
+	return note, nil
+}
+
+// Stats counts what is currently indexed.
+func (d *Domain) Stats() model.IndexStats {
+	return d.store().Stats()
+}
+
+// RenderDailyNote substitutes the {date}, {weekday} and {iso_week}
+// placeholders of a daily or weekly note template.
+func RenderDailyNote(template string, date time.Time) string {
+	year, week := date.ISOWeek()
+	return strings.NewReplacer(
+		"{date}", date.Format("2006-01-02"),
+		"{weekday}", date.Weekday().String(),
+		"{iso_week}", fmt.Sprintf("%04d-W%02d", year, week),
+	).Replace(template)
+}
+
+// ListTasks returns the tasks matching filter.
+func (d *Domain) ListTasks(filter index.TaskFilter) []model.Task {
+	return d.store().ListTasks(filter)
+}
+
+// TaskDetail returns a task together with its mentions and log entries.
+func (d *Domain) TaskDetail(id model.TaskID) (model.TaskDetail, bool) {
+	task, ok := d.store().GetTask(id)
+	if !ok {
+		return model.TaskDetail{}, false
+	}
+	return model.TaskDetail{
+		Task:       task,
+		Mentions:   d.store().GetMentionsForTask(id),
+		LogEntries: d.store().GetLogEntriesForTask(id),
+	}, true
+}
+
+// ItemsForTag returns the tasks and log entries carrying exactly tag. A
+// leading '#' is ignored.
+func (d *Domain) ItemsForTag(tag string) model.TagResult {
+	return d.store().ItemsForTag(strings.TrimPrefix(tag, "#"))
+}
+
+// TagGraph returns every tag with the IDs of the tasks and log entries
+// carrying it, so a client can cache the whole tag index in one call.
+func (d *Domain) TagGraph() map[string]model.TagItems {
+	return d.store().TagGraph()
+}
+
+// SearchTags returns the tags containing query, ignoring case and a leading
+// '#', prefix matches first, for autocompleting a tag as it is typed.
+func (d *Domain) SearchTags(query string, limit int) []string {
+	return d.store().SearchTags(strings.TrimPrefix(query, "#"), limit)
+}
+
+// ItemsUnderTag returns the items carrying prefix or any tag nested below it,
+// so "projects/app" covers "projects/app/ui" but not "projects/appx". Tags are
+// compared by the store's tag key. Items carrying several matching tags are
+// returned once.
+func (d *Domain) ItemsUnderTag(prefix string) model.TagResult {
+	prefix = strings.TrimSuffix(strings.TrimPrefix(prefix, "#"), "/")
+	result := model.TagResult{
+		Tag:        prefix,
+		Tasks:      []model.Task{},
+		LogEntries: []model.LogEntry{},
+	}
+
+	key := d.store().TagKey(prefix)
+	seenTasks := map[model.TaskID]bool{}
+	seenEntries := map[model.LogEntryID]bool{}
+	for _, tag := range d.store().ListTags() {
+		tagKey := d.store().TagKey(tag)
+		if tagKey != key && !strings.HasPrefix(tagKey, key+"/") {
+			continue
+		}
+		items := d.store().ItemsForTag(tag)
+		for _, task := range items.Tasks {
+			if !seenTasks[task.ID] {
+				seenTasks[task.ID] = true
+				result.Tasks = append(result.Tasks, task)
+			}
+		}
+		for _, entry := range items.LogEntries {
+			if !seenEntries[entry.ID] {
+				seenEntries[entry.ID] = true
+				result.LogEntries = append(result.LogEntries, entry)
+			}
+		}
+	}
+	return result
+}
+
+// NotesInRange returns the dated notes within r. With includeUndated set,
+// notes without a date follow them.
+func (d *Domain) NotesInRange(r model.DateRange, includeUndated bool) []model.NoteMeta {
+	notes := d.store().ListNotesByDate(r)
+	if includeUndated {
+		notes = append(notes, d.store().ListUndatedNotes()...)
+	}
+	return notes
+}
+
+// UndatedNotes returns the notes without a date, ordered by title.
+func (d *Domain) UndatedNotes() []model.NoteMeta {
+	return d.store().ListUndatedNotes()
+}
+
+// LogEntriesInRange returns the log entries of the notes dated within r,
+// ordered by date. With includeUndated set, the entries of undated notes
+// follow, ordered as UndatedNotes.
+func (d *Domain) LogEntriesInRange(r model.DateRange, includeUndated bool) []model.LogEntry {
+	entries := d.store().ListLogEntriesByDate(r)
+	if includeUndated {
+		for _, note := range d.store().ListUndatedNotes() {
+			entries = append(entries, d.store().GetLogEntriesForNote(note.ID)...)
+		}
+	}
+	return entries
+}
+
+// WeeklySummary reports the notes in r and their total word count, along
+// with the tasks created and completed within it, and task counts by status
+// as of the end of r.
+func (d *Domain) WeeklySummary(r model.DateRange) model.WeeklySummary {
+	summary := model.WeeklySummary{
+		Range:          r,
+		Notes:          d.store().ListNotesByDate(r),
+		NewTasks:       []model.Task{},
+		CompletedTasks: []model.Task{},
+	}
+	for task := range d.store().Tasks() {
+		if r.Contains(task.CreatedAt) {
+			summary.NewTasks = append(summary.NewTasks, task)
+		}
+		if task.CompletedAt != nil && r.Contains(*task.CompletedAt) {
+			summary.CompletedTasks = append(summary.CompletedTasks, task)
+		}
+		summary.StatusCounts.Add(statusAt(task, r.End))
+	}
+	slices.SortFunc(summary.NewTasks, compareTaskIDs)
+	slices.SortFunc(summary.CompletedTasks, compareTaskIDs)
+	for _, note := range summary.Notes {
+		summary.WordCount += note.WordCount
+	}
+	return summary
+}
+
+// compareTaskIDs orders tasks by ID, as IndexStore.ListTasks does.
+func compareTaskIDs(a, b model.Task) int {
+	return cmp.Compare(a.ID, b.ID)
+}
+
+// statusAt approximates a task's status at the end of day, returning "" for
+// tasks created later. A task completed later was still open then; other
+// status changes are not recorded, so the current status stands for them.
+func statusAt(task model.Task, day time.Time) model.TaskStatus {
+	if afterDay(task.CreatedAt, day) {
+		return ""
+	}
+	if task.Status == model.TaskStatusDone && task.CompletedAt != nil && afterDay(*task.CompletedAt, day) {
+		return model.TaskStatusTodo
+	}
+	return task.Status
+}
+
+// afterDay reports whether the calendar day of t falls after day.
+func afterDay(t, day time.Time) bool {
+	return !t.Before(day.AddDate(0, 0, 1))
+}
+
+// Dashboard reports current task counts by status, note and tag totals, and
+// the tasks created and completed in the Monday–Sunday week containing today.
+func (d *Domain) Dashboard(today time.Time) model.Dashboard {
+	start := weekStart(today)
+	stats := d.store().Stats()
+	dashboard := model.Dashboard{
+		Week:  model.DateRange{Start: start, End: start.AddDate(0, 0, 6)},
+		Notes: stats.Notes,
+		Tags:  stats.Tags,
+	}
+	for task := range d.store().Tasks() {
+		dashboard.StatusCounts.Add(task.Status)
+		if dashboard.Week.Contains(task.CreatedAt) {
+			dashboard.CreatedThisWeek++
+		}
+		if task.CompletedAt != nil && dashboard.Week.Contains(*task.CompletedAt) {
+			dashboard.CompletedThisWeek++
+		}
+	}
+	return dashboard
+}
+
+// ReadNote returns an indexed note with its content. An id that is not a
+// note ID is tried as a frontmatter alias.
+func (d *Domain) ReadNote(id model.NoteID) (model.Note, bool) {
+	if note, ok := d.store().GetNote(id); ok {
+		return note, true
+	}
+	if aliased, ok := d.store().ResolveAlias(string(id)); ok {
+		return d.store().GetNote(aliased)
+	}
+	return model.Note{}, false
+}
+
+// NoteMeta returns an indexed note's metadata without its content.
+func (d *Domain) NoteMeta(id model.NoteID) (model.NoteMeta, bool) {
+	return d.store().GetNoteMeta(id)
+}
+
+// NoteContents returns the tasks and log entries extracted from a note.
+func (d *Domain) NoteContents(id model.NoteID) (model.NoteContents, bool) {
+	if _, ok := d.store().GetNoteMeta(id); !ok {
+		return model.NoteContents{}, false
+	}
+	return model.NoteContents{
+		NoteID:     id,
+		Tasks:      d.store().GetTasksForNote(id),
+		LogEntries: d.store().GetLogEntriesForNote(id),
+	}, true
+}
+
+// DefaultWordsPerMinute is the reading speed NoteStats assumes when none is
+// given.
+const DefaultWordsPerMinute = 200
+
+// NoteStats returns a note's word count and how many minutes it takes to
+// read at wpm words per minute, rounded up. A wpm of zero or less uses
+// DefaultWordsPerMinute.
+func (d *Domain) NoteStats(id model.NoteID, wpm int) (model.NoteStats, bool) {
+	meta, ok := d.store().GetNoteMeta(id)
+	if !ok {
+		return model.NoteStats{}, false
+	}
+	if wpm <= 0 {
+		wpm = DefaultWordsPerMinute
+	}
+	return model.NoteStats{
+		NoteID:         id,
+		WordCount:      meta.WordCount,
+		ReadingMinutes: (meta.WordCount + wpm - 1) / wpm,
+	}, true
+}
+
+// ListNotes returns the metadata of every indexed note, ordered by date then
+// title with undated notes last.
+func (d *Domain) ListNotes() []model.NoteMeta {
+	return d.store().ListNotes()
+}
+
+// RecentNotes returns the metadata of the notes most recently modified on
+// disk, newest first, capped at limit when positive. A note's modification
+// time is the one seen when it was last indexed.
+func (d *Domain) RecentNotes(limit int) []model.NoteMeta {
+	return d.store().ListRecentNotes(limit)
+}
+
+// NoteBacklinks returns the notes linking to the given note via [[wikilinks]].
+func (d *Domain) NoteBacklinks(id model.NoteID) ([]model.NoteRef, bool) {
+	if _, ok := d.store().GetNote(id); !ok {
+		return nil, false
+	}
+	return d.store().GetNoteBacklinks(id), true
+}
+
+// PreviewWrite reports how replacing the content of an indexed note would
+// change its tasks, log entries and tags, without touching the filesystem or
+// the index. Both versions are run through the indexing parser so the diff
+// reflects exactly what a write would index.
+func (d *Domain) PreviewWrite(id model.NoteID, content string) (model.WriteDiff, error) {
+	current, ok := d.store().GetNote(id)
+	if !ok {
+		return model.WriteDiff{}, fmt.Errorf("note %s: %w", id, ErrNotFound)
+	}
+	before, err := d.manager.Parser().Parse(current)
+	if err != nil {
+		return model.WriteDiff{}, fmt.Errorf("parse current note: %w", err)
+	}
+	proposed := current
+	proposed.Content = content
+	after, err := d.manager.Parser().Parse(proposed)
+	if err != nil {
+		return model.WriteDiff{}, fmt.Errorf("parse proposed content: %w", err)
+	}
+	return diffParsedNotes(before, after), nil
+}
+
+// WriteNote replaces the content of an indexed note on disk and reindexes it.
+// The note's path is resolved from its vault-relative ID, which must not
+// lead outside the vault.
+func (d *Domain) WriteNote(id model.NoteID, content string) (model.Note, error) {
+	path, err := d.notePath(string(id))
+	if err != nil {
+		return model.Note{}, err
+	}
+	if _, ok := d.store().GetNote(id); !ok {
+		return model.Note{}, fmt.Errorf("note %s: %w", id, ErrNotFound)
+	}
+	if err := d.vault().WriteNote(path, content); err != nil {
+		return model.Note{}, err
+	}
+	return d.reindexNote(path)
+}
+
+// SetTaskStatus rewrites the checkbox of a task's line in its note to show
+// status, using the parser's glyph for it, and reindexes the note. The task
+// must still be on the line it was indexed from, in a note that is valid
+// UTF-8.
+func (d *Domain) SetTaskStatus(id model.TaskID, status model.TaskStatus) (model.Task, error) {
+	task, ok := d.store().GetTask(id)
+	if !ok {
+		return model.Task{}, fmt.Errorf("task %s: %w", id, ErrNotFound)
+	}
+	note, ok := d.store().GetNote(task.NoteID)
+	if !ok {
+		return model.Task{}, fmt.Errorf("note %s: %w", task.NoteID, ErrNotFound)
+	}
+	if err := rewritable(note); err != nil {
+		return model.Task{}, err
+	}
+
+	lines := strings.Split(note.Content, "\n")
+	if task.Line < 1 || task.Line > len(lines) {
+		return model.Task{}, fmt.Errorf("task %s: line %d is outside its note; reindex and retry", id, task.Line)
+	}
+	updated, ok := parser.SetTaskLineStatus(lines[task.Line-1], id, status)
+	if !ok {
+		return model.Task{}, fmt.Errorf("task %s: not found on line %d; reindex and retry", id, task.Line)
+	}
+	lines[task.Line-1] = updated
+
+	path, err := d.notePath(string(note.ID))
+	if err != nil {
+		return model.Task{}, err
+	}
+	if err := d.vault().WriteNote(path, strings.Join(lines, "\n")); err != nil {
+		return model.Task{}, err
+	}
+	if _, err := d.reindexNote(path); err != nil {
+		return model.Task{}, err
+	}
+	task, ok = d.store().GetTask(id)
+	if !ok {
+		return model.Task{}, fmt.Errorf("task %s: %w", id, ErrNotFound)
+	}
+	return task, nil
+}
+
+// CreateNote writes a new note at rel, a slash-separated path relative to the
+// vault root, creating parent directories, and indexes it. Paths that are
+// absolute, escape the vault, or would never be indexed (no configured
+// extension, or a hidden or ignored component) fail with ErrInvalidPath; an
+// existing file fails with ErrExists.
+func (d *Domain) CreateNote(rel string, content string) (model.Note, error) {
+	path, err := d.notePath(rel)
+	if err != nil {
+		return model.Note{}, err
+	}
+	if !d.vault().Indexable(path) {
+		return model.Note{}, fmt.Errorf("note %s: %w", rel, ErrInvalidPath)
+	}
+	if _, err := d.vault().ReadNote(path); err == nil {
+		return model.Note{}, fmt.Errorf("note %s: %w", rel, ErrExists)
+	} else if !errors.Is(err, fs.ErrNotExist) {
+		return model.Note{}, err
+	}
+	if err := d.vault().WriteNote(path, content); err != nil {
+		return model.Note{}, err
+	}
+	return d.reindexNote(path)
+}
+
+// rewritable fails with ErrLossy for a note read from a file that was not
+// valid UTF-8. Every write deriving new content from a note's indexed content
+// checks it first.
+func rewritable(note model.Note) error {
+	if note.Lossy {
+		return fmt.Errorf("note %s: %w", note.ID, ErrLossy)
+	}
+	return nil
+}
+
+// notePath resolves a vault-relative note path or ID to an absolute path.
+// Every write goes through it: absolute paths, paths whose ".." segments
+// lead outside the vault root, and paths whose directory is reached through
+// a symlink pointing outside it fail with ErrInvalidPath.
+func (d *Domain) notePath(rel string) (string, error) {
+	local := filepath.FromSlash(rel)
+	if !filepath.IsLocal(local) {
+		return "", fmt.Errorf("%q: %w", rel, ErrInvalidPath)
+	}
+	root := d.vault().RootPath()
+	path := filepath.Join(root, local)
+	inside, err := resolvesInside(root, filepath.Dir(path))
+	if err != nil {
+		return "", err
+	}
+	if !inside {
+		return "", fmt.Errorf("%q: %w", rel, ErrInvalidPath)
+	}
+	return path, nil
+}
+
+// resolvesInside reports whether dir stays under root once symlinks are
+// resolved. Directories that do not exist yet are judged by their deepest
+// existing ancestor, since that is where they would be created.
+func resolvesInside(root, dir string) (bool, error) {
+	resolvedRoot, err := filepath.EvalSymlinks(root)
+	if err != nil {
+		return false, fmt.Errorf("resolve vault root: %w", err)
+	}
+	for {
+		resolved, err := filepath.EvalSymlinks(dir)
+		if err == nil {
+			rel, err := filepath.Rel(resolvedRoot, resolved)
+			return err == nil && filepath.IsLocal(rel), nil
+		}
+		if !errors.Is(err, fs.ErrNotExist) {
+			return false, fmt.Errorf("resolve %s: %w", dir, err)
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return false, nil
+		}
+		dir = parent
+	}
+}
@@ -0,0 +1,254 @@
+package domain
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+// writeVault creates files, keyed by slash-separated path, in a fresh vault
+// directory and returns its root.
+func writeVault(t *testing.T, files map[string]string) string {
+	t.Helper()
+	root := t.TempDir()
+	for rel, content := range files {
+		writeFile(t, root, rel, content)
+	}
+	return root
+}
+
+// writeFile writes content to the slash-separated path rel under root,
+// creating parent directories.
+func writeFile(t *testing.T, root, rel, content string) {
+	t.Helper()
+	path := filepath.Join(root, filepath.FromSlash(rel))
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+		t.Fatal(err)
+	}
+}
+
+// readFile returns the content of the slash-separated path rel under root.
+func readFile(t *testing.T, root, rel string) string {
+	t.Helper()
+	raw, err := os.ReadFile(filepath.Join(root, filepath.FromSlash(rel)))
+	if err != nil {
+		t.Fatal(err)
+	}
+	return string(raw)
+}
+
+// openVault indexes the vault at root with the default vault, parser and
+// store.
+func openVault(t *testing.T, root string, opts ...Option) *Domain {
+	t.Helper()
+	d, _, err := OpenInMemory(root, nil, nil, nil, opts...)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return d
+}
+
+// testTime is a fixed instant for calls that take the current time.
+var testTime = time.Date(2025, time.April, 1, 9, 30, 0, 0, time.UTC)
+
+// latin1Note is a note saved as Latin-1: the "é" of "café" is the single
+// byte 0xE9, which is not valid UTF-8.
+const latin1Note = "# Caf\xe9\n\n## Tasks\n\n- [ ] [T-1] Order caf\xe9 beans #shop\n"
+
+func TestLossyNoteIsIndexed(t *testing.T) {
+	root := writeVault(t, map[string]string{"cafe.md": latin1Note})
+	d, report, err := OpenInMemory(root, nil, nil, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if report.Indexed != 1 || !slices.Equal(report.Lossy, []model.FilePath{model.FilePath(filepath.Join(root, "cafe.md"))}) {
+		t.Errorf("report = %+v, want cafe.md indexed and lossy", report)
+	}
+	task, ok := d.TaskDetail("T-1")
+	if !ok || task.Task.Title != "Order caf\uFFFD beans" {
+		t.Errorf("T-1 = %+v, %v, want the title with U+FFFD", task.Task, ok)
+	}
+	note, ok := d.ReadNote("cafe.md")
+	if !ok || !note.Lossy {
+		t.Errorf("ReadNote lossy = %v, %v, want true", note.Lossy, ok)
+	}
+	if meta, _ := d.NoteMeta("cafe.md"); !meta.Lossy {
+		t.Error("NoteMeta does not report the note as lossy")
+	}
+}
+
+func TestLossyNoteIsNotRewritten(t *testing.T) {
+	root := writeVault(t, map[string]string{"cafe.md": latin1Note})
+	d := openVault(t, root)
+
+	if _, err := d.SetTaskStatus("T-1", model.TaskStatusDone); !errors.Is(err, ErrLossy) {
+		t.Errorf("SetTaskStatus err = %v, want ErrLossy", err)
+	}
+	if _, err := d.RenameTag("shop", "shopping", false); !errors.Is(err, ErrLossy) {
+		t.Errorf("RenameTag err = %v, want ErrLossy", err)
+	}
+	d.ScratchAppend("remember milk", testTime)
+	if _, err := d.ScratchFlush("cafe.md"); !errors.Is(err, ErrLossy) {
+		t.Errorf("ScratchFlush err = %v, want ErrLossy", err)
+	}
+	if _, err := d.DuplicateNote("cafe.md", "copy.md", false, testTime); !errors.Is(err, ErrLossy) {
+		t.Errorf("DuplicateNote err = %v, want ErrLossy", err)
+	}
+	if got := readFile(t, root, "cafe.md"); got != latin1Note {
+		t.Errorf("note bytes changed to %q", got)
+	}
+}
+
+func TestWritesRejectPathTraversal(t *testing.T) {
+	parent := t.TempDir()
+	root := filepath.Join(parent, "vault")
+	writeFile(t, root, "note.md", "# Note\n")
+	d := openVault(t, root)
+
+	for _, rel := range []string{"../../etc/passwd", "../escaped.md", "notes/../../escaped.md", filepath.Join(parent, "escaped.md")} {
+		if _, err := d.CreateNote(rel, "# Escaped\n"); !errors.Is(err, ErrInvalidPath) {
+			t.Errorf("CreateNote(%q) err = %v, want ErrInvalidPath", rel, err)
+		}
+		if _, err := d.WriteNote(model.NoteID(rel), "# Escaped\n"); !errors.Is(err, ErrInvalidPath) {
+			t.Errorf("WriteNote(%q) err = %v, want ErrInvalidPath", rel, err)
+		}
+		if _, err := d.DuplicateNote("note.md", rel, false, testTime); !errors.Is(err, ErrInvalidPath) {
+			t.Errorf("DuplicateNote(%q) err = %v, want ErrInvalidPath", rel, err)
+		}
+	}
+	entries, err := os.ReadDir(parent)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(entries) != 1 || entries[0].Name() != "vault" {
+		t.Errorf("files outside the vault: %v", entries)
+	}
+}
+
+func TestWritesRejectSymlinkEscape(t *testing.T) {
+	outside := t.TempDir()
+	writeFile(t, outside, "shared.md", "# Shared\n")
+	root := writeVault(t, map[string]string{"note.md": "# Note\n"})
+	if err := os.Symlink(outside, filepath.Join(root, "linked")); err != nil {
+		t.Skipf("symlinks unsupported: %v", err)
+	}
+	d := openVault(t, root)
+
+	if _, err := d.CreateNote("linked/evil.md", "# Evil\n"); !errors.Is(err, ErrInvalidPath) {
+		t.Errorf("CreateNote through symlink err = %v, want ErrInvalidPath", err)
+	}
+	if _, err := d.CreateNote("linked/new/evil.md", "# Evil\n"); !errors.Is(err, ErrInvalidPath) {
+		t.Errorf("CreateNote below symlink err = %v, want ErrInvalidPath", err)
+	}
+	if _, err := d.DuplicateNote("note.md", "linked/copy.md", false, testTime); !errors.Is(err, ErrInvalidPath) {
+		t.Errorf("DuplicateNote through symlink err = %v, want ErrInvalidPath", err)
+	}
+	if _, err := d.WriteNote("linked/shared.md", "# Overwritten\n"); !errors.Is(err, ErrInvalidPath) {
+		t.Errorf("WriteNote through symlink err = %v, want ErrInvalidPath", err)
+	}
+	for _, name := range []string{"evil.md", "new", "copy.md"} {
+		if _, err := os.Lstat(filepath.Join(outside, name)); !errors.Is(err, os.ErrNotExist) {
+			t.Errorf("%s was written outside the vault", name)
+		}
+	}
+	if got := readFile(t, outside, "shared.md"); got != "# Shared\n" {
+		t.Errorf("shared.md = %q, want it untouched", got)
+	}
+
+	if _, err := d.CreateNote("inside/fresh.md", "# Fresh\n"); err != nil {
+		t.Errorf("CreateNote inside the vault: %v", err)
+	}
+}
+
+func TestCreateNoteRejectsUnindexedPaths(t *testing.T) {
+	root := writeVault(t, nil)
+	d, _, err := OpenInMemory(root, []vault.Option{vault.WithIgnorePatterns([]string{"archive", "*.tmp.md"})}, nil, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	for _, rel := range []string{"notes.txt", "README", ".hidden.md", ".obsidian/note.md", "archive/old.md", "draft.tmp.md"} {
+		if _, err := d.CreateNote(rel, "# Lost\n"); !errors.Is(err, ErrInvalidPath) {
+			t.Errorf("CreateNote(%q) err = %v, want ErrInvalidPath", rel, err)
+		}
+		if _, err := os.Lstat(filepath.Join(root, filepath.FromSlash(rel))); !errors.Is(err, os.ErrNotExist) {
+			t.Errorf("%s was written", rel)
+		}
+	}
+	note, err := d.CreateNote("projects/plan.md", "# Plan\n")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := d.NoteMeta(note.ID); !ok {
+		t.Errorf("created note %s is not indexed", note.ID)
+	}
+}
+
+func TestCreateNote(t *testing.T) {
+	root := writeVault(t, nil)
+	d := openVault(t, root)
+
+	note, err := d.CreateNote("projects/2025/plan.md", "# Plan\n\n## Tasks\n\n- [ ] [T-1] Draft\n")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if note.ID != "projects/2025/plan.md" || note.Title != "Plan" {
+		t.Errorf("created note = %s titled %q, want projects/2025/plan.md titled Plan", note.ID, note.Title)
+	}
+	if got := readFile(t, root, "projects/2025/plan.md"); got != "# Plan\n\n## Tasks\n\n- [ ] [T-1] Draft\n" {
+		t.Errorf("file content = %q", got)
+	}
+	if detail, ok := d.TaskDetail("T-1"); !ok || detail.Task.NoteID != note.ID {
+		t.Errorf("T-1 = %+v, %v, want it indexed from the new note", detail.Task, ok)
+	}
+	if _, err := d.CreateNote("projects/2025/plan.md", "# Again\n"); !errors.Is(err, ErrExists) {
+		t.Errorf("second CreateNote err = %v, want ErrExists", err)
+	}
+	if got := readFile(t, root, "projects/2025/plan.md"); strings.Contains(got, "Again") {
+		t.Error("existing note was overwritten")
+	}
+}
+
+func TestRelativeNoteIDsRoundTrip(t *testing.T) {
+	root := writeVault(t, map[string]string{"projects/plan.md": "# Plan\n"})
+	d := openVault(t, root)
+
+	note, ok := d.ReadNote("projects/plan.md")
+	if !ok {
+		t.Fatal("projects/plan.md not found by its relative ID")
+	}
+	if note.Path != model.FilePath(filepath.Join(root, "projects", "plan.md")) {
+		t.Errorf("path = %s, want it under the vault root", note.Path)
+	}
+	written, err := d.WriteNote(note.ID, "# Plan\n\nRevised.\n")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if written.ID != "projects/plan.md" {
+		t.Errorf("written note ID = %s, want projects/plan.md", written.ID)
+	}
+	if got := readFile(t, root, "projects/plan.md"); got != "# Plan\n\nRevised.\n" {
+		t.Errorf("file content = %q", got)
+	}
+	if reread, ok := openVault(t, root).ReadNote("projects/plan.md"); !ok || reread.Content != "# Plan\n\nRevised.\n" {
+		t.Errorf("reopened note = %+v, %v, want the revised content", reread, ok)
+	}
+
+	daily, err := d.OpenDaily(testTime)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if daily.ID != "2025-04-01.md" {
+		t.Errorf("daily note ID = %s, want 2025-04-01.md", daily.ID)
+	}
+}
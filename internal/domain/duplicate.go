@@ -0,0 +1,57 @@
+package domain
+
+import (
+	"fmt"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+// DuplicateNote copies the content of the note source to a new note at rel,
+// as CreateNote would, and returns the copy. With regenerateIDs set, every
+// task the source defines gets a fresh ID in the copy, and references to
+// those tasks within the copy follow, so the duplicate's tasks do not shadow
+// the source's. Fresh IDs are minted for at's year in the T-YYYY-NNN scheme;
+// if the parser was given a task ID pattern those IDs do not match, nothing
+// is written and DuplicateNote fails with ErrInvalidTaskID.
+// A source that is not valid UTF-8 fails with ErrLossy.
+func (d *Domain) DuplicateNote(source model.NoteID, rel string, regenerateIDs bool, at time.Time) (model.Note, error) {
+	note, ok := d.store().GetNote(source)
+	if !ok {
+		return model.Note{}, fmt.Errorf("note %s: %w", source, ErrNotFound)
+	}
+	if err := rewritable(note); err != nil {
+		return model.Note{}, err
+	}
+	content := note.Content
+	if regenerateIDs {
+		allocator := d.TaskIDAllocator(false)
+		ids := map[model.TaskID]model.TaskID{}
+		for _, task := range d.store().GetTasksForNote(source) {
+			ids[task.ID] = allocator.Next(at.Year())
+		}
+		if err := d.checkTaskIDs(ids); err != nil {
+			return model.Note{}, err
+		}
+		content = parser.RenameTaskIDs(content, ids)
+	}
+	return d.CreateNote(rel, content)
+}
+
+// checkTaskIDs fails with ErrInvalidTaskID unless every minted ID in ids
+// matches the parser's task ID pattern, so a rewrite cannot turn tasks into
+// plain text. Parsers that do not expose a pattern are trusted.
+func (d *Domain) checkTaskIDs(ids map[model.TaskID]model.TaskID) error {
+	p, ok := d.manager.Parser().(interface{ TaskIDPattern() parser.TaskIDPattern })
+	if !ok {
+		return nil
+	}
+	pattern := p.TaskIDPattern()
+	for _, id := range ids {
+		if !pattern.Matches(id) {
+			return fmt.Errorf("task ID %s does not match the task ID pattern %s: %w", id, pattern, ErrInvalidTaskID)
+		}
+	}
+	return nil
+}
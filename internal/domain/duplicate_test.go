@@ -0,0 +1,65 @@
+package domain
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+const sprintNote = "# Sprint\n\n## Tasks\n\n- [ ] [T-2025-001] Plan the sprint\n- [ ] [T-2025-002] Review the plan\n\n## Log\n\n- started [T-2025-001]\n"
+
+func TestDuplicateNoteRegeneratesTaskIDs(t *testing.T) {
+	root := writeVault(t, map[string]string{"sprint.md": sprintNote})
+	d := openVault(t, root)
+
+	if _, err := d.DuplicateNote("sprint.md", "next.md", true, testTime); err != nil {
+		t.Fatal(err)
+	}
+	want := strings.NewReplacer("T-2025-001", "T-2025-003", "T-2025-002", "T-2025-004").Replace(sprintNote)
+	if got := readFile(t, root, "next.md"); got != want {
+		t.Errorf("copy =\n%s\nwant\n%s", got, want)
+	}
+	if got := readFile(t, root, "sprint.md"); got != sprintNote {
+		t.Errorf("source changed to\n%s", got)
+	}
+	for id, note := range map[string]string{"T-2025-001": "sprint.md", "T-2025-003": "next.md", "T-2025-004": "next.md"} {
+		task, ok := d.TaskDetail(model.TaskID(id))
+		if !ok || string(task.Task.NoteID) != note {
+			t.Errorf("%s in %q, %v, want %s", id, task.Task.NoteID, ok, note)
+		}
+	}
+}
+
+func TestDuplicateNoteKeepsIDsByDefault(t *testing.T) {
+	root := writeVault(t, map[string]string{"sprint.md": sprintNote})
+	d := openVault(t, root)
+
+	if _, err := d.DuplicateNote("sprint.md", "copy.md", false, testTime); err != nil {
+		t.Fatal(err)
+	}
+	if got := readFile(t, root, "copy.md"); got != sprintNote {
+		t.Errorf("copy =\n%s\nwant the source unchanged", got)
+	}
+}
+
+func TestDuplicateNoteRejectsIDsOutsideTheTaskIDPattern(t *testing.T) {
+	const note = "# Sprint\n\n## Tasks\n\n- [ ] [PROJ-1] Plan the sprint\n"
+	root := writeVault(t, map[string]string{"sprint.md": note})
+	jira := parser.MustCompileTaskIDPattern(`[A-Z]+-[0-9]+`)
+	d, _, err := OpenInMemory(root, nil, []parser.Option{parser.WithTaskIDPattern(jira)}, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if _, err := d.DuplicateNote("sprint.md", "next.md", true, testTime); !errors.Is(err, ErrInvalidTaskID) {
+		t.Fatalf("err = %v, want ErrInvalidTaskID", err)
+	}
+	if _, err := os.Stat(filepath.Join(root, "next.md")); !os.IsNotExist(err) {
+		t.Errorf("next.md was written: %v", err)
+	}
+}
@@ -0,0 +1,33 @@
+package domain
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/weakphish/yapper/internal/index"
+)
+
+func ExampleOpenInMemory() {
+	root, err := os.MkdirTemp("", "vault")
+	if err != nil {
+		panic(err)
+	}
+	defer os.RemoveAll(root)
+	note := "# Monday\n\n## Tasks\n\n- [ ] [T-1] Write the report\n"
+	if err := os.WriteFile(filepath.Join(root, "2025-04-07.md"), []byte(note), 0o644); err != nil {
+		panic(err)
+	}
+
+	d, report, err := OpenInMemory(root, nil, nil, nil)
+	if err != nil {
+		panic(err)
+	}
+	fmt.Println("indexed", report.Indexed, "note")
+	for _, task := range d.ListTasks(index.TaskFilter{}) {
+		fmt.Println(task.ID, task.Title)
+	}
+	// Output:
+	// indexed 1 note
+	// T-1 Write the report
+}
@@ -0,0 +1,85 @@
+package domain
+
+import (
+	"cmp"
+	"slices"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+// FindDuplicateTaskIDs reports every task ID defined more than once, whether
+// across notes or within one. The index keeps only the last definition, so
+// the others are silently shadowed until fixed.
+func (d *Domain) FindDuplicateTaskIDs() []model.DuplicateTaskID {
+	duplicates := []model.DuplicateTaskID{}
+	for id, notes := range d.store().TaskDefinitions() {
+		if len(notes) > 1 {
+			duplicates = append(duplicates, model.DuplicateTaskID{TaskID: id, NoteIDs: notes})
+		}
+	}
+	slices.SortFunc(duplicates, func(a, b model.DuplicateTaskID) int {
+		return cmp.Compare(a.TaskID, b.TaskID)
+	})
+	return duplicates
+}
+
+// OrphanedMentions returns the task mentions whose task is defined nowhere
+// in the vault, typically a mistyped task ID, ordered by note ID then line.
+func (d *Domain) OrphanedMentions() []model.TaskMention {
+	orphaned := []model.TaskMention{}
+	for _, mention := range d.store().ListAllMentions() {
+		if _, ok := d.store().GetTask(mention.TaskID); !ok {
+			orphaned = append(orphaned, mention)
+		}
+	}
+	return orphaned
+}
+
+// LintVault collects read-only diagnostics about the vault: duplicate task
+// IDs, checkbox tasks without an ID, wikilinks that resolve to no note, task
+// references to tasks that do not exist, and the parser's warnings about
+// task lines it could not read.
+func (d *Domain) LintVault() model.LintReport {
+	return model.LintReport{
+		DuplicateTaskIDs: d.FindDuplicateTaskIDs(),
+		UntrackedTasks:   d.store().ListUntrackedTasks(),
+		UnresolvedLinks:  d.store().ListUnresolvedLinks(),
+		OrphanedMentions: d.OrphanedMentions(),
+		ParseWarnings:    d.store().ListParseWarnings(),
+	}
+}
+
+// IndexIntegrity reports inconsistencies in the index's internal structures.
+// With compact set, dangling entries are pruned and the remaining issues are
+// reported.
+func (d *Domain) IndexIntegrity(compact bool) []model.IntegrityIssue {
+	if compact {
+		d.store().Compact()
+	}
+	issues := d.store().VerifyIntegrity()
+	if issues == nil {
+		issues = []model.IntegrityIssue{}
+	}
+	return issues
+}
+
+// Validate parses content as a note would be parsed, without reading or
+// writing the vault or touching the index, and reports what it yields. The
+// result's tasks and entries carry no note ID.
+func (d *Domain) Validate(content string) (model.Validation, error) {
+	parsed, err := d.manager.Parser().Parse(model.Note{Content: content})
+	if err != nil {
+		return model.Validation{}, err
+	}
+	missing := parser.MissingSections(content)
+	if missing == nil {
+		missing = []string{}
+	}
+	return model.Validation{
+		Tasks:           parsed.Tasks,
+		LogEntries:      parsed.LogEntries,
+		Warnings:        parsed.Warnings,
+		MissingSections: missing,
+	}, nil
+}
@@ -0,0 +1,63 @@
+package domain
+
+import (
+	"os"
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestLintVaultReportsOrphanedMentions(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"tasks.md":      "# Tasks\n\n## Tasks\n\n- [ ] [T-1] Ship\n",
+		"2025-04-01.md": "# Tuesday\n\n## Log\n\n- worked on [T-1]\n- chased [T-999], a typo\n",
+	}))
+
+	orphaned := d.LintVault().OrphanedMentions
+	if len(orphaned) != 1 {
+		t.Fatalf("orphaned mentions = %+v, want only T-999", orphaned)
+	}
+	if m := orphaned[0]; m.TaskID != "T-999" || m.NoteID != "2025-04-01.md" || m.Line != 6 {
+		t.Errorf("orphaned mention = %+v, want T-999 on line 6 of 2025-04-01.md", m)
+	}
+}
+
+func TestValidateReportsWarningsWithoutTouchingVault(t *testing.T) {
+	root := writeVault(t, map[string]string{"tasks.md": "# Tasks\n\n## Tasks\n\n- [ ] [T-1] Ship\n"})
+	d := openVault(t, root)
+
+	validation, err := d.Validate("# Draft\n\n## Tasks\n\n" +
+		"- [ ] Buy milk\n" +
+		"- [ ] [T-9] Fine\n" +
+		"- [?] [T-10] Odd glyph\n" +
+		"- [ ] [T-9] Again\n")
+	if err != nil {
+		t.Fatal(err)
+	}
+	var kinds []model.ParseWarningKind
+	for _, w := range validation.Warnings {
+		kinds = append(kinds, w.Kind)
+	}
+	want := []model.ParseWarningKind{model.ParseWarningMissingTaskID, model.ParseWarningUnknownCheckbox, model.ParseWarningDuplicateTaskID}
+	if !slices.Equal(kinds, want) {
+		t.Errorf("warnings = %+v, want kinds %v", validation.Warnings, want)
+	}
+	if len(validation.Tasks) == 0 || validation.Tasks[0].ID != "T-9" || validation.Tasks[0].NoteID != "" {
+		t.Errorf("tasks = %+v, want T-9 first with no note ID", validation.Tasks)
+	}
+	if !slices.Equal(validation.MissingSections, []string{"Log"}) {
+		t.Errorf("missing sections = %v, want [Log]", validation.MissingSections)
+	}
+
+	if _, ok := d.store().GetTask("T-9"); ok {
+		t.Error("validated content was indexed")
+	}
+	entries, err := os.ReadDir(root)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(entries) != 1 {
+		t.Errorf("vault holds %d entries after validating, want only tasks.md", len(entries))
+	}
+}
@@ -0,0 +1,44 @@
+package domain
+
+import (
+	"slices"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// RecentMentions returns task mentions across the vault, most recently dated
+// note first, capped at limit when positive. Mentions in undated notes come
+// last; ties are ordered by note ID then line.
+func (d *Domain) RecentMentions(limit int) []model.TaskMention {
+	mentions := d.store().ListAllMentions()
+	dates := map[model.NoteID]*time.Time{}
+	for _, mention := range mentions {
+		if _, ok := dates[mention.NoteID]; !ok {
+			meta, _ := d.store().GetNoteMeta(mention.NoteID)
+			dates[mention.NoteID] = meta.Date
+		}
+	}
+	slices.SortStableFunc(mentions, func(a, b model.TaskMention) int {
+		return compareDatesDesc(dates[a.NoteID], dates[b.NoteID])
+	})
+	if limit > 0 && len(mentions) > limit {
+		mentions = mentions[:limit]
+	}
+	return mentions
+}
+
+// compareDatesDesc orders later dates first and nil dates last.
+func compareDatesDesc(a, b *time.Time) int {
+	switch {
+	case a == nil && b == nil:
+		return 0
+	case a == nil:
+		return 1
+	case b == nil:
+		return -1
+	default:
+		return b.Compare(*a)
+	}
+}
+
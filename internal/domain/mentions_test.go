@@ -0,0 +1,49 @@
+package domain
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// mentionSources lists the notes of mentions in order.
+func mentionSources(mentions []model.TaskMention) []model.NoteID {
+	var ids []model.NoteID
+	for _, mention := range mentions {
+		ids = append(ids, mention.NoteID)
+	}
+	return ids
+}
+
+func TestRecentMentionsDropDeletedNotes(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"tasks.md":      "# Tasks\n\n## Tasks\n\n- [ ] [T-1] Ship\n",
+		"2025-04-01.md": "# Tuesday\n\n## Log\n\n- started [T-1]\n",
+		"2025-04-03.md": "# Thursday\n\n## Log\n\n- finished [T-1]\n",
+		"ideas.md":      "# Ideas\n\n## Log\n\n- follow-up to [T-1]\n",
+	})
+	d := openVault(t, root)
+
+	if got, want := mentionSources(d.RecentMentions(0)), []model.NoteID{"2025-04-03.md", "2025-04-01.md", "ideas.md"}; !slices.Equal(got, want) {
+		t.Errorf("recent mentions from %v, want %v", got, want)
+	}
+	if got := mentionSources(d.RecentMentions(1)); !slices.Equal(got, []model.NoteID{"2025-04-03.md"}) {
+		t.Errorf("latest mention from %v, want [2025-04-03.md]", got)
+	}
+
+	if err := os.Remove(filepath.Join(root, "2025-04-03.md")); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := d.ReindexAll(); err != nil {
+		t.Fatal(err)
+	}
+	if got, want := mentionSources(d.RecentMentions(0)), []model.NoteID{"2025-04-01.md", "ideas.md"}; !slices.Equal(got, want) {
+		t.Errorf("after deleting 2025-04-03.md, mentions from %v, want %v", got, want)
+	}
+	if detail, _ := d.TaskDetail("T-1"); len(detail.Mentions) != 2 {
+		t.Errorf("T-1 mentions = %+v, want two", detail.Mentions)
+	}
+}
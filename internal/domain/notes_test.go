@@ -0,0 +1,181 @@
+package domain
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestNoteBacklinks(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"roadmap.md": "# Roadmap\n\nQuarterly goals.\n",
+		"monday.md":  "# Monday\n\nReviewed the [[Roadmap]] with the team.\n",
+		"tuesday.md": "# Tuesday\n\nSee [[roadmap#Goals|the plan]] and [[Missing]].\n",
+	}))
+
+	refs, ok := d.NoteBacklinks("roadmap.md")
+	if !ok {
+		t.Fatal("roadmap.md not found")
+	}
+	sources := map[model.NoteID]bool{}
+	for _, ref := range refs {
+		sources[ref.SourceID] = true
+		if ref.TargetID != "roadmap.md" || !ref.Resolved {
+			t.Errorf("ref = %+v, want it resolved to roadmap.md", ref)
+		}
+	}
+	if len(refs) != 2 || !sources["monday.md"] || !sources["tuesday.md"] {
+		t.Errorf("backlinks = %+v, want one each from monday.md and tuesday.md", refs)
+	}
+	if refs, _ := d.NoteBacklinks("monday.md"); len(refs) != 0 {
+		t.Errorf("monday.md backlinks = %+v, want none", refs)
+	}
+	if _, ok := d.NoteBacklinks("nowhere.md"); ok {
+		t.Error("backlinks of a missing note were found")
+	}
+}
+
+// noteIDs lists the IDs of notes in order.
+func noteIDs(notes []model.NoteMeta) []model.NoteID {
+	var ids []model.NoteID
+	for _, note := range notes {
+		ids = append(ids, note.ID)
+	}
+	return ids
+}
+
+func TestUndatedNotes(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"2025-04-01.md": "# Tuesday\n",
+		"2025-04-03.md": "# Thursday\n",
+		"ideas.md":      "# Zebra ideas\n",
+		"reference.md":  "# Alpha reference\n",
+	}))
+	april := model.DateRange{
+		Start: time.Date(2025, time.April, 1, 0, 0, 0, 0, time.UTC),
+		End:   time.Date(2025, time.April, 2, 0, 0, 0, 0, time.UTC),
+	}
+
+	if got := noteIDs(d.UndatedNotes()); !slices.Equal(got, []model.NoteID{"reference.md", "ideas.md"}) {
+		t.Errorf("undated notes = %v, want [reference.md ideas.md]", got)
+	}
+	if got := noteIDs(d.NotesInRange(april, false)); !slices.Equal(got, []model.NoteID{"2025-04-01.md"}) {
+		t.Errorf("notes in range = %v, want [2025-04-01.md]", got)
+	}
+	if got, want := noteIDs(d.NotesInRange(april, true)), []model.NoteID{"2025-04-01.md", "reference.md", "ideas.md"}; !slices.Equal(got, want) {
+		t.Errorf("notes in range with undated = %v, want %v", got, want)
+	}
+}
+
+func TestLogEntriesInRange(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"2025-04-02.md": "# Wednesday\n\n## Log\n\n- 14:00 retro\n- 09:00 standup\n",
+		"2025-04-01.md": "# Tuesday\n\n## Log\n\n- planning\n",
+		"2025-04-09.md": "# Next week\n\n## Log\n\n- too late\n",
+		"ideas.md":      "# Ideas\n\n## Log\n\n- someday\n",
+	}))
+	week := model.DateRange{
+		Start: time.Date(2025, time.March, 31, 0, 0, 0, 0, time.UTC),
+		End:   time.Date(2025, time.April, 6, 0, 0, 0, 0, time.UTC),
+	}
+	contents := func(entries []model.LogEntry) []string {
+		var got []string
+		for _, entry := range entries {
+			got = append(got, entry.Content)
+		}
+		return got
+	}
+
+	if got, want := contents(d.LogEntriesInRange(week, false)), []string{"planning", "09:00 standup", "14:00 retro"}; !slices.Equal(got, want) {
+		t.Errorf("entries = %q, want %q", got, want)
+	}
+	if got, want := contents(d.LogEntriesInRange(week, true)), []string{"planning", "09:00 standup", "14:00 retro", "someday"}; !slices.Equal(got, want) {
+		t.Errorf("entries with undated = %q, want %q", got, want)
+	}
+}
+
+func TestTouchedNoteBecomesMostRecent(t *testing.T) {
+	root := writeVault(t, map[string]string{"a.md": "# A\n", "b.md": "# B\n", "c.md": "# C\n"})
+	for i, name := range []string{"a.md", "b.md", "c.md"} {
+		modified := testTime.Add(time.Duration(i) * time.Hour)
+		if err := os.Chtimes(filepath.Join(root, name), modified, modified); err != nil {
+			t.Fatal(err)
+		}
+	}
+	d := openVault(t, root)
+	if got := noteIDs(d.RecentNotes(0)); !slices.Equal(got, []model.NoteID{"c.md", "b.md", "a.md"}) {
+		t.Fatalf("recent notes = %v, want [c.md b.md a.md]", got)
+	}
+
+	touched := testTime.Add(24 * time.Hour)
+	if err := os.Chtimes(filepath.Join(root, "a.md"), touched, touched); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := d.ReindexAll(); err != nil {
+		t.Fatal(err)
+	}
+	if got := noteIDs(d.RecentNotes(2)); !slices.Equal(got, []model.NoteID{"a.md", "c.md"}) {
+		t.Errorf("recent notes after touching a.md = %v, want [a.md c.md]", got)
+	}
+}
+
+func TestReadNoteByAlias(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"projects/yapper.md": "---\naliases: [Yapper, \"Note Daemon\"]\n---\n# Yapper\n",
+		"other.md":           "---\naliases:\n  - Side Project\n---\n# Other\n",
+	}))
+
+	for _, id := range []model.NoteID{"projects/yapper.md", "Yapper", "note daemon"} {
+		note, ok := d.ReadNote(id)
+		if !ok || note.ID != "projects/yapper.md" {
+			t.Errorf("ReadNote(%q) = %q, %v, want projects/yapper.md", id, note.ID, ok)
+		}
+	}
+	if note, ok := d.ReadNote("Side Project"); !ok || note.ID != "other.md" {
+		t.Errorf("ReadNote(Side Project) = %q, %v, want other.md", note.ID, ok)
+	}
+	if _, ok := d.ReadNote("Nobody"); ok {
+		t.Error("an unknown alias was resolved")
+	}
+}
+
+func TestMultiDayNotesInRange(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"trip.md":       "---\ndates: [2025-04-03, 2025-04-01]\n---\n# Trip\n",
+		"conference.md": "---\nstart: 2025-04-10\nend: 2025-04-12\n---\n# Conference\n",
+		"2025-04-05.md": "# Saturday\n",
+	}))
+	days := func(start, end int) model.DateRange {
+		return model.DateRange{
+			Start: time.Date(2025, time.April, start, 0, 0, 0, 0, time.UTC),
+			End:   time.Date(2025, time.April, end, 0, 0, 0, 0, time.UTC),
+		}
+	}
+
+	trip, ok := d.NoteMeta("trip.md")
+	if !ok || trip.DateRange == nil || !trip.DateRange.Start.Equal(days(1, 3).Start) || !trip.DateRange.End.Equal(days(1, 3).End) {
+		t.Fatalf("trip date range = %+v, want April 1 to 3", trip.DateRange)
+	}
+	for _, tc := range []struct {
+		query model.DateRange
+		want  []model.NoteID
+	}{
+		// Overlapping the end of the trip without containing it.
+		{days(3, 5), []model.NoteID{"trip.md", "2025-04-05.md"}},
+		{days(2, 2), []model.NoteID{"trip.md"}},
+		{days(6, 10), []model.NoteID{"conference.md"}},
+		{days(4, 4), nil},
+		{days(13, 30), nil},
+	} {
+		if got := noteIDs(d.NotesInRange(tc.query, false)); !slices.Equal(got, tc.want) {
+			t.Errorf("notes from April %d to %d = %v, want %v", tc.query.Start.Day(), tc.query.End.Day(), got, tc.want)
+		}
+	}
+	if daily, ok := d.Daily(days(2, 2).Start); ok {
+		t.Errorf("Daily(April 2) = %q, want the trip skipped", daily.ID)
+	}
+}
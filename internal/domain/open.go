@@ -0,0 +1,37 @@
+package domain
+
+import (
+	"fmt"
+
+	"github.com/weakphish/yapper/internal/index"
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+// OpenInMemory wires a FileSystemVault rooted at root, a RegexMarkdownParser
+// and an InMemoryIndexStore into a Domain and indexes the vault, so a
+// frontend can embed the domain directly instead of talking to the daemon:
+//
+//	d, _, err := domain.OpenInMemory("/path/to/vault", nil, nil, nil)
+//	if err != nil {
+//		return err
+//	}
+//	tasks := d.ListTasks(index.TaskFilter{})
+//
+// vaultOpts, parserOpts and storeOpts configure the vault, parser and
+// store; opts configure the Domain. The report lists notes the initial
+// reindex had to skip.
+func OpenInMemory(root string, vaultOpts []vault.Option, parserOpts []parser.Option, storeOpts []index.StoreOption, opts ...Option) (*Domain, model.ReindexReport, error) {
+	manager := index.NewVaultIndexManager(
+		vault.NewFileSystemVault(root, vaultOpts...),
+		parser.NewRegexMarkdownParser(parserOpts...),
+		index.NewInMemoryIndexStore(storeOpts...),
+	)
+	d := New(manager, opts...)
+	report, err := d.ReindexAll()
+	if err != nil {
+		return nil, model.ReindexReport{}, fmt.Errorf("index vault %s: %w", root, err)
+	}
+	return d, report, nil
+}
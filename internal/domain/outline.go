@@ -0,0 +1,27 @@
+package domain
+
+import "github.com/weakphish/yapper/internal/model"
+
+// NoteOutline returns the headings of a note as a tree. A heading nests under
+// the closest preceding heading of a lower level, so skipped levels ("#"
+// followed by "###") still nest.
+func (d *Domain) NoteOutline(id model.NoteID) ([]model.OutlineNode, bool) {
+	headings, ok := d.store().GetNoteOutline(id)
+	if !ok {
+		return nil, false
+	}
+	roots, _ := buildOutline(headings, 0)
+	return roots, true
+}
+
+// buildOutline consumes the headings deeper than parentLevel from the front of
+// headings, returning them as a forest and the unconsumed remainder.
+func buildOutline(headings []model.Heading, parentLevel int) ([]model.OutlineNode, []model.Heading) {
+	nodes := []model.OutlineNode{}
+	for len(headings) > 0 && headings[0].Level > parentLevel {
+		node := model.OutlineNode{Heading: headings[0]}
+		node.Children, headings = buildOutline(headings[1:], headings[0].Level)
+		nodes = append(nodes, node)
+	}
+	return nodes, headings
+}
@@ -0,0 +1,60 @@
+package domain
+
+import (
+	"fmt"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+// ScratchAppend adds text to the scratch buffer as a log bullet stamped with
+// the time of day of at, and returns the buffer. The scratch is held in
+// memory only, outside the index, until ScratchFlush writes it to a note;
+// it is lost if the daemon exits first. Line breaks in text are folded into
+// spaces so each capture stays one bullet.
+func (d *Domain) ScratchAppend(text string, at time.Time) []string {
+	text = strings.Join(strings.Fields(text), " ")
+	if text != "" {
+		d.scratch = append(d.scratch, fmt.Sprintf("- %s %s", at.Format("15:04"), text))
+	}
+	return d.ScratchRead()
+}
+
+// ScratchRead returns the bullets in the scratch buffer, oldest first.
+func (d *Domain) ScratchRead() []string {
+	lines := slices.Clone(d.scratch)
+	if lines == nil {
+		lines = []string{}
+	}
+	return lines
+}
+
+// ScratchFlush appends the scratch buffer to the Log section of the note id,
+// adding a "## Log" heading if the note does not end in one, reindexes the
+// note and empties the buffer. The buffer is kept if the note does not exist
+// (ErrNotFound), is not valid UTF-8 (ErrLossy) or cannot be written. An empty
+// buffer leaves the note as is.
+func (d *Domain) ScratchFlush(id model.NoteID) (model.Note, error) {
+	note, ok := d.store().GetNote(id)
+	if !ok {
+		return model.Note{}, fmt.Errorf("note %s: %w", id, ErrNotFound)
+	}
+	if len(d.scratch) == 0 {
+		return note, nil
+	}
+	if err := rewritable(note); err != nil {
+		return model.Note{}, err
+	}
+	path, err := d.notePath(string(id))
+	if err != nil {
+		return model.Note{}, err
+	}
+	if err := d.vault().WriteNote(path, parser.AppendToLog(note.Content, d.scratch)); err != nil {
+		return model.Note{}, err
+	}
+	d.scratch = nil
+	return d.reindexNote(path)
+}
@@ -0,0 +1,58 @@
+package domain
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+	"time"
+)
+
+func TestScratchLifecycle(t *testing.T) {
+	root := writeVault(t, map[string]string{"2025-04-01.md": "# Tuesday\n\n## Log\n\n- earlier\n"})
+	d := openVault(t, root)
+
+	if got := d.ScratchRead(); got == nil || len(got) != 0 {
+		t.Fatalf("fresh scratch = %#v, want empty", got)
+	}
+	d.ScratchAppend("first\n  thought", testTime)
+	d.ScratchAppend("   ", testTime)
+	got := d.ScratchAppend("second", testTime.Add(35*time.Minute))
+	want := []string{"- 09:30 first thought", "- 10:05 second"}
+	if !slices.Equal(got, want) || !slices.Equal(d.ScratchRead(), want) {
+		t.Fatalf("scratch = %q, want %q", d.ScratchRead(), want)
+	}
+
+	if _, err := d.ScratchFlush("missing.md"); !errors.Is(err, ErrNotFound) {
+		t.Errorf("flush to a missing note err = %v, want ErrNotFound", err)
+	}
+	if _, err := os.Stat(filepath.Join(root, "missing.md")); !errors.Is(err, os.ErrNotExist) {
+		t.Errorf("flush to a missing note created it: %v", err)
+	}
+	if !slices.Equal(d.ScratchRead(), want) {
+		t.Errorf("scratch after failed flush = %q, want it kept", d.ScratchRead())
+	}
+
+	note, err := d.ScratchFlush("2025-04-01.md")
+	if err != nil {
+		t.Fatal(err)
+	}
+	flushed := "# Tuesday\n\n## Log\n\n- earlier\n- 09:30 first thought\n- 10:05 second\n"
+	if note.Content != flushed || readFile(t, root, "2025-04-01.md") != flushed {
+		t.Errorf("note after flush =\n%s\nwant\n%s", readFile(t, root, "2025-04-01.md"), flushed)
+	}
+	if entries := d.store().GetLogEntriesForNote("2025-04-01.md"); len(entries) != 3 {
+		t.Errorf("indexed log entries = %+v, want 3", entries)
+	}
+	if got := d.ScratchRead(); len(got) != 0 {
+		t.Errorf("scratch after flush = %q, want empty", got)
+	}
+
+	if _, err := d.ScratchFlush("2025-04-01.md"); err != nil {
+		t.Fatal(err)
+	}
+	if readFile(t, root, "2025-04-01.md") != flushed {
+		t.Error("flushing an empty scratch changed the note")
+	}
+}
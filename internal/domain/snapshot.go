@@ -0,0 +1,83 @@
+package domain
+
+import (
+	"fmt"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// SnapshotVersion is the format version written by ExportSnapshot and
+// required by ImportSnapshot.
+const SnapshotVersion = 1
+
+// ExportSnapshot serializes everything indexed for the vault.
+func (d *Domain) ExportSnapshot() model.Snapshot {
+	return model.Snapshot{Version: SnapshotVersion, Notes: d.store().ExportNotes()}
+}
+
+// ImportSnapshot replaces the index with the contents of snapshot without
+// reading or writing the vault. A later reindex restores what is on disk.
+// A snapshot the index could not hold consistently is rejected as a whole,
+// leaving the index unchanged.
+func (d *Domain) ImportSnapshot(snapshot model.Snapshot) error {
+	if snapshot.Version != SnapshotVersion {
+		return fmt.Errorf("unsupported snapshot version %d, expected %d", snapshot.Version, SnapshotVersion)
+	}
+	if err := validateSnapshot(snapshot); err != nil {
+		return err
+	}
+	notes, tasks := d.store().NoteIDs(), d.indexedTaskIDs("")
+	for _, id := range d.store().NoteIDs() {
+		d.store().RemoveNote(id)
+	}
+	for _, parsed := range snapshot.Notes {
+		d.store().UpsertParsedNote(parsed)
+	}
+	notes = append(notes, d.store().NoteIDs()...)
+	tasks = append(tasks, d.indexedTaskIDs("")...)
+	d.notify(changeSet(notes, tasks))
+	return nil
+}
+
+// validateSnapshot checks that every note in snapshot has a unique ID and a
+// date consistent with its date range, and that its tasks, log entries and
+// mentions have IDs and belong to it.
+func validateSnapshot(snapshot model.Snapshot) error {
+	seen := make(map[model.NoteID]bool, len(snapshot.Notes))
+	for i, parsed := range snapshot.Notes {
+		note := parsed.Note
+		switch {
+		case note.ID == "":
+			return fmt.Errorf("note %d: id is empty", i)
+		case seen[note.ID]:
+			return fmt.Errorf("note %s: listed twice", note.ID)
+		case note.DateRange != nil && note.Date == nil:
+			return fmt.Errorf("note %s: date_range without date", note.ID)
+		case note.DateRange != nil && note.DateRange.End.Before(note.DateRange.Start):
+			return fmt.Errorf("note %s: date_range ends before it starts", note.ID)
+		}
+		seen[note.ID] = true
+		for _, task := range parsed.Tasks {
+			if task.ID == "" {
+				return fmt.Errorf("note %s: task on line %d has no id", note.ID, task.Line)
+			}
+			if task.NoteID != note.ID {
+				return fmt.Errorf("note %s: task %s belongs to note %q", note.ID, task.ID, task.NoteID)
+			}
+		}
+		for _, entry := range parsed.LogEntries {
+			if entry.ID == "" {
+				return fmt.Errorf("note %s: log entry on line %d has no id", note.ID, entry.Line)
+			}
+			if entry.NoteID != note.ID {
+				return fmt.Errorf("note %s: log entry %s belongs to note %q", note.ID, entry.ID, entry.NoteID)
+			}
+		}
+		for _, mention := range parsed.Mentions {
+			if mention.TaskID == "" || mention.NoteID != note.ID {
+				return fmt.Errorf("note %s: mention on line %d does not reference a task from this note", note.ID, mention.Line)
+			}
+		}
+	}
+	return nil
+}
@@ -0,0 +1,66 @@
+package domain
+
+import (
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestSnapshotRoundTrip(t *testing.T) {
+	source := openVault(t, writeVault(t, map[string]string{
+		"note.md": "# Note\n\n## Tasks\n\n- [ ] [T-1] Write the report #work\n\n## Log\n\n- drafted [T-1]\n",
+	}))
+	d := openVault(t, writeVault(t, nil))
+
+	if err := d.ImportSnapshot(source.ExportSnapshot()); err != nil {
+		t.Fatal(err)
+	}
+	task, ok := d.TaskDetail("T-1")
+	if !ok || task.Task.Title != "Write the report" || task.Task.NoteID != "note.md" {
+		t.Errorf("T-1 = %+v, %v, want the imported task", task.Task, ok)
+	}
+}
+
+func TestImportSnapshotRejectsInconsistentRecords(t *testing.T) {
+	day := time.Date(2025, time.April, 1, 0, 0, 0, 0, time.UTC)
+	note := func(id model.NoteID) model.Note {
+		return model.Note{ID: id, Path: model.FilePath(id), Title: "Note"}
+	}
+	tests := map[string]model.ParsedNote{
+		"empty note id": {Note: note("")},
+		"range without date": {Note: func() model.Note {
+			n := note("week.md")
+			n.DateRange = &model.DateRange{Start: day, End: day.AddDate(0, 0, 6)}
+			return n
+		}()},
+		"range ending before start": {Note: func() model.Note {
+			n := note("week.md")
+			n.Date = &day
+			n.DateRange = &model.DateRange{Start: day, End: day.AddDate(0, 0, -1)}
+			return n
+		}()},
+		"empty task id":        {Note: note("note.md"), Tasks: []model.Task{{NoteID: "note.md", Title: "Untitled"}}},
+		"task from elsewhere":  {Note: note("note.md"), Tasks: []model.Task{{ID: "T-1", NoteID: "other.md"}}},
+		"empty log entry id":   {Note: note("note.md"), LogEntries: []model.LogEntry{{NoteID: "note.md"}}},
+		"mention without task": {Note: note("note.md"), Mentions: []model.TaskMention{{NoteID: "note.md"}}},
+	}
+	for name, parsed := range tests {
+		t.Run(name, func(t *testing.T) {
+			d := openVault(t, writeVault(t, map[string]string{"kept.md": "# Kept\n"}))
+			snapshot := model.Snapshot{Version: SnapshotVersion, Notes: []model.ParsedNote{parsed}}
+			if err := d.ImportSnapshot(snapshot); err == nil {
+				t.Fatal("ImportSnapshot accepted the snapshot")
+			}
+			if _, ok := d.NoteMeta("kept.md"); !ok {
+				t.Error("rejected import changed the index")
+			}
+		})
+	}
+
+	d := openVault(t, writeVault(t, nil))
+	twice := model.Snapshot{Version: SnapshotVersion, Notes: []model.ParsedNote{{Note: note("a.md")}, {Note: note("a.md")}}}
+	if err := d.ImportSnapshot(twice); err == nil {
+		t.Error("ImportSnapshot accepted a note listed twice")
+	}
+}
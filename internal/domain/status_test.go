@@ -0,0 +1,37 @@
+package domain
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestSetTaskStatusRoundTrip(t *testing.T) {
+	root := writeVault(t, map[string]string{"note.md": "# Note\n\n## Tasks\n\n- [ ] [T-1] Write the report\n"})
+	d := openVault(t, root)
+
+	for _, status := range []model.TaskStatus{
+		model.TaskStatusInProgress,
+		model.TaskStatusBlocked,
+		model.TaskStatusDone,
+		model.TaskStatusTodo,
+	} {
+		if _, err := d.SetTaskStatus("T-1", status); err != nil {
+			t.Fatalf("set %s: %v", status, err)
+		}
+		if _, err := d.ReindexAll(); err != nil {
+			t.Fatal(err)
+		}
+		detail, ok := d.TaskDetail("T-1")
+		if !ok || detail.Task.Status != status {
+			t.Errorf("after setting %s and reindexing, status = %s", status, detail.Task.Status)
+		}
+		if reopened, ok := openVault(t, root).TaskDetail("T-1"); !ok || reopened.Task.Status != status {
+			t.Errorf("after setting %s and reopening, status = %s", status, reopened.Task.Status)
+		}
+	}
+	if content := readFile(t, root, "note.md"); !strings.Contains(content, "- [ ] [T-1] Write the report\n") {
+		t.Errorf("note after returning to todo:\n%s", content)
+	}
+}
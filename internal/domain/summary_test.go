@@ -0,0 +1,79 @@
+package domain
+
+import (
+	"encoding/json"
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+const backlogNote = "# Backlog\n\n## Tasks\n\n" +
+	"- [ ] [T-1] Long open ➕2025-03-20\n" +
+	"- [/] [T-2] Started this week ➕2025-04-01\n" +
+	"- [!] [T-3] Waiting on legal ➕2025-03-25\n" +
+	"- [x] [T-4] Shipped this week ➕2025-03-01 ✅2025-04-02\n" +
+	"- [x] [T-5] Shipped later ➕2025-03-01 ✅2025-04-10\n" +
+	"- [ ] [T-6] Not yet created ➕2025-04-20\n"
+
+func TestWeeklySummaryStatusCounts(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{"backlog.md": backlogNote}))
+	week := model.DateRange{
+		Start: time.Date(2025, time.March, 31, 0, 0, 0, 0, time.UTC),
+		End:   time.Date(2025, time.April, 6, 0, 0, 0, 0, time.UTC),
+	}
+
+	summary := d.WeeklySummary(week)
+	if got := idsOf(summary.NewTasks); !slices.Equal(got, []model.TaskID{"T-2"}) {
+		t.Errorf("new tasks = %v, want [T-2]", got)
+	}
+	if got := idsOf(summary.CompletedTasks); !slices.Equal(got, []model.TaskID{"T-4"}) {
+		t.Errorf("completed tasks = %v, want [T-4]", got)
+	}
+	want := model.StatusCounts{Todo: 2, InProgress: 1, Blocked: 1, Done: 1}
+	if summary.StatusCounts != want {
+		t.Errorf("status counts = %+v, want %+v", summary.StatusCounts, want)
+	}
+
+	encoded, err := json.Marshal(summary.StatusCounts)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if string(encoded) != `{"todo":2,"in_progress":1,"blocked":1,"done":1}` {
+		t.Errorf("status counts encode as %s", encoded)
+	}
+}
+
+func TestDashboardWeeklyCounts(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{
+		"work.md": "# Work\n\n## Tasks\n\n" +
+			"- [ ] [T-1] Created Monday ➕2025-03-31 #api\n" +
+			"- [/] [T-2] Created Sunday ➕2025-04-06 #api #ops\n" +
+			"- [x] [T-3] Old, finished Wednesday ➕2025-03-20 ✅2025-04-02\n" +
+			"- [x] [T-4] Finished last Sunday ➕2025-03-20 ✅2025-03-30\n",
+		"home.md": "# Home\n\n## Tasks\n\n" +
+			"- [!] [T-5] Created next Monday ➕2025-04-07 #home\n" +
+			"- [x] [T-6] Created and finished this week ➕2025-04-01 ✅2025-04-03\n",
+	}))
+
+	got := d.Dashboard(time.Date(2025, time.April, 2, 15, 0, 0, 0, time.UTC))
+	want := model.Dashboard{
+		Week: model.DateRange{
+			Start: time.Date(2025, time.March, 31, 0, 0, 0, 0, time.UTC),
+			End:   time.Date(2025, time.April, 6, 0, 0, 0, 0, time.UTC),
+		},
+		StatusCounts:      model.StatusCounts{Todo: 1, InProgress: 1, Blocked: 1, Done: 3},
+		Notes:             2,
+		Tags:              3,
+		CreatedThisWeek:   3,
+		CompletedThisWeek: 2,
+	}
+	if !got.Week.Start.Equal(want.Week.Start) || !got.Week.End.Equal(want.Week.End) {
+		t.Errorf("week = %v to %v, want %v to %v", got.Week.Start, got.Week.End, want.Week.Start, want.Week.End)
+	}
+	got.Week = want.Week
+	if got != want {
+		t.Errorf("dashboard = %+v, want %+v", got, want)
+	}
+}
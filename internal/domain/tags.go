@@ -0,0 +1,107 @@
+package domain
+
+import (
+	"cmp"
+	"fmt"
+	"slices"
+	"strings"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+// TagCooccurrence counts, over every task and log entry carrying tag, the
+// other tags appearing alongside it. Results are ordered by count, most
+// frequent first, then alphabetically, and capped at limit when positive.
+// Tags are compared by the store's tag key and the queried tag is excluded.
+func (d *Domain) TagCooccurrence(tag string, limit int) []model.TagCount {
+	items := d.store().ItemsForTag(strings.TrimPrefix(tag, "#"))
+	key := d.store().TagKey(strings.TrimPrefix(tag, "#"))
+
+	counts := map[string]int{}
+	display := map[string]string{}
+	count := func(tags []string) {
+		seen := map[string]bool{key: true}
+		for _, t := range tags {
+			k := d.store().TagKey(t)
+			if seen[k] {
+				continue
+			}
+			seen[k] = true
+			counts[k]++
+			if _, ok := display[k]; !ok {
+				display[k] = t
+			}
+		}
+	}
+	for _, task := range items.Tasks {
+		count(task.Tags)
+	}
+	for _, entry := range items.LogEntries {
+		count(entry.Tags)
+	}
+
+	result := make([]model.TagCount, 0, len(counts))
+	for k, n := range counts {
+		result = append(result, model.TagCount{Tag: display[k], Count: n})
+	}
+	slices.SortFunc(result, func(a, b model.TagCount) int {
+		return cmp.Or(cmp.Compare(b.Count, a.Count), cmp.Compare(a.Tag, b.Tag))
+	})
+	if limit > 0 && len(result) > limit {
+		result = result[:limit]
+	}
+	return result
+}
+
+// RenameTag rewrites every "#from" tag in the vault to "#to", writing each
+// affected note back to disk and reindexing it, and returns how many notes
+// changed. Tags are compared by the store's tag key, and only whole tags
+// match: renaming "old" leaves "#oldish" alone.
+// With children set, nested tags such as "#from/sub" are renamed too. Both
+// names must be valid tags, or ErrInvalidTag is returned. If a note carrying
+// the tag is not valid UTF-8, ErrLossy is returned before any note is
+// written.
+func (d *Domain) RenameTag(from, to string, children bool) (int, error) {
+	from = strings.TrimPrefix(from, "#")
+	to = strings.TrimPrefix(to, "#")
+	for _, name := range []string{from, to} {
+		if !parser.ValidTag(name) {
+			return 0, fmt.Errorf("%q: %w", name, ErrInvalidTag)
+		}
+	}
+
+	var ids []model.NoteID
+	contents := map[model.NoteID]string{}
+	for _, id := range d.store().NoteIDs() {
+		note, ok := d.store().GetNote(id)
+		if !ok {
+			continue
+		}
+		content, n := parser.RenameTag(note.Content, from, to, children, d.store().TagKey)
+		if n == 0 {
+			continue
+		}
+		if err := rewritable(note); err != nil {
+			return 0, err
+		}
+		ids = append(ids, id)
+		contents[id] = content
+	}
+
+	changed := 0
+	for _, id := range ids {
+		path, err := d.notePath(string(id))
+		if err != nil {
+			return changed, err
+		}
+		if err := d.vault().WriteNote(path, contents[id]); err != nil {
+			return changed, err
+		}
+		if _, err := d.reindexNote(path); err != nil {
+			return changed, err
+		}
+		changed++
+	}
+	return changed, nil
+}
@@ -0,0 +1,131 @@
+package domain
+
+import (
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/index"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// idsOf lists the IDs of tasks, sorted.
+func idsOf(tasks []model.Task) []model.TaskID {
+	var ids []model.TaskID
+	for _, task := range tasks {
+		ids = append(ids, task.ID)
+	}
+	slices.Sort(ids)
+	return ids
+}
+
+const projectsNote = "# Projects\n\n## Tasks\n\n" +
+	"- [ ] [T-1] Plan the app #projects/app\n" +
+	"- [ ] [T-2] Sketch screens #projects/app/ui #projects/app\n" +
+	"- [ ] [T-3] Unrelated app #projects/appx\n" +
+	"- [ ] [T-4] Everything #projects\n" +
+	"\n## Log\n\n- paired on layout #Projects/App/UI\n"
+
+func TestItemsUnderTag(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{"projects.md": projectsNote}))
+
+	result := d.ItemsUnderTag("#projects/app/")
+	if got := idsOf(result.Tasks); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("tasks under projects/app = %v, want [T-1 T-2]", got)
+	}
+	if len(result.LogEntries) != 1 {
+		t.Errorf("log entries under projects/app = %+v, want the one tagged Projects/App/UI", result.LogEntries)
+	}
+	if got := idsOf(d.ItemsUnderTag("projects").Tasks); !slices.Equal(got, []model.TaskID{"T-1", "T-2", "T-3", "T-4"}) {
+		t.Errorf("tasks under projects = %v, want all four", got)
+	}
+	if got := d.ItemsUnderTag("proj").Tasks; len(got) != 0 {
+		t.Errorf("tasks under proj = %v, want none", idsOf(got))
+	}
+}
+
+func TestTagCooccurrence(t *testing.T) {
+	d := openVault(t, writeVault(t, map[string]string{"work.md": "# Work\n\n## Tasks\n\n" +
+		"- [ ] [T-1] Add endpoint #backend #api\n" +
+		"- [ ] [T-2] Page the on-call #Backend #API #zeta\n" +
+		"- [ ] [T-3] Deploy #api #backend #ops\n" +
+		"- [ ] [T-4] Write docs #docs\n" +
+		"\n## Log\n\n- load tested #backend #api #api\n"}))
+
+	want := []model.TagCount{{Tag: "api", Count: 4}, {Tag: "ops", Count: 1}, {Tag: "zeta", Count: 1}}
+	if got := d.TagCooccurrence("#backend", 0); !slices.Equal(got, want) {
+		t.Errorf("co-occurring with backend = %+v, want %+v", got, want)
+	}
+	if got := d.TagCooccurrence("api", 1); !slices.Equal(got, []model.TagCount{{Tag: "backend", Count: 4}}) {
+		t.Errorf("top co-occurring with api = %+v, want backend 4", got)
+	}
+	if got := d.TagCooccurrence("docs", 0); len(got) != 0 {
+		t.Errorf("co-occurring with docs = %+v, want none", got)
+	}
+}
+
+func TestRenameTag(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"a.md": "# A\n\n## Tasks\n\n- [ ] [T-1] Plan #old\n- [ ] [T-2] Near miss #oldish\n",
+		"b.md": "# B\n\n## Log\n\n- reviewed #old and #old/sub\n",
+		"c.md": "# C\n\nNothing tagged here, not even #older.\n",
+	})
+	d := openVault(t, root)
+
+	changed, err := d.RenameTag("#old", "new", false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if changed != 2 {
+		t.Errorf("changed %d notes, want 2", changed)
+	}
+	want := map[string]string{
+		"a.md": "# A\n\n## Tasks\n\n- [ ] [T-1] Plan #new\n- [ ] [T-2] Near miss #oldish\n",
+		"b.md": "# B\n\n## Log\n\n- reviewed #new and #old/sub\n",
+		"c.md": "# C\n\nNothing tagged here, not even #older.\n",
+	}
+	for rel, content := range want {
+		if got := readFile(t, root, rel); got != content {
+			t.Errorf("%s =\n%s\nwant\n%s", rel, got, content)
+		}
+	}
+	if got := idsOf(d.ItemsForTag("new").Tasks); !slices.Equal(got, []model.TaskID{"T-1"}) {
+		t.Errorf("tasks tagged new = %v, want [T-1]", got)
+	}
+	if got := d.ItemsForTag("old").Tasks; len(got) != 0 {
+		t.Errorf("tasks still tagged old: %v", idsOf(got))
+	}
+
+	if _, err := d.RenameTag("old", "new", true); err != nil {
+		t.Fatal(err)
+	}
+	if got := readFile(t, root, "b.md"); got != "# B\n\n## Log\n\n- reviewed #new and #new/sub\n" {
+		t.Errorf("b.md after renaming children =\n%s", got)
+	}
+	if _, err := d.RenameTag("old", "has space", false); !errors.Is(err, ErrInvalidTag) {
+		t.Errorf("invalid new tag err = %v, want ErrInvalidTag", err)
+	}
+}
+
+func TestTagOperationsFollowTheStoreNormalizer(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"a.md": "# A\n\n## Tasks\n\n- [ ] [T-1] Plan #Old #docs\n- [ ] [T-2] Write #old/sub #docs\n",
+	})
+	d, _, err := OpenInMemory(root, nil, nil, []index.StoreOption{index.WithTagNormalizer(index.ExactTag)})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if got := idsOf(d.ItemsUnderTag("old").Tasks); !slices.Equal(got, []model.TaskID{"T-2"}) {
+		t.Errorf("tasks under old = %v, want [T-2]", got)
+	}
+	if got := d.TagCooccurrence("docs", 0); len(got) != 2 {
+		t.Errorf("co-occurring with docs = %+v, want Old and old/sub apart", got)
+	}
+	if _, err := d.RenameTag("old", "new", true); err != nil {
+		t.Fatal(err)
+	}
+	if got, want := readFile(t, root, "a.md"), "# A\n\n## Tasks\n\n- [ ] [T-1] Plan #Old #docs\n- [ ] [T-2] Write #new/sub #docs\n"; got != want {
+		t.Errorf("a.md =\n%s\nwant\n%s", got, want)
+	}
+}
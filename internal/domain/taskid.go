@@ -0,0 +1,72 @@
+package domain
+
+import (
+	"fmt"
+	"regexp"
+	"strconv"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+var yearTaskIDRE = regexp.MustCompile(`^T-(\d{4})-(\d+)$`)
+
+// TaskIDAllocator mints sequential T-YYYY-NNN task IDs, such as T-2025-007,
+// that collide with no ID it was given or has already minted. Numbers are
+// zero-padded to three digits and grow wider past 999.
+type TaskIDAllocator struct {
+	taken    map[model.TaskID]bool
+	fillGaps bool
+}
+
+// NewTaskIDAllocator returns an allocator avoiding the IDs in taken. With
+// fillGaps set, Next returns the lowest free number for the year; otherwise
+// it returns one past the highest number in use.
+func NewTaskIDAllocator(taken []model.TaskID, fillGaps bool) *TaskIDAllocator {
+	a := &TaskIDAllocator{taken: make(map[model.TaskID]bool, len(taken)), fillGaps: fillGaps}
+	for _, id := range taken {
+		a.taken[id] = true
+	}
+	return a
+}
+
+// TaskIDAllocator returns an allocator avoiding every task ID indexed in the
+// vault.
+func (d *Domain) TaskIDAllocator(fillGaps bool) *TaskIDAllocator {
+	return NewTaskIDAllocator(d.taskIDs(), fillGaps)
+}
+
+// taskIDs lists the ID of every indexed task.
+func (d *Domain) taskIDs() []model.TaskID {
+	var ids []model.TaskID
+	for task := range d.store().Tasks() {
+		ids = append(ids, task.ID)
+	}
+	return ids
+}
+
+// Next returns a free ID for year and marks it taken.
+func (a *TaskIDAllocator) Next(year int) model.TaskID {
+	n := 1
+	if !a.fillGaps {
+		for id := range a.taken {
+			m := yearTaskIDRE.FindStringSubmatch(string(id))
+			if m == nil {
+				continue
+			}
+			if y, _ := strconv.Atoi(m[1]); y != year {
+				continue
+			}
+			if used, err := strconv.Atoi(m[2]); err == nil && used >= n {
+				n = used + 1
+			}
+		}
+	}
+	for {
+		id := model.TaskID(fmt.Sprintf("T-%04d-%03d", year, n))
+		if !a.taken[id] {
+			a.taken[id] = true
+			return id
+		}
+		n++
+	}
+}
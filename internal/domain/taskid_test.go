@@ -0,0 +1,42 @@
+package domain
+
+import (
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestTaskIDAllocatorFirstOfYear(t *testing.T) {
+	a := NewTaskIDAllocator([]model.TaskID{"T-1", "T-2024-041", "PROJ-7"}, false)
+	if got := a.Next(2025); got != "T-2025-001" {
+		t.Errorf("Next(2025) = %s, want T-2025-001", got)
+	}
+	if got := a.Next(2025); got != "T-2025-002" {
+		t.Errorf("second Next(2025) = %s, want T-2025-002", got)
+	}
+}
+
+func TestTaskIDAllocatorGap(t *testing.T) {
+	taken := []model.TaskID{"T-2025-001", "T-2025-003"}
+	if got := NewTaskIDAllocator(taken, false).Next(2025); got != "T-2025-004" {
+		t.Errorf("without fillGaps Next = %s, want T-2025-004", got)
+	}
+	if got := NewTaskIDAllocator(taken, true).Next(2025); got != "T-2025-002" {
+		t.Errorf("with fillGaps Next = %s, want T-2025-002", got)
+	}
+}
+
+func TestTaskIDAllocatorCollision(t *testing.T) {
+	a := NewTaskIDAllocator([]model.TaskID{"T-2025-001", "T-2025-002"}, true)
+	seen := map[model.TaskID]bool{"T-2025-001": true, "T-2025-002": true}
+	for range 5 {
+		id := a.Next(2025)
+		if seen[id] {
+			t.Fatalf("Next returned %s twice", id)
+		}
+		seen[id] = true
+	}
+	if got := NewTaskIDAllocator([]model.TaskID{"T-2025-999"}, false).Next(2025); got != "T-2025-1000" {
+		t.Errorf("Next past 999 = %s, want T-2025-1000", got)
+	}
+}
@@ -0,0 +1,75 @@
+package domain
+
+import (
+	"cmp"
+	"slices"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// TaskTimeline returns a task's history as one chronological list: its
+// creation, every log entry referencing it and, once done, its completion.
+//
+// Events are ordered by calendar day, then creation before log entries
+// before completion, then time of day, note and line. Log entries from
+// undated notes carry no time and come last.
+func (d *Domain) TaskTimeline(id model.TaskID) ([]model.TaskEvent, bool) {
+	task, ok := d.store().GetTask(id)
+	if !ok {
+		return nil, false
+	}
+
+	events := []model.TaskEvent{{
+		Kind:   model.TaskEventCreated,
+		Time:   task.CreatedAt,
+		NoteID: task.NoteID,
+		Line:   task.Line,
+	}}
+	for _, entry := range d.store().GetLogEntriesForTask(id) {
+		events = append(events, model.TaskEvent{
+			Kind:     model.TaskEventLogged,
+			Time:     entry.Timestamp,
+			NoteID:   entry.NoteID,
+			Line:     entry.Line,
+			LogEntry: &entry,
+		})
+	}
+	if task.CompletedAt != nil {
+		events = append(events, model.TaskEvent{
+			Kind:   model.TaskEventCompleted,
+			Time:   *task.CompletedAt,
+			NoteID: task.NoteID,
+			Line:   task.Line,
+		})
+	}
+
+	slices.SortStableFunc(events, compareTaskEvents)
+	return events, true
+}
+
+var taskEventRank = map[model.TaskEventKind]int{
+	model.TaskEventCreated:   0,
+	model.TaskEventLogged:    1,
+	model.TaskEventCompleted: 2,
+}
+
+func compareTaskEvents(a, b model.TaskEvent) int {
+	if a.Time.IsZero() != b.Time.IsZero() {
+		if a.Time.IsZero() {
+			return 1
+		}
+		return -1
+	}
+	return cmp.Or(
+		calendarDay(a.Time).Compare(calendarDay(b.Time)),
+		cmp.Compare(taskEventRank[a.Kind], taskEventRank[b.Kind]),
+		a.Time.Compare(b.Time),
+		cmp.Compare(a.NoteID, b.NoteID),
+		cmp.Compare(a.Line, b.Line),
+	)
+}
+
+func calendarDay(t time.Time) time.Time {
+	return time.Date(t.Year(), t.Month(), t.Day(), 0, 0, 0, 0, time.UTC)
+}
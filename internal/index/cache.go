@@ -0,0 +1,94 @@
+package index
+
+import (
+	"container/list"
+	"crypto/sha256"
+	"sync"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// DefaultParseCacheSize is the number of parsed notes VaultIndexManager keeps
+// unless configured otherwise.
+const DefaultParseCacheSize = 1024
+
+// parseCache is a bounded, least-recently-used cache of parse results keyed
+// by note path. Each entry is only reused for a note with the modification
+// time, size and content hash it was parsed from. The modification time is
+// part of the key because the parser derives task timestamps from it, so a
+// touched note is parsed again. It is safe for concurrent use.
+type parseCache struct {
+	mu       sync.Mutex
+	capacity int
+	order    *list.List
+	entries  map[string]*list.Element
+}
+
+type cacheEntry struct {
+	path    string
+	modTime time.Time
+	size    int
+	hash    [sha256.Size]byte
+	parsed  model.ParsedNote
+}
+
+func newParseCache(capacity int) *parseCache {
+	return &parseCache{
+		capacity: capacity,
+		order:    list.New(),
+		entries:  map[string]*list.Element{},
+	}
+}
+
+// contentHash fingerprints a note's content. The rest of what the parser
+// reads, the note's ID, title and date, follows from its path and content.
+func contentHash(content string) [sha256.Size]byte {
+	return sha256.Sum256([]byte(content))
+}
+
+// get returns the cached parse of the note at path if note has the
+// modification time and content it was parsed from.
+func (c *parseCache) get(path string, note model.Note) (model.ParsedNote, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	elem, ok := c.entries[path]
+	if !ok {
+		return model.ParsedNote{}, false
+	}
+	entry := elem.Value.(*cacheEntry)
+	if !entry.modTime.Equal(note.ModifiedAt) || entry.size != len(note.Content) || entry.hash != contentHash(note.Content) {
+		return model.ParsedNote{}, false
+	}
+	c.order.MoveToFront(elem)
+	parsed := entry.parsed
+	parsed.Note = note
+	return parsed, true
+}
+
+func (c *parseCache) put(path string, note model.Note, parsed model.ParsedNote) {
+	if c.capacity <= 0 {
+		return
+	}
+	entry := &cacheEntry{path: path, modTime: note.ModifiedAt, size: len(note.Content), hash: contentHash(note.Content), parsed: parsed}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if elem, ok := c.entries[path]; ok {
+		elem.Value = entry
+		c.order.MoveToFront(elem)
+		return
+	}
+	c.entries[path] = c.order.PushFront(entry)
+	for c.order.Len() > c.capacity {
+		oldest := c.order.Back()
+		c.order.Remove(oldest)
+		delete(c.entries, oldest.Value.(*cacheEntry).path)
+	}
+}
+
+func (c *parseCache) clear() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.order.Init()
+	clear(c.entries)
+}
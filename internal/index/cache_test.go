@@ -0,0 +1,73 @@
+package index
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+// countingParser counts the notes it parses.
+type countingParser struct {
+	parser.NoteParser
+	parses int
+}
+
+func (p *countingParser) Parse(note model.Note) (model.ParsedNote, error) {
+	p.parses++
+	return p.NoteParser.Parse(note)
+}
+
+func TestParseCacheSkipsUnchangedNotes(t *testing.T) {
+	root := t.TempDir()
+	path := filepath.Join(root, "note.md")
+	if err := os.WriteFile(path, []byte("# Note\n\n## Tasks\n\n- [ ] [T-1] Write\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	p := &countingParser{NoteParser: parser.NewRegexMarkdownParser()}
+	m := NewVaultIndexManager(vault.NewFileSystemVault(root), p, NewInMemoryIndexStore())
+	if _, err := m.FullReindex(); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := m.ReindexNote(path); err != nil {
+		t.Fatal(err)
+	}
+	if p.parses != 1 {
+		t.Errorf("unchanged note parsed %d times, want 1", p.parses)
+	}
+
+	touched := time.Now().Add(time.Hour).Truncate(time.Second)
+	if err := os.Chtimes(path, touched, touched); err != nil {
+		t.Fatal(err)
+	}
+	note, err := m.ReindexNote(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if p.parses != 2 {
+		t.Errorf("touched note parsed %d times in total, want 2", p.parses)
+	}
+	if !note.ModifiedAt.Equal(touched) {
+		t.Errorf("ModifiedAt = %v, want the touched time %v", note.ModifiedAt, touched)
+	}
+	if task, _ := m.Store().GetTask("T-1"); !task.UpdatedAt.Equal(touched) {
+		t.Errorf("T-1 UpdatedAt = %v, want the touched time %v", task.UpdatedAt, touched)
+	}
+
+	if err := os.WriteFile(path, []byte("# Note\n\n## Tasks\n\n- [x] [T-1] Write\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := m.ReindexNote(path); err != nil {
+		t.Fatal(err)
+	}
+	if p.parses != 3 {
+		t.Errorf("edited note parsed %d times in total, want 3", p.parses)
+	}
+	if task, _ := m.Store().GetTask("T-1"); task.Status != model.TaskStatusDone {
+		t.Errorf("T-1 status = %s, want the edit indexed", task.Status)
+	}
+}
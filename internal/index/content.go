@@ -0,0 +1,88 @@
+package index
+
+import (
+	"container/list"
+	"sync"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// ContentLoader reads the current content of the note at path.
+type ContentLoader func(path string) (string, error)
+
+// contentCache is a bounded, least-recently-used cache of note content keyed
+// by note ID, for stores that do not keep every note's content resident.
+// Content that was evicted, or never cached, is read again through load. It
+// is safe for concurrent use.
+type contentCache struct {
+	mu       sync.Mutex
+	capacity int
+	order    *list.List
+	entries  map[model.NoteID]*list.Element
+	load     ContentLoader
+}
+
+type contentEntry struct {
+	id      model.NoteID
+	content string
+}
+
+func newContentCache(capacity int) *contentCache {
+	return &contentCache{
+		capacity: capacity,
+		order:    list.New(),
+		entries:  map[model.NoteID]*list.Element{},
+	}
+}
+
+// get returns the content of the note id, stored at path, loading and
+// caching it when it is not cached.
+func (c *contentCache) get(id model.NoteID, path string) (string, bool) {
+	c.mu.Lock()
+	if elem, ok := c.entries[id]; ok {
+		c.order.MoveToFront(elem)
+		content := elem.Value.(*contentEntry).content
+		c.mu.Unlock()
+		return content, true
+	}
+	load := c.load
+	c.mu.Unlock()
+
+	if load == nil {
+		return "", false
+	}
+	content, err := load(path)
+	if err != nil {
+		return "", false
+	}
+	c.put(id, content)
+	return content, true
+}
+
+func (c *contentCache) put(id model.NoteID, content string) {
+	if c.capacity <= 0 {
+		return
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if elem, ok := c.entries[id]; ok {
+		elem.Value = &contentEntry{id: id, content: content}
+		c.order.MoveToFront(elem)
+		return
+	}
+	c.entries[id] = c.order.PushFront(&contentEntry{id: id, content: content})
+	for c.order.Len() > c.capacity {
+		oldest := c.order.Back()
+		c.order.Remove(oldest)
+		delete(c.entries, oldest.Value.(*contentEntry).id)
+	}
+}
+
+func (c *contentCache) remove(id model.NoteID) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if elem, ok := c.entries[id]; ok {
+		c.order.Remove(elem)
+		delete(c.entries, id)
+	}
+}
@@ -0,0 +1,44 @@
+package index
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+func TestContentCacheStaysBounded(t *testing.T) {
+	root := t.TempDir()
+	for i := range 50 {
+		name := fmt.Sprintf("note-%02d.md", i)
+		if err := os.WriteFile(filepath.Join(root, name), []byte(fmt.Sprintf("# Note %d\n", i)), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	s := NewInMemoryIndexStore(WithContentCache(5))
+	m := NewVaultIndexManager(vault.NewFileSystemVault(root), parser.NewRegexMarkdownParser(), s)
+	if _, err := m.FullReindex(); err != nil {
+		t.Fatal(err)
+	}
+
+	if cached := s.content.order.Len(); cached > 5 || len(s.noteContent) != 0 {
+		t.Fatalf("%d notes cached and %d resident, want at most 5 and none", cached, len(s.noteContent))
+	}
+	if got := s.Stats().Notes; got != 50 {
+		t.Errorf("indexed %d notes, want all 50", got)
+	}
+	for i := range 50 {
+		id := fmt.Sprintf("note-%02d.md", i)
+		note, ok := s.GetNote(model.NoteID(id))
+		if want := fmt.Sprintf("# Note %d\n", i); !ok || note.Content != want {
+			t.Errorf("%s content = %q, %v, want %q", id, note.Content, ok, want)
+		}
+		if cached := s.content.order.Len(); cached > 5 {
+			t.Fatalf("%d notes cached after reading %s, want at most 5", cached, id)
+		}
+	}
+}
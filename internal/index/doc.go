@@ -1,3 +1,3 @@
-// Package index will contain the in-memory and persistent indexing logic.
+// Package index contains the indexing layer and the manager that keeps it in
+// sync with the vault.
 package index
-
@@ -0,0 +1,160 @@
+package index
+
+import (
+	"cmp"
+	"fmt"
+	"slices"
+	"strings"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// VerifyIntegrity implements IndexStore. It reports reverse-index entries
+// that point at missing or mismatched entities, empty entries, per-note data
+// for unknown notes, and entities their reverse indexes cannot reach.
+func (s *InMemoryIndexStore) VerifyIntegrity() []model.IntegrityIssue {
+	issues := s.auditReverseIndexes(false)
+	issues = append(issues, s.auditReachability()...)
+	slices.SortFunc(issues, func(a, b model.IntegrityIssue) int {
+		return cmp.Or(cmp.Compare(a.Index, b.Index), cmp.Compare(a.Key, b.Key), cmp.Compare(a.Problem, b.Problem))
+	})
+	return issues
+}
+
+// Compact implements IndexStore, pruning the dangling and empty entries
+// VerifyIntegrity would report and returning how many were removed.
+// Unreachable entities are left alone since only a reindex can restore them.
+func (s *InMemoryIndexStore) Compact() int {
+	return len(s.auditReverseIndexes(true))
+}
+
+// auditReverseIndexes finds references to missing entities, removing them
+// when prune is set.
+func (s *InMemoryIndexStore) auditReverseIndexes(prune bool) []model.IntegrityIssue {
+	var issues []model.IntegrityIssue
+	noteLive := func(id model.NoteID) bool {
+		_, ok := s.notes[id]
+		return ok
+	}
+
+	auditIndex(&issues, "tags_to_tasks", s.tagsToTasks, prune, func(key string, id model.TaskID) bool {
+		task, ok := s.tasks[id]
+		return ok && slices.ContainsFunc(task.Tags, func(t string) bool { return s.tagKey(t) == key })
+	})
+	auditIndex(&issues, "tags_to_log_entries", s.tagsToLogEntries, prune, func(key string, id model.LogEntryID) bool {
+		entry, ok := s.logEntries[id]
+		return ok && slices.ContainsFunc(entry.Tags, func(t string) bool { return s.tagKey(t) == key })
+	})
+	auditIndex(&issues, "task_to_log_entries", s.taskToLogEntryIDs, prune, func(ref model.TaskID, id model.LogEntryID) bool {
+		entry, ok := s.logEntries[id]
+		return ok && slices.Contains(entry.TaskRefs, ref)
+	})
+	auditIndex(&issues, "note_to_log_entries", s.noteToLogEntryIDs, prune, func(note model.NoteID, id model.LogEntryID) bool {
+		entry, ok := s.logEntries[id]
+		return ok && entry.NoteID == note
+	})
+	auditIndex(&issues, "mentions", s.mentions, prune, func(_ model.TaskID, m model.TaskMention) bool {
+		return noteLive(m.NoteID)
+	})
+	auditNoteKeys(&issues, "note_to_tasks", s.noteToTaskIDs, s.notes, prune)
+	auditNoteKeys(&issues, "links", s.links, s.notes, prune)
+	auditIndex(&issues, "aliases", s.aliases, prune, func(alias string, id model.NoteID) bool {
+		meta, ok := s.notes[id]
+		return ok && slices.ContainsFunc(meta.Aliases, func(a string) bool { return strings.EqualFold(a, alias) })
+	})
+	auditNoteKeys(&issues, "untracked", s.untracked, s.notes, prune)
+	auditNoteKeys(&issues, "warnings", s.warnings, s.notes, prune)
+	auditNoteKeys(&issues, "outlines", s.outlines, s.notes, prune)
+	auditNoteKeys(&issues, "note_content", s.noteContent, s.notes, prune)
+
+	for key := range s.tagDisplay {
+		_, onTasks := s.tagsToTasks[key]
+		_, onEntries := s.tagsToLogEntries[key]
+		if !onTasks && !onEntries {
+			issues = append(issues, model.IntegrityIssue{Index: "tag_display", Key: key, Problem: "tag carried by nothing"})
+			if prune {
+				delete(s.tagDisplay, key)
+			}
+		}
+	}
+	return issues
+}
+
+// auditIndex checks every value of a reverse index with live, reporting (and
+// when prune is set, removing) dead values and entries left empty.
+func auditIndex[K comparable, V any](issues *[]model.IntegrityIssue, name string, index map[K][]V, prune bool, live func(K, V) bool) {
+	for key, values := range index {
+		kept := values[:0:0]
+		for _, value := range values {
+			if live(key, value) {
+				kept = append(kept, value)
+				continue
+			}
+			*issues = append(*issues, model.IntegrityIssue{
+				Index:   name,
+				Key:     fmt.Sprint(key),
+				Problem: fmt.Sprintf("dangling reference %v", value),
+			})
+		}
+		if len(values) == 0 {
+			*issues = append(*issues, model.IntegrityIssue{Index: name, Key: fmt.Sprint(key), Problem: "empty entry"})
+		}
+		if !prune || len(kept) == len(values) && len(kept) > 0 {
+			continue
+		}
+		if len(kept) == 0 {
+			delete(index, key)
+		} else {
+			index[key] = kept
+		}
+	}
+}
+
+// auditNoteKeys reports (and when prune is set, removes) per-note data kept
+// for notes that are no longer indexed.
+func auditNoteKeys[V any](issues *[]model.IntegrityIssue, name string, data map[model.NoteID]V, notes map[model.NoteID]model.NoteMeta, prune bool) {
+	for id := range data {
+		if _, ok := notes[id]; ok {
+			continue
+		}
+		*issues = append(*issues, model.IntegrityIssue{Index: name, Key: string(id), Problem: "entry for unknown note"})
+		if prune {
+			delete(data, id)
+		}
+	}
+}
+
+// auditReachability reports live tasks and log entries missing from the
+// reverse indexes that should lead to them.
+func (s *InMemoryIndexStore) auditReachability() []model.IntegrityIssue {
+	var issues []model.IntegrityIssue
+	for id, task := range s.tasks {
+		if _, ok := s.notes[task.NoteID]; !ok {
+			issues = append(issues, model.IntegrityIssue{Index: "tasks", Key: string(id), Problem: "task of unknown note " + string(task.NoteID)})
+		}
+		if !slices.Contains(s.noteToTaskIDs[task.NoteID], id) {
+			issues = append(issues, model.IntegrityIssue{Index: "note_to_tasks", Key: string(task.NoteID), Problem: "missing task " + string(id)})
+		}
+		for _, tag := range task.Tags {
+			if !slices.Contains(s.tagsToTasks[s.tagKey(tag)], id) {
+				issues = append(issues, model.IntegrityIssue{Index: "tags_to_tasks", Key: s.tagKey(tag), Problem: "missing task " + string(id)})
+			}
+		}
+	}
+	for id, entry := range s.logEntries {
+		if !slices.Contains(s.noteToLogEntryIDs[entry.NoteID], id) {
+			issues = append(issues, model.IntegrityIssue{Index: "note_to_log_entries", Key: string(entry.NoteID), Problem: "missing log entry " + string(id)})
+		}
+		for _, tag := range entry.Tags {
+			if !slices.Contains(s.tagsToLogEntries[s.tagKey(tag)], id) {
+				issues = append(issues, model.IntegrityIssue{Index: "tags_to_log_entries", Key: s.tagKey(tag), Problem: "missing log entry " + string(id)})
+			}
+		}
+		for _, ref := range entry.TaskRefs {
+			if !slices.Contains(s.taskToLogEntryIDs[ref], id) {
+				issues = append(issues, model.IntegrityIssue{Index: "task_to_log_entries", Key: string(ref), Problem: "missing log entry " + string(id)})
+			}
+		}
+	}
+	return issues
+}
@@ -0,0 +1,49 @@
+package index
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// hasIssue reports whether issues include one in the named index for key.
+func hasIssue(issues []model.IntegrityIssue, index, key string) bool {
+	return slices.ContainsFunc(issues, func(issue model.IntegrityIssue) bool {
+		return issue.Index == index && issue.Key == key
+	})
+}
+
+func TestVerifyIntegrityReportsCorruption(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"work.md": "## Tasks\n\n- [ ] [T-1] Ship #release\n\n## Log\n\n- prepared [T-1] #release\n",
+		"old.md":  "## Tasks\n\n- [ ] [T-2] Retire #legacy\n",
+	})
+	s.RemoveNote("old.md")
+	if issues := s.VerifyIntegrity(); len(issues) != 0 {
+		t.Fatalf("fresh index has issues: %+v", issues)
+	}
+
+	s.tagsToTasks["ghost"] = []model.TaskID{"T-9"}
+	s.tagsToTasks["release"] = nil
+	issues := s.VerifyIntegrity()
+	for _, want := range []struct{ index, key string }{
+		{"tags_to_tasks", "ghost"},
+		{"tags_to_tasks", "release"},
+	} {
+		if !hasIssue(issues, want.index, want.key) {
+			t.Errorf("no %s issue for %q in %+v", want.index, want.key, issues)
+		}
+	}
+
+	if removed := s.Compact(); removed == 0 {
+		t.Error("Compact removed nothing")
+	}
+	if _, ok := s.tagsToTasks["ghost"]; ok {
+		t.Error("dangling tags_to_tasks entry survived Compact")
+	}
+	issues = s.VerifyIntegrity()
+	if hasIssue(issues, "tags_to_tasks", "ghost") || !hasIssue(issues, "tags_to_tasks", "release") {
+		t.Errorf("after Compact issues = %+v, want only the unreachable T-1 left", issues)
+	}
+}
@@ -0,0 +1,242 @@
+package index
+
+import (
+	"context"
+	"fmt"
+	"runtime"
+	"sync"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+// VaultIndexManager coordinates the vault, parser and index store: it reads
+// notes from disk, parses them and keeps the store in sync.
+//
+// Parse results are cached per note path. A note whose content and
+// modification time are unchanged since it was last parsed is not parsed
+// again.
+type VaultIndexManager struct {
+	vault  vault.Vault
+	parser parser.NoteParser
+	store  IndexStore
+	cache  *parseCache
+	// workers is how many goroutines FullReindex reads and parses notes
+	// with; below 2 it reads them one at a time.
+	workers int
+}
+
+// ManagerOption configures a VaultIndexManager.
+type ManagerOption func(*VaultIndexManager)
+
+// WithParseCacheSize bounds the number of parsed notes kept in the cache,
+// evicting the least recently used first. Zero disables caching.
+func WithParseCacheSize(size int) ManagerOption {
+	return func(m *VaultIndexManager) {
+		m.cache = newParseCache(size)
+	}
+}
+
+// WithReindexWorkers makes FullReindex read and parse notes on up to workers
+// goroutines, as ConcurrentReindex does, instead of one at a time. The vault
+// and parser must then be safe for concurrent use. Values below 2 keep the
+// sequential reindex.
+func WithReindexWorkers(workers int) ManagerOption {
+	return func(m *VaultIndexManager) {
+		m.workers = workers
+	}
+}
+
+// NewVaultIndexManager wires a vault, parser and store together. An
+// InMemoryIndexStore with a content cache reads evicted content back through
+// the vault.
+func NewVaultIndexManager(v vault.Vault, p parser.NoteParser, s IndexStore, opts ...ManagerOption) *VaultIndexManager {
+	m := &VaultIndexManager{vault: v, parser: p, store: s, cache: newParseCache(DefaultParseCacheSize)}
+	for _, opt := range opts {
+		opt(m)
+	}
+	if mem, ok := s.(*InMemoryIndexStore); ok {
+		mem.setContentLoader(func(path string) (string, error) {
+			note, err := v.ReadNote(path)
+			return note.Content, err
+		})
+	}
+	return m
+}
+
+// ClearCache drops every cached parse result, so the next reindex parses
+// each note again.
+func (m *VaultIndexManager) ClearCache() {
+	m.cache.clear()
+}
+
+// Vault returns the underlying vault.
+func (m *VaultIndexManager) Vault() vault.Vault {
+	return m.vault
+}
+
+// Parser returns the parser used for indexing.
+func (m *VaultIndexManager) Parser() parser.NoteParser {
+	return m.parser
+}
+
+// Store returns the underlying index store.
+func (m *VaultIndexManager) Store() IndexStore {
+	return m.store
+}
+
+// ProgressFunc is told how many of total notes a reindex has processed.
+type ProgressFunc func(processed, total int)
+
+// FullReindex parses every note in the vault into the store and drops notes
+// that no longer exist on disk. A note that cannot be read or parsed is
+// recorded in the report and skipped, keeping whatever was indexed for it
+// before; only failing to list the vault is an error. With
+// WithReindexWorkers it runs as ConcurrentReindex.
+func (m *VaultIndexManager) FullReindex() (model.ReindexReport, error) {
+	return m.FullReindexWithProgress(nil)
+}
+
+// FullReindexWithProgress is FullReindex calling progress, when non-nil,
+// after each note is processed.
+func (m *VaultIndexManager) FullReindexWithProgress(progress ProgressFunc) (model.ReindexReport, error) {
+	if m.workers > 1 {
+		return m.concurrentReindex(context.Background(), m.workers, progress)
+	}
+	paths, err := m.vault.ListNotePaths()
+	if err != nil {
+		return model.ReindexReport{}, fmt.Errorf("list notes: %w", err)
+	}
+
+	report := newReindexReport()
+	seen := make(map[model.NoteID]bool, len(paths))
+	for i, path := range paths {
+		seen[m.vault.NoteID(path)] = true
+		if note, err := m.ReindexNote(path); err != nil {
+			report.Errors = append(report.Errors, model.ReindexError{Path: model.FilePath(path), Error: err.Error()})
+		} else {
+			report.Indexed++
+			if note.Lossy {
+				report.Lossy = append(report.Lossy, note.Path)
+			}
+		}
+		if progress != nil {
+			progress(i+1, len(paths))
+		}
+	}
+	m.removeUnseen(seen)
+	return report, nil
+}
+
+func newReindexReport() model.ReindexReport {
+	return model.ReindexReport{Errors: []model.ReindexError{}, Lossy: []model.FilePath{}}
+}
+
+// removeUnseen drops every indexed note not in seen.
+func (m *VaultIndexManager) removeUnseen(seen map[model.NoteID]bool) {
+	for _, id := range m.store.NoteIDs() {
+		if !seen[id] {
+			m.store.RemoveNote(id)
+		}
+	}
+}
+
+// ConcurrentReindex is FullReindex with notes read and parsed by up to
+// workers goroutines (GOMAXPROCS when workers < 1), so a slow filesystem
+// does not serialize every read. The vault's ReadNote and the parser must be
+// safe for concurrent use. The store is only updated once every note has
+// been read, and is left untouched if ctx is cancelled first.
+func (m *VaultIndexManager) ConcurrentReindex(ctx context.Context, workers int) (model.ReindexReport, error) {
+	return m.concurrentReindex(ctx, workers, nil)
+}
+
+// concurrentReindex is ConcurrentReindex calling progress, when non-nil, as
+// each note's result is applied to the store.
+func (m *VaultIndexManager) concurrentReindex(ctx context.Context, workers int, progress ProgressFunc) (model.ReindexReport, error) {
+	paths, err := m.vault.ListNotePaths()
+	if err != nil {
+		return model.ReindexReport{}, fmt.Errorf("list notes: %w", err)
+	}
+	if workers < 1 {
+		workers = runtime.GOMAXPROCS(0)
+	}
+
+	type result struct {
+		parsed model.ParsedNote
+		err    error
+	}
+	results := make([]result, len(paths))
+	jobs := make(chan int)
+	var wg sync.WaitGroup
+	for range workers {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for i := range jobs {
+				parsed, err := m.readAndParse(paths[i])
+				results[i] = result{parsed: parsed, err: err}
+			}
+		}()
+	}
+send:
+	for i := range paths {
+		select {
+		case jobs <- i:
+		case <-ctx.Done():
+			break send
+		}
+	}
+	close(jobs)
+	wg.Wait()
+	if err := ctx.Err(); err != nil {
+		return model.ReindexReport{}, err
+	}
+
+	report := newReindexReport()
+	seen := make(map[model.NoteID]bool, len(paths))
+	for i, r := range results {
+		seen[m.vault.NoteID(paths[i])] = true
+		if r.err != nil {
+			report.Errors = append(report.Errors, model.ReindexError{Path: model.FilePath(paths[i]), Error: r.err.Error()})
+		} else {
+			m.store.UpsertParsedNote(r.parsed)
+			report.Indexed++
+			if r.parsed.Note.Lossy {
+				report.Lossy = append(report.Lossy, r.parsed.Note.Path)
+			}
+		}
+		if progress != nil {
+			progress(i+1, len(paths))
+		}
+	}
+	m.removeUnseen(seen)
+	return report, nil
+}
+
+// ReindexNote reads, parses and indexes the note at path, returning the note
+// as read from disk.
+func (m *VaultIndexManager) ReindexNote(path string) (model.Note, error) {
+	parsed, err := m.readAndParse(path)
+	if err != nil {
+		return model.Note{}, err
+	}
+	m.store.UpsertParsedNote(parsed)
+	return parsed.Note, nil
+}
+
+func (m *VaultIndexManager) readAndParse(path string) (model.ParsedNote, error) {
+	note, err := m.vault.ReadNote(path)
+	if err != nil {
+		return model.ParsedNote{}, err
+	}
+	if parsed, ok := m.cache.get(path, note); ok {
+		return parsed, nil
+	}
+	parsed, err := m.parser.Parse(note)
+	if err != nil {
+		return model.ParsedNote{}, fmt.Errorf("parse note %s: %w", path, err)
+	}
+	m.cache.put(path, note, parsed)
+	return parsed, nil
+}
@@ -0,0 +1,141 @@
+package index
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+	"github.com/weakphish/yapper/internal/vault"
+)
+
+// newManager returns a manager indexing the vault at root into a fresh store.
+func newManager(root string) *VaultIndexManager {
+	return NewVaultIndexManager(vault.NewFileSystemVault(root), parser.NewRegexMarkdownParser(), NewInMemoryIndexStore())
+}
+
+// writeNotes writes each note, keyed by file name, under root.
+func writeNotes(t *testing.T, root string, notes map[string]string) {
+	t.Helper()
+	for name, content := range notes {
+		if err := os.WriteFile(filepath.Join(root, name), []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+}
+
+func TestFullReindexReportsProgress(t *testing.T) {
+	root := t.TempDir()
+	writeNotes(t, root, map[string]string{"a.md": "# A\n", "b.md": "# B\n", "c.md": "# C\n", "d.md": "# D\n"})
+
+	var calls [][2]int
+	report, err := newManager(root).FullReindexWithProgress(func(processed, total int) {
+		calls = append(calls, [2]int{processed, total})
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if report.Indexed != 4 {
+		t.Errorf("indexed %d notes, want 4", report.Indexed)
+	}
+	if len(calls) != 4 {
+		t.Fatalf("progress called %d times, want once per note: %v", len(calls), calls)
+	}
+	for i, call := range calls {
+		if call != [2]int{i + 1, 4} {
+			t.Errorf("call %d = %v, want [%d 4]", i, call, i+1)
+		}
+	}
+}
+
+// failingParser fails to parse the note with the given ID.
+type failingParser struct {
+	parser.NoteParser
+	bad model.NoteID
+}
+
+func (p failingParser) Parse(note model.Note) (model.ParsedNote, error) {
+	if note.ID == p.bad {
+		return model.ParsedNote{}, errors.New("malformed note")
+	}
+	return p.NoteParser.Parse(note)
+}
+
+func TestFullReindexSkipsBrokenNotes(t *testing.T) {
+	root := t.TempDir()
+	writeNotes(t, root, map[string]string{
+		"good.md":    "## Tasks\n\n- [ ] [T-1] Keep going\n",
+		"bad.md":     "## Tasks\n\n- [ ] [T-2] Never indexed\n",
+		"locked.md":  "## Tasks\n\n- [ ] [T-3] Unreadable\n",
+		"another.md": "# Another\n",
+	})
+	locked := filepath.Join(root, "locked.md")
+	if err := os.Chmod(locked, 0); err != nil {
+		t.Fatal(err)
+	}
+	_, readErr := os.ReadFile(locked)
+
+	store := NewInMemoryIndexStore()
+	m := NewVaultIndexManager(vault.NewFileSystemVault(root), failingParser{NoteParser: parser.NewRegexMarkdownParser(), bad: "bad.md"}, store)
+	report, err := m.FullReindex()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	wantFailed := []model.FilePath{model.FilePath(filepath.Join(root, "bad.md"))}
+	if readErr != nil {
+		wantFailed = append(wantFailed, model.FilePath(locked))
+	}
+	var failed []model.FilePath
+	for _, e := range report.Errors {
+		failed = append(failed, e.Path)
+	}
+	if !slices.Equal(failed, wantFailed) {
+		t.Errorf("failed notes = %v, want %v", failed, wantFailed)
+	}
+	if report.Indexed != 4-len(wantFailed) {
+		t.Errorf("indexed %d notes, want %d", report.Indexed, 4-len(wantFailed))
+	}
+	if _, ok := store.GetTask("T-1"); !ok {
+		t.Error("T-1 from good.md was not indexed")
+	}
+	if _, ok := store.GetNoteMeta("bad.md"); ok {
+		t.Error("bad.md was indexed")
+	}
+}
+
+func TestFullReindexWithWorkers(t *testing.T) {
+	root := t.TempDir()
+	writeNotes(t, root, map[string]string{
+		"a.md": "## Tasks\n\n- [ ] [T-1] First\n",
+		"b.md": "## Tasks\n\n- [ ] [T-2] Second\n",
+		"c.md": "# C\n",
+	})
+	m := NewVaultIndexManager(vault.NewFileSystemVault(root), parser.NewRegexMarkdownParser(), NewInMemoryIndexStore(), WithReindexWorkers(4))
+	if _, err := m.FullReindex(); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Remove(filepath.Join(root, "b.md")); err != nil {
+		t.Fatal(err)
+	}
+
+	var calls int
+	report, err := m.FullReindexWithProgress(func(processed, total int) {
+		calls++
+		if processed != calls || total != 2 {
+			t.Errorf("progress(%d, %d) on call %d", processed, total, calls)
+		}
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if report.Indexed != 2 || calls != 2 {
+		t.Errorf("indexed %d notes with %d progress calls, want 2 and 2", report.Indexed, calls)
+	}
+	if got := taskIDs(m.Store().ListTasks(TaskFilter{})); !slices.Equal(got, []model.TaskID{"T-1"}) {
+		t.Errorf("tasks = %v, want [T-1] once b.md is gone", got)
+	}
+}
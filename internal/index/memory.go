@@ -0,0 +1,746 @@
+package index
+
+import (
+	"cmp"
+	"iter"
+	"maps"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// InMemoryIndexStore is an IndexStore backed by Go maps. It is not safe for
+// concurrent use.
+//
+// Tags are bucketed by a lookup key produced by the store's tag normalizer
+// (NormalizeTag unless configured otherwise), while ListTags and ItemsForTag
+// report the first-seen spelling of each tag.
+//
+// Note content is kept for every note unless WithContentCache bounds it.
+type InMemoryIndexStore struct {
+	tagKey            func(string) string
+	tagDisplay        map[string]string
+	notes             map[model.NoteID]model.NoteMeta
+	noteContent       map[model.NoteID]string
+	content           *contentCache
+	tasks             map[model.TaskID]model.Task
+	definitions       map[model.TaskID][]model.Task
+	logEntries        map[model.LogEntryID]model.LogEntry
+	mentions          map[model.TaskID][]model.TaskMention
+	taskToLogEntryIDs map[model.TaskID][]model.LogEntryID
+	tagsToTasks       map[string][]model.TaskID
+	tagsToLogEntries  map[string][]model.LogEntryID
+	noteToTaskIDs     map[model.NoteID][]model.TaskID
+	noteToLogEntryIDs map[model.NoteID][]model.LogEntryID
+	links             map[model.NoteID][]model.NoteLink
+	untracked         map[model.NoteID][]model.UntrackedTask
+	warnings          map[model.NoteID][]model.ParseWarning
+	aliases           map[string][]model.NoteID
+	outlines          map[model.NoteID][]model.Heading
+	now               func() time.Time
+}
+
+// StoreOption configures an InMemoryIndexStore.
+type StoreOption func(*InMemoryIndexStore)
+
+// WithTagNormalizer sets the function mapping tags to lookup keys. Passing
+// ExactTag keeps tags case- and form-sensitive.
+func WithTagNormalizer(normalize func(string) string) StoreOption {
+	return func(s *InMemoryIndexStore) {
+		s.tagKey = normalize
+	}
+}
+
+// WithContentCache keeps the content of at most size notes in memory,
+// evicting the least recently read first, instead of every note's. Evicted
+// content is read again from the vault by GetNote, so it may be newer than
+// what was indexed; a note that can no longer be read is reported missing.
+// The loader is supplied by the VaultIndexManager the store is given to.
+func WithContentCache(size int) StoreOption {
+	return func(s *InMemoryIndexStore) {
+		s.content = newContentCache(size)
+	}
+}
+
+// WithIndexClock overrides the clock that stamps each note's LastIndexed.
+func WithIndexClock(now func() time.Time) StoreOption {
+	return func(s *InMemoryIndexStore) {
+		s.now = now
+	}
+}
+
+// setContentLoader sets how content evicted from the content cache is read
+// back. It does nothing unless WithContentCache was given.
+func (s *InMemoryIndexStore) setContentLoader(load ContentLoader) {
+	if s.content != nil {
+		s.content.load = load
+	}
+}
+
+// NewInMemoryIndexStore returns an empty store.
+func NewInMemoryIndexStore(opts ...StoreOption) *InMemoryIndexStore {
+	s := &InMemoryIndexStore{
+		tagKey:            NormalizeTag,
+		tagDisplay:        map[string]string{},
+		notes:             map[model.NoteID]model.NoteMeta{},
+		noteContent:       map[model.NoteID]string{},
+		tasks:             map[model.TaskID]model.Task{},
+		definitions:       map[model.TaskID][]model.Task{},
+		logEntries:        map[model.LogEntryID]model.LogEntry{},
+		mentions:          map[model.TaskID][]model.TaskMention{},
+		taskToLogEntryIDs: map[model.TaskID][]model.LogEntryID{},
+		tagsToTasks:       map[string][]model.TaskID{},
+		tagsToLogEntries:  map[string][]model.LogEntryID{},
+		noteToTaskIDs:     map[model.NoteID][]model.TaskID{},
+		noteToLogEntryIDs: map[model.NoteID][]model.LogEntryID{},
+		links:             map[model.NoteID][]model.NoteLink{},
+		untracked:         map[model.NoteID][]model.UntrackedTask{},
+		warnings:          map[model.NoteID][]model.ParseWarning{},
+		aliases:           map[string][]model.NoteID{},
+		outlines:          map[model.NoteID][]model.Heading{},
+		now:               time.Now,
+	}
+	for _, opt := range opts {
+		opt(s)
+	}
+	return s
+}
+
+// UpsertParsedNote implements IndexStore. A task ID defined in several notes
+// resolves to whichever note was indexed last; the other definitions are kept
+// so that removing the winning note falls back to them.
+func (s *InMemoryIndexStore) UpsertParsedNote(parsed model.ParsedNote) {
+	id := parsed.Note.ID
+	s.RemoveNote(id)
+
+	meta := parsed.Note.Meta()
+	meta.WordCount = parsed.WordCount
+	meta.LastIndexed = s.now()
+	s.notes[id] = meta
+	for _, alias := range meta.Aliases {
+		key := strings.ToLower(alias)
+		s.aliases[key] = appendUnique(s.aliases[key], id)
+	}
+	if s.content != nil {
+		s.content.put(id, parsed.Note.Content)
+	} else {
+		s.noteContent[id] = parsed.Note.Content
+	}
+
+	for _, task := range parsed.Tasks {
+		if shadowed, ok := s.tasks[task.ID]; ok {
+			s.unindexTaskTags(shadowed)
+		}
+		s.definitions[task.ID] = append(s.definitions[task.ID], task)
+		s.noteToTaskIDs[id] = append(s.noteToTaskIDs[id], task.ID)
+		s.indexTask(task)
+	}
+	for _, entry := range parsed.LogEntries {
+		s.logEntries[entry.ID] = entry
+		s.noteToLogEntryIDs[id] = append(s.noteToLogEntryIDs[id], entry.ID)
+		for _, tag := range entry.Tags {
+			key := s.addTag(tag)
+			s.tagsToLogEntries[key] = appendUnique(s.tagsToLogEntries[key], entry.ID)
+		}
+		for _, ref := range entry.TaskRefs {
+			s.taskToLogEntryIDs[ref] = appendUnique(s.taskToLogEntryIDs[ref], entry.ID)
+		}
+	}
+	for _, mention := range parsed.Mentions {
+		s.mentions[mention.TaskID] = append(s.mentions[mention.TaskID], mention)
+	}
+	if len(parsed.Links) > 0 {
+		s.links[id] = parsed.Links
+	}
+	if len(parsed.Untracked) > 0 {
+		s.untracked[id] = parsed.Untracked
+	}
+	if len(parsed.Warnings) > 0 {
+		s.warnings[id] = parsed.Warnings
+	}
+	if len(parsed.Outline) > 0 {
+		s.outlines[id] = parsed.Outline
+	}
+}
+
+// RemoveNote implements IndexStore.
+func (s *InMemoryIndexStore) RemoveNote(id model.NoteID) {
+	for _, taskID := range s.noteToTaskIDs[id] {
+		defs, ok := s.definitions[taskID]
+		if !ok {
+			continue
+		}
+		defs = slices.DeleteFunc(defs, func(t model.Task) bool { return t.NoteID == id })
+		won := s.tasks[taskID].NoteID == id
+		if won {
+			s.unindexTaskTags(s.tasks[taskID])
+		}
+		if len(defs) == 0 {
+			delete(s.tasks, taskID)
+			delete(s.definitions, taskID)
+			continue
+		}
+		s.definitions[taskID] = defs
+		if won {
+			s.indexTask(defs[len(defs)-1])
+		}
+	}
+	delete(s.noteToTaskIDs, id)
+
+	for _, entryID := range s.noteToLogEntryIDs[id] {
+		entry, ok := s.logEntries[entryID]
+		if !ok {
+			continue
+		}
+		delete(s.logEntries, entryID)
+		for _, tag := range entry.Tags {
+			key := s.tagKey(tag)
+			removeFromIndex(s.tagsToLogEntries, key, entryID)
+			s.releaseTag(key)
+		}
+		for _, ref := range entry.TaskRefs {
+			removeFromIndex(s.taskToLogEntryIDs, ref, entryID)
+		}
+	}
+	delete(s.noteToLogEntryIDs, id)
+
+	for taskID, mentions := range s.mentions {
+		kept := slices.DeleteFunc(mentions, func(m model.TaskMention) bool {
+			return m.NoteID == id
+		})
+		if len(kept) == 0 {
+			delete(s.mentions, taskID)
+		} else {
+			s.mentions[taskID] = kept
+		}
+	}
+
+	delete(s.links, id)
+	delete(s.untracked, id)
+	delete(s.warnings, id)
+	for _, alias := range s.notes[id].Aliases {
+		removeFromIndex(s.aliases, strings.ToLower(alias), id)
+	}
+	delete(s.outlines, id)
+	delete(s.notes, id)
+	delete(s.noteContent, id)
+	if s.content != nil {
+		s.content.remove(id)
+	}
+}
+
+// Stats implements IndexStore.
+func (s *InMemoryIndexStore) Stats() model.IndexStats {
+	return model.IndexStats{
+		Notes:      len(s.notes),
+		Tasks:      len(s.tasks),
+		LogEntries: len(s.logEntries),
+		Tags:       len(s.tagDisplay),
+	}
+}
+
+// NoteIDs implements IndexStore.
+func (s *InMemoryIndexStore) NoteIDs() []model.NoteID {
+	ids := make([]model.NoteID, 0, len(s.notes))
+	for id := range s.notes {
+		ids = append(ids, id)
+	}
+	slices.Sort(ids)
+	return ids
+}
+
+// GetNote implements IndexStore.
+func (s *InMemoryIndexStore) GetNote(id model.NoteID) (model.Note, bool) {
+	meta, ok := s.notes[id]
+	if !ok {
+		return model.Note{}, false
+	}
+	content := s.noteContent[id]
+	if s.content != nil {
+		if content, ok = s.content.get(id, string(meta.Path)); !ok {
+			return model.Note{}, false
+		}
+	}
+	return model.Note{
+		ID:         meta.ID,
+		Path:       meta.Path,
+		Title:      meta.Title,
+		Aliases:    meta.Aliases,
+		Date:       meta.Date,
+		DateRange:  meta.DateRange,
+		ModifiedAt: meta.ModifiedAt,
+		Content:    content,
+		Lossy:      meta.Lossy,
+	}, true
+}
+
+// ResolveAlias implements IndexStore. When several notes share an alias, the
+// one with the lowest ID wins.
+func (s *InMemoryIndexStore) ResolveAlias(alias string) (model.NoteID, bool) {
+	ids := s.aliases[strings.ToLower(alias)]
+	if len(ids) == 0 {
+		return "", false
+	}
+	return slices.Min(ids), true
+}
+
+// GetNoteMeta implements IndexStore.
+func (s *InMemoryIndexStore) GetNoteMeta(id model.NoteID) (model.NoteMeta, bool) {
+	meta, ok := s.notes[id]
+	return meta, ok
+}
+
+// ListNotes implements IndexStore.
+func (s *InMemoryIndexStore) ListNotes() []model.NoteMeta {
+	notes := make([]model.NoteMeta, 0, len(s.notes))
+	for _, meta := range s.notes {
+		notes = append(notes, meta)
+	}
+	slices.SortFunc(notes, func(a, b model.NoteMeta) int {
+		switch {
+		case a.Date == nil && b.Date != nil:
+			return 1
+		case a.Date != nil && b.Date == nil:
+			return -1
+		case a.Date != nil && !a.Date.Equal(*b.Date):
+			return a.Date.Compare(*b.Date)
+		}
+		return cmp.Or(cmp.Compare(a.Title, b.Title), cmp.Compare(a.ID, b.ID))
+	})
+	return notes
+}
+
+// Notes implements IndexStore.
+func (s *InMemoryIndexStore) Notes() iter.Seq[model.NoteMeta] {
+	return maps.Values(s.notes)
+}
+
+// ListRecentNotes implements IndexStore. Notes modified at the same instant
+// are ordered by ID.
+func (s *InMemoryIndexStore) ListRecentNotes(limit int) []model.NoteMeta {
+	notes := make([]model.NoteMeta, 0, len(s.notes))
+	for _, meta := range s.notes {
+		notes = append(notes, meta)
+	}
+	slices.SortFunc(notes, func(a, b model.NoteMeta) int {
+		return cmp.Or(b.ModifiedAt.Compare(a.ModifiedAt), cmp.Compare(a.ID, b.ID))
+	})
+	if limit > 0 && len(notes) > limit {
+		notes = notes[:limit]
+	}
+	return notes
+}
+
+// GetTask implements IndexStore.
+func (s *InMemoryIndexStore) GetTask(id model.TaskID) (model.Task, bool) {
+	task, ok := s.tasks[id]
+	return task, ok
+}
+
+// ListTasks implements IndexStore. Tag filters are compared by lookup key;
+// a SourceNoteID filter only visits that note's tasks.
+func (s *InMemoryIndexStore) ListTasks(filter TaskFilter) []model.Task {
+	tasks := []model.Task{}
+	if filter.SourceNoteID != "" {
+		// A note defining an ID twice lists it twice.
+		seen := map[model.TaskID]bool{}
+		for _, id := range s.noteToTaskIDs[filter.SourceNoteID] {
+			if task, ok := s.tasks[id]; ok && !seen[id] && filter.matches(task, s.tagKey) {
+				seen[id] = true
+				tasks = append(tasks, task)
+			}
+		}
+	} else {
+		for _, task := range s.tasks {
+			if filter.matches(task, s.tagKey) {
+				tasks = append(tasks, task)
+			}
+		}
+	}
+	sort.Slice(tasks, func(i, j int) bool { return tasks[i].ID < tasks[j].ID })
+	if filter.Limit > 0 && len(tasks) > filter.Limit {
+		tasks = tasks[:filter.Limit]
+	}
+	return tasks
+}
+
+// Tasks implements IndexStore.
+func (s *InMemoryIndexStore) Tasks() iter.Seq[model.Task] {
+	return maps.Values(s.tasks)
+}
+
+// GetLogEntriesForTask implements IndexStore.
+func (s *InMemoryIndexStore) GetLogEntriesForTask(id model.TaskID) []model.LogEntry {
+	return s.lookupLogEntries(s.taskToLogEntryIDs[id])
+}
+
+// GetMentionsForTask implements IndexStore.
+func (s *InMemoryIndexStore) GetMentionsForTask(id model.TaskID) []model.TaskMention {
+	mentions := slices.Clone(s.mentions[id])
+	if mentions == nil {
+		mentions = []model.TaskMention{}
+	}
+	slices.SortFunc(mentions, func(a, b model.TaskMention) int {
+		return cmp.Or(cmp.Compare(a.NoteID, b.NoteID), cmp.Compare(a.Line, b.Line))
+	})
+	return mentions
+}
+
+// ListAllMentions implements IndexStore.
+func (s *InMemoryIndexStore) ListAllMentions() []model.TaskMention {
+	mentions := []model.TaskMention{}
+	for _, taskMentions := range s.mentions {
+		mentions = append(mentions, taskMentions...)
+	}
+	slices.SortFunc(mentions, func(a, b model.TaskMention) int {
+		return cmp.Or(cmp.Compare(a.NoteID, b.NoteID), cmp.Compare(a.Line, b.Line), cmp.Compare(a.TaskID, b.TaskID))
+	})
+	return mentions
+}
+
+// ListNotesByDate implements IndexStore. Undated notes are never returned;
+// a note with a DateRange is returned when it overlaps r.
+func (s *InMemoryIndexStore) ListNotesByDate(r model.DateRange) []model.NoteMeta {
+	notes := []model.NoteMeta{}
+	for _, meta := range s.notes {
+		if inRange(meta, r) {
+			notes = append(notes, meta)
+		}
+	}
+	sort.Slice(notes, func(i, j int) bool {
+		if !notes[i].Date.Equal(*notes[j].Date) {
+			return notes[i].Date.Before(*notes[j].Date)
+		}
+		return notes[i].Title < notes[j].Title
+	})
+	return notes
+}
+
+// inRange reports whether a note's days fall within r: any day of its
+// DateRange, or else its Date.
+func inRange(meta model.NoteMeta, r model.DateRange) bool {
+	if meta.DateRange != nil {
+		return r.Overlaps(*meta.DateRange)
+	}
+	return meta.Date != nil && r.Contains(*meta.Date)
+}
+
+// ListUndatedNotes implements IndexStore.
+func (s *InMemoryIndexStore) ListUndatedNotes() []model.NoteMeta {
+	notes := []model.NoteMeta{}
+	for _, meta := range s.notes {
+		if meta.Date == nil {
+			notes = append(notes, meta)
+		}
+	}
+	slices.SortFunc(notes, func(a, b model.NoteMeta) int {
+		return cmp.Or(cmp.Compare(a.Title, b.Title), cmp.Compare(a.ID, b.ID))
+	})
+	return notes
+}
+
+// ListLogEntriesByDate implements IndexStore. Entries of undated notes are
+// never returned; those of a note with a DateRange are when it overlaps r.
+func (s *InMemoryIndexStore) ListLogEntriesByDate(r model.DateRange) []model.LogEntry {
+	entries := []model.LogEntry{}
+	for id, meta := range s.notes {
+		if inRange(meta, r) {
+			entries = append(entries, s.lookupLogEntries(s.noteToLogEntryIDs[id])...)
+		}
+	}
+	slices.SortFunc(entries, func(a, b model.LogEntry) int {
+		return cmp.Or(
+			s.notes[a.NoteID].Date.Compare(*s.notes[b.NoteID].Date),
+			a.Timestamp.Compare(b.Timestamp),
+			cmp.Compare(a.NoteID, b.NoteID),
+			cmp.Compare(a.Line, b.Line),
+		)
+	})
+	return entries
+}
+
+// GetTasksForNote implements IndexStore.
+func (s *InMemoryIndexStore) GetTasksForNote(id model.NoteID) []model.Task {
+	tasks := []model.Task{}
+	for _, taskID := range s.noteToTaskIDs[id] {
+		if task, ok := s.tasks[taskID]; ok && task.NoteID == id {
+			tasks = append(tasks, task)
+		}
+	}
+	slices.SortFunc(tasks, func(a, b model.Task) int {
+		return cmp.Or(cmp.Compare(a.Line, b.Line), cmp.Compare(a.ID, b.ID))
+	})
+	return tasks
+}
+
+// GetLogEntriesForNote implements IndexStore.
+func (s *InMemoryIndexStore) GetLogEntriesForNote(id model.NoteID) []model.LogEntry {
+	return s.lookupLogEntries(s.noteToLogEntryIDs[id])
+}
+
+// TagKey implements IndexStore using the store's tag normalizer.
+func (s *InMemoryIndexStore) TagKey(tag string) string {
+	return s.tagKey(tag)
+}
+
+// ListTags implements IndexStore, returning display forms ordered by key.
+func (s *InMemoryIndexStore) ListTags() []string {
+	keys := make([]string, 0, len(s.tagDisplay))
+	for key := range s.tagDisplay {
+		keys = append(keys, key)
+	}
+	slices.Sort(keys)
+	tags := make([]string, len(keys))
+	for i, key := range keys {
+		tags[i] = s.tagDisplay[key]
+	}
+	return tags
+}
+
+// SearchTags implements IndexStore, matching display forms.
+func (s *InMemoryIndexStore) SearchTags(query string, limit int) []string {
+	query = strings.ToLower(query)
+	var prefixed, interior []string
+	for _, tag := range s.ListTags() {
+		switch i := strings.Index(strings.ToLower(tag), query); {
+		case i == 0:
+			prefixed = append(prefixed, tag)
+		case i > 0:
+			interior = append(interior, tag)
+		}
+	}
+	tags := append(append([]string{}, prefixed...), interior...)
+	if limit > 0 && len(tags) > limit {
+		tags = tags[:limit]
+	}
+	return tags
+}
+
+// TagGraph implements IndexStore, keyed by the tags' display forms. IDs of
+// entities no longer indexed are left out, as ItemsForTag does.
+func (s *InMemoryIndexStore) TagGraph() map[string]model.TagItems {
+	graph := make(map[string]model.TagItems, len(s.tagDisplay))
+	for key, tag := range s.tagDisplay {
+		items := model.TagItems{TaskIDs: []model.TaskID{}, LogEntryIDs: []model.LogEntryID{}}
+		for _, id := range s.tagsToTasks[key] {
+			if _, ok := s.tasks[id]; ok {
+				items.TaskIDs = append(items.TaskIDs, id)
+			}
+		}
+		for _, id := range s.tagsToLogEntries[key] {
+			if _, ok := s.logEntries[id]; ok {
+				items.LogEntryIDs = append(items.LogEntryIDs, id)
+			}
+		}
+		slices.Sort(items.TaskIDs)
+		slices.Sort(items.LogEntryIDs)
+		graph[tag] = items
+	}
+	return graph
+}
+
+// ItemsForTag implements IndexStore. The tag is matched by lookup key, so
+// "Projects" and "projects" resolve to the same bucket by default.
+func (s *InMemoryIndexStore) ItemsForTag(tag string) model.TagResult {
+	key := s.tagKey(tag)
+	if display, ok := s.tagDisplay[key]; ok {
+		tag = display
+	}
+	result := model.TagResult{
+		Tag:        tag,
+		Tasks:      []model.Task{},
+		LogEntries: s.lookupLogEntries(s.tagsToLogEntries[key]),
+	}
+	for _, id := range s.tagsToTasks[key] {
+		if task, ok := s.tasks[id]; ok {
+			result.Tasks = append(result.Tasks, task)
+		}
+	}
+	slices.SortFunc(result.Tasks, func(a, b model.Task) int {
+		return cmp.Or(a.CreatedAt.Compare(b.CreatedAt), cmp.Compare(a.ID, b.ID))
+	})
+	return result
+}
+
+// GetNoteBacklinks implements IndexStore. Links are stored by their raw
+// target and resolved at query time, so a link starts resolving as soon as a
+// note with a matching title or file name is indexed.
+func (s *InMemoryIndexStore) GetNoteBacklinks(id model.NoteID) []model.NoteRef {
+	refs := []model.NoteRef{}
+	meta, ok := s.notes[id]
+	if !ok {
+		return refs
+	}
+	names := linkNames(meta)
+	for _, source := range s.NoteIDs() {
+		for _, link := range s.links[source] {
+			if slices.Contains(names, strings.ToLower(link.Target)) {
+				refs = append(refs, noteRef(link, id))
+			}
+		}
+	}
+	return refs
+}
+
+// linkNames returns the lowercased names a wikilink may use for a note: its
+// title, its file stem and its aliases.
+func linkNames(meta model.NoteMeta) []string {
+	base := filepath.Base(string(meta.Path))
+	names := []string{strings.ToLower(meta.Title)}
+	if stem := strings.ToLower(strings.TrimSuffix(base, filepath.Ext(base))); stem != "" {
+		names = appendUnique(names, stem)
+	}
+	for _, alias := range meta.Aliases {
+		names = appendUnique(names, strings.ToLower(alias))
+	}
+	return names
+}
+
+// ListUnresolvedLinks implements IndexStore.
+func (s *InMemoryIndexStore) ListUnresolvedLinks() []model.NoteRef {
+	names := make(map[string]bool, len(s.notes))
+	for _, meta := range s.notes {
+		for _, name := range linkNames(meta) {
+			names[name] = true
+		}
+	}
+	refs := []model.NoteRef{}
+	for _, source := range s.NoteIDs() {
+		for _, link := range s.links[source] {
+			if !names[strings.ToLower(link.Target)] {
+				refs = append(refs, noteRef(link, ""))
+			}
+		}
+	}
+	return refs
+}
+
+// TaskDefinitions implements IndexStore.
+func (s *InMemoryIndexStore) TaskDefinitions() map[model.TaskID][]model.NoteID {
+	defs := map[model.TaskID][]model.NoteID{}
+	for _, noteID := range s.NoteIDs() {
+		for _, taskID := range s.noteToTaskIDs[noteID] {
+			defs[taskID] = append(defs[taskID], noteID)
+		}
+	}
+	return defs
+}
+
+// GetNoteOutline implements IndexStore.
+func (s *InMemoryIndexStore) GetNoteOutline(id model.NoteID) ([]model.Heading, bool) {
+	if _, ok := s.notes[id]; !ok {
+		return nil, false
+	}
+	outline := slices.Clone(s.outlines[id])
+	if outline == nil {
+		outline = []model.Heading{}
+	}
+	return outline, true
+}
+
+// ListUntrackedTasks implements IndexStore.
+func (s *InMemoryIndexStore) ListUntrackedTasks() []model.UntrackedTask {
+	untracked := []model.UntrackedTask{}
+	for _, noteID := range s.NoteIDs() {
+		untracked = append(untracked, s.untracked[noteID]...)
+	}
+	return untracked
+}
+
+// ListParseWarnings implements IndexStore.
+func (s *InMemoryIndexStore) ListParseWarnings() []model.ParseWarning {
+	warnings := []model.ParseWarning{}
+	for _, noteID := range s.NoteIDs() {
+		warnings = append(warnings, s.warnings[noteID]...)
+	}
+	return warnings
+}
+
+// noteRef converts a link into a reference to target; an empty target marks
+// the link as unresolved.
+func noteRef(link model.NoteLink, target model.NoteID) model.NoteRef {
+	return model.NoteRef{
+		SourceID: link.NoteID,
+		TargetID: target,
+		Target:   link.Target,
+		Line:     link.Line,
+		Context:  link.Context,
+		Resolved: target != "",
+	}
+}
+
+// addTag returns the lookup key for tag, recording its display form if the
+// key is new.
+func (s *InMemoryIndexStore) addTag(tag string) string {
+	key := s.tagKey(tag)
+	if _, ok := s.tagDisplay[key]; !ok {
+		s.tagDisplay[key] = tag
+	}
+	return key
+}
+
+// indexTask makes task the definition its ID resolves to and indexes its
+// tags.
+func (s *InMemoryIndexStore) indexTask(task model.Task) {
+	s.tasks[task.ID] = task
+	for _, tag := range task.Tags {
+		key := s.addTag(tag)
+		s.tagsToTasks[key] = appendUnique(s.tagsToTasks[key], task.ID)
+	}
+}
+
+// unindexTaskTags drops task's ID from the reverse index of each of its
+// tags, before another definition of the ID replaces it.
+func (s *InMemoryIndexStore) unindexTaskTags(task model.Task) {
+	for _, tag := range task.Tags {
+		key := s.tagKey(tag)
+		removeFromIndex(s.tagsToTasks, key, task.ID)
+		s.releaseTag(key)
+	}
+}
+
+// releaseTag forgets the display form of key once nothing carries it.
+func (s *InMemoryIndexStore) releaseTag(key string) {
+	_, onTasks := s.tagsToTasks[key]
+	_, onEntries := s.tagsToLogEntries[key]
+	if !onTasks && !onEntries {
+		delete(s.tagDisplay, key)
+	}
+}
+
+// lookupLogEntries resolves ids to log entries ordered by note ID then line,
+// independent of the order in which notes were indexed.
+func (s *InMemoryIndexStore) lookupLogEntries(ids []model.LogEntryID) []model.LogEntry {
+	entries := []model.LogEntry{}
+	for _, id := range ids {
+		if entry, ok := s.logEntries[id]; ok {
+			entries = append(entries, entry)
+		}
+	}
+	slices.SortFunc(entries, func(a, b model.LogEntry) int {
+		return cmp.Or(cmp.Compare(a.NoteID, b.NoteID), cmp.Compare(a.Line, b.Line))
+	})
+	return entries
+}
+
+func appendUnique[T comparable](values []T, value T) []T {
+	if slices.Contains(values, value) {
+		return values
+	}
+	return append(values, value)
+}
+
+// removeFromIndex drops value from the reverse index entry for key, deleting
+// the entry once it is empty.
+func removeFromIndex[K comparable, V comparable](index map[K][]V, key K, value V) {
+	values := slices.DeleteFunc(index[key], func(v V) bool { return v == value })
+	if len(values) == 0 {
+		delete(index, key)
+		return
+	}
+	index[key] = values
+}
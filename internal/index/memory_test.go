@@ -0,0 +1,289 @@
+package index
+
+import (
+	"fmt"
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+	"github.com/weakphish/yapper/internal/parser"
+)
+
+// modTime is the modification time given to every note indexNotes parses.
+var modTime = time.Date(2025, time.January, 6, 12, 0, 0, 0, time.UTC)
+
+// indexNotes parses each note's content, keyed by note ID, and upserts it
+// into a fresh store built with opts. Notes are indexed in ID order.
+func indexNotes(t *testing.T, notes map[model.NoteID]string, opts ...StoreOption) *InMemoryIndexStore {
+	t.Helper()
+	s := NewInMemoryIndexStore(opts...)
+	ids := make([]model.NoteID, 0, len(notes))
+	for id := range notes {
+		ids = append(ids, id)
+	}
+	slices.Sort(ids)
+	for _, id := range ids {
+		s.UpsertParsedNote(parseNote(t, id, notes[id]))
+	}
+	return s
+}
+
+// parseNote parses content as the note id, modified at modTime.
+func parseNote(t *testing.T, id model.NoteID, content string) model.ParsedNote {
+	t.Helper()
+	note := model.Note{ID: id, Path: model.FilePath(id), Title: string(id), ModifiedAt: modTime, Content: content}
+	parsed, err := parser.NewRegexMarkdownParser().Parse(note)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return parsed
+}
+
+// taskIDs lists the IDs of tasks in order.
+func taskIDs(tasks []model.Task) []model.TaskID {
+	ids := []model.TaskID{}
+	for _, task := range tasks {
+		ids = append(ids, task.ID)
+	}
+	return ids
+}
+
+func TestListTasksTextSearchCoversDescription(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"plan.md": "## Tasks\n\n- [ ] [T-1] Plan offsite\n  Book the Venue near the office for forty people\n- [ ] [T-2] Venue review\n- [ ] [T-3] Unrelated\n",
+	})
+	if got := taskIDs(s.ListTasks(TaskFilter{TextSearch: "venue"})); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("text search = %v, want [T-1 T-2]", got)
+	}
+}
+
+func TestListTasksClosedBetween(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"done.md": "## Tasks\n\n" +
+			"- [x] [T-1] Before ✅2025-02-28\n" +
+			"- [x] [T-2] First day ✅2025-03-01\n" +
+			"- [x] [T-3] Last day ✅2025-03-07\n" +
+			"- [x] [T-4] After ✅2025-03-08\n" +
+			"- [ ] [T-5] Still open\n",
+	})
+	week := model.DateRange{
+		Start: time.Date(2025, time.March, 1, 0, 0, 0, 0, time.UTC),
+		End:   time.Date(2025, time.March, 7, 0, 0, 0, 0, time.UTC),
+	}
+	if got := taskIDs(s.ListTasks(TaskFilter{ClosedBetween: &week})); !slices.Equal(got, []model.TaskID{"T-2", "T-3"}) {
+		t.Errorf("closed in week = %v, want [T-2 T-3]", got)
+	}
+}
+
+func TestItemsForTagOrderSurvivesReinsert(t *testing.T) {
+	notes := map[model.NoteID]string{
+		"a.md": "## Tasks\n\n- [ ] [T-9] Oldest ➕2025-01-01 #work\n- [ ] [T-2] Newest ➕2025-03-01 #work\n\n## Log\n\n- first #work\n- second #work\n",
+		"b.md": "## Tasks\n\n- [ ] [T-5] Middle ➕2025-02-01 #work\n- [ ] [T-4] Also middle ➕2025-02-01 #work\n\n## Log\n\n- third #work\n",
+	}
+	s := indexNotes(t, notes)
+
+	before := s.ItemsForTag("work")
+	if got := taskIDs(before.Tasks); !slices.Equal(got, []model.TaskID{"T-9", "T-4", "T-5", "T-2"}) {
+		t.Errorf("tasks = %v, want by creation date then ID", got)
+	}
+	var entries []string
+	for _, entry := range before.LogEntries {
+		entries = append(entries, fmt.Sprintf("%s:%d", entry.NoteID, entry.Line))
+	}
+	if !slices.Equal(entries, []string{"a.md:8", "a.md:9", "b.md:8"}) {
+		t.Errorf("log entries = %v, want by note then line", entries)
+	}
+
+	s.RemoveNote("a.md")
+	s.UpsertParsedNote(parseNote(t, "a.md", notes["a.md"]))
+	after := s.ItemsForTag("work")
+	if !slices.Equal(taskIDs(after.Tasks), taskIDs(before.Tasks)) {
+		t.Errorf("tasks after reinsert = %v, want %v", taskIDs(after.Tasks), taskIDs(before.Tasks))
+	}
+	if !slices.EqualFunc(after.LogEntries, before.LogEntries, func(a, b model.LogEntry) bool { return a.ID == b.ID }) {
+		t.Error("log entry order changed after reinsert")
+	}
+}
+
+func TestTasksAndLogEntriesForNote(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"plan.md":  "## Tasks\n\n- [ ] [T-2] Second\n- [x] [T-1] First\n\n## Log\n\n- kicked off [T-1]\n",
+		"other.md": "## Tasks\n\n- [ ] [T-3] Elsewhere\n\n## Log\n\n- unrelated\n",
+	})
+
+	if got := taskIDs(s.GetTasksForNote("plan.md")); !slices.Equal(got, []model.TaskID{"T-2", "T-1"}) {
+		t.Errorf("tasks = %v, want [T-2 T-1] in line order", got)
+	}
+	entries := s.GetLogEntriesForNote("plan.md")
+	if len(entries) != 1 || entries[0].Content != "kicked off [T-1]" || entries[0].NoteID != "plan.md" {
+		t.Errorf("log entries = %+v, want the one kick-off entry", entries)
+	}
+	if tasks, entries := s.GetTasksForNote("missing.md"), s.GetLogEntriesForNote("missing.md"); len(tasks) != 0 || len(entries) != 0 {
+		t.Errorf("missing note has tasks %v and entries %+v", taskIDs(tasks), entries)
+	}
+}
+
+func TestListTasksBySourceNoteAndStatus(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"plan.md":  "## Tasks\n\n- [ ] [T-1] Open here\n- [x] [T-2] Done here\n- [ ] [T-3] Also open here\n",
+		"other.md": "## Tasks\n\n- [ ] [T-4] Open elsewhere\n",
+	})
+	todo := model.TaskStatusTodo
+
+	if got := taskIDs(s.ListTasks(TaskFilter{SourceNoteID: "plan.md"})); !slices.Equal(got, []model.TaskID{"T-1", "T-2", "T-3"}) {
+		t.Errorf("plan.md tasks = %v, want [T-1 T-2 T-3]", got)
+	}
+	if got := taskIDs(s.ListTasks(TaskFilter{SourceNoteID: "plan.md", Status: &todo})); !slices.Equal(got, []model.TaskID{"T-1", "T-3"}) {
+		t.Errorf("open plan.md tasks = %v, want [T-1 T-3]", got)
+	}
+	if got := taskIDs(s.ListTasks(TaskFilter{SourceNoteID: "missing.md"})); len(got) != 0 {
+		t.Errorf("missing.md tasks = %v, want none", got)
+	}
+}
+
+func TestListTasksIncludeAndExcludeTags(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"work.md": "## Tasks\n\n" +
+			"- [ ] [T-1] Ship the API #work #api\n" +
+			"- [ ] [T-2] Rewrite the API #work #api #Someday\n" +
+			"- [ ] [T-3] Tidy desk #work\n" +
+			"- [ ] [T-4] Learn Rust #someday\n" +
+			"- [ ] [T-5] Paint the fence #home\n",
+	})
+
+	for _, tc := range []struct {
+		filter TaskFilter
+		want   []model.TaskID
+	}{
+		{TaskFilter{ExcludeTags: []string{"someday"}}, []model.TaskID{"T-1", "T-3", "T-5"}},
+		{TaskFilter{Tags: []string{"work"}, ExcludeTags: []string{"someday"}}, []model.TaskID{"T-1", "T-3"}},
+		{TaskFilter{Tags: []string{"work", "api"}, ExcludeTags: []string{"SOMEDAY"}}, []model.TaskID{"T-1"}},
+		{TaskFilter{Tags: []string{"work"}, ExcludeTags: []string{"someday", "api"}}, []model.TaskID{"T-3"}},
+		{TaskFilter{Tags: []string{"work"}, ExcludeTags: []string{"work"}}, []model.TaskID{}},
+	} {
+		if got := taskIDs(s.ListTasks(tc.filter)); !slices.Equal(got, tc.want) {
+			t.Errorf("tags %v excluding %v = %v, want %v", tc.filter.Tags, tc.filter.ExcludeTags, got, tc.want)
+		}
+	}
+}
+
+func TestListTasksTagMatchModes(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"tags.md": "## Tasks\n\n" +
+			"- [ ] [T-1] Both #api #ops\n" +
+			"- [ ] [T-2] Only api #api\n" +
+			"- [ ] [T-3] Only ops #OPS #docs\n" +
+			"- [ ] [T-4] Neither #docs\n" +
+			"- [ ] [T-5] Untagged\n",
+	})
+	tags := []string{"api", "ops"}
+
+	for _, tc := range []struct {
+		mode TagMatch
+		want []model.TaskID
+	}{
+		{"", []model.TaskID{"T-1"}},
+		{TagMatchAll, []model.TaskID{"T-1"}},
+		{TagMatchAny, []model.TaskID{"T-1", "T-2", "T-3"}},
+	} {
+		if got := taskIDs(s.ListTasks(TaskFilter{Tags: tags, TagMatch: tc.mode})); !slices.Equal(got, tc.want) {
+			t.Errorf("tag match %q = %v, want %v", tc.mode, got, tc.want)
+		}
+	}
+	if got := taskIDs(s.ListTasks(TaskFilter{Tags: tags, TagMatch: TagMatchAny, ExcludeTags: []string{"docs"}})); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("any of api, ops excluding docs = %v, want [T-1 T-2]", got)
+	}
+}
+
+func TestTasksVisitsEachTaskOnce(t *testing.T) {
+	notes := map[model.NoteID]string{}
+	for n := range 3 {
+		content := "## Tasks\n\n"
+		for i := 1; i <= 4; i++ {
+			content += fmt.Sprintf("- [ ] [T-%d] Task %d\n", n*4+i, n*4+i)
+		}
+		notes[model.NoteID(fmt.Sprintf("note-%d.md", n))] = content
+	}
+	s := indexNotes(t, notes)
+	// Moving T-1 into another note must not leave a second copy behind.
+	s.UpsertParsedNote(parseNote(t, "note-0.md", "## Tasks\n\n- [ ] [T-2] Task 2\n- [ ] [T-3] Task 3\n- [ ] [T-4] Task 4\n"))
+	s.UpsertParsedNote(parseNote(t, "note-1.md", notes["note-1.md"]+"- [ ] [T-1] Task 1, moved\n"))
+
+	seen := map[model.TaskID]int{}
+	for task := range s.Tasks() {
+		seen[task.ID]++
+	}
+	if len(seen) != 12 {
+		t.Errorf("visited %d distinct tasks, want 12", len(seen))
+	}
+	for id, count := range seen {
+		if count != 1 {
+			t.Errorf("%s visited %d times, want once", id, count)
+		}
+	}
+	noteCount := 0
+	for range s.Notes() {
+		noteCount++
+	}
+	if noteCount != 3 {
+		t.Errorf("visited %d notes, want 3", noteCount)
+	}
+}
+
+func TestDuplicateTaskIDSurvivesRemovingTheWinner(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"a.md": "## Tasks\n\n- [ ] [T-1] From A #home\n",
+		"b.md": "## Tasks\n\n- [ ] [T-1] From B #work\n",
+	})
+	if task, _ := s.GetTask("T-1"); task.NoteID != "b.md" {
+		t.Fatalf("T-1 resolves to %s, want the last indexed b.md", task.NoteID)
+	}
+	if got := s.ItemsForTag("home").Tasks; len(got) != 0 {
+		t.Errorf("shadowed T-1 still listed under #home: %v", taskIDs(got))
+	}
+	if tags := s.ListTags(); !slices.Equal(tags, []string{"work"}) {
+		t.Errorf("tags = %v, want only the winner's", tags)
+	}
+
+	s.RemoveNote("b.md")
+	task, ok := s.GetTask("T-1")
+	if !ok || task.NoteID != "a.md" {
+		t.Fatalf("T-1 = %+v, %v after removing b.md, want a.md's definition", task, ok)
+	}
+	if got := taskIDs(s.ItemsForTag("home").Tasks); !slices.Equal(got, []model.TaskID{"T-1"}) {
+		t.Errorf("#home tasks = %v, want [T-1]", got)
+	}
+	if got := s.ItemsForTag("work").Tasks; len(got) != 0 {
+		t.Errorf("removed definition still listed under #work: %v", taskIDs(got))
+	}
+	if issues := s.VerifyIntegrity(); len(issues) != 0 {
+		t.Errorf("integrity issues: %+v", issues)
+	}
+
+	s.RemoveNote("a.md")
+	if _, ok := s.GetTask("T-1"); ok {
+		t.Error("T-1 still indexed after removing every note defining it")
+	}
+}
+
+func TestUpsertStampsLastIndexed(t *testing.T) {
+	now := time.Date(2025, time.April, 1, 9, 0, 0, 0, time.UTC)
+	s := NewInMemoryIndexStore(WithIndexClock(func() time.Time { return now }))
+	s.UpsertParsedNote(parseNote(t, "plan.md", "# Plan\n"))
+
+	first, ok := s.GetNoteMeta("plan.md")
+	if !ok || !first.LastIndexed.Equal(now) {
+		t.Fatalf("last indexed = %v, %v, want %v", first.LastIndexed, ok, now)
+	}
+	if !first.ModifiedAt.Equal(modTime) {
+		t.Errorf("modified at = %v, want the file's %v", first.ModifiedAt, modTime)
+	}
+
+	now = now.Add(time.Hour)
+	s.UpsertParsedNote(parseNote(t, "plan.md", "# Plan\n\nMore.\n"))
+	second, _ := s.GetNoteMeta("plan.md")
+	if !second.LastIndexed.Equal(now) {
+		t.Errorf("last indexed after re-upsert = %v, want %v", second.LastIndexed, now)
+	}
+}
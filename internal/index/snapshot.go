@@ -0,0 +1,40 @@
+package index
+
+import (
+	"cmp"
+	"slices"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// ExportNotes implements IndexStore. Tasks another note has shadowed are
+// left out, so that an import resolves each task ID as the export did.
+func (s *InMemoryIndexStore) ExportNotes() []model.ParsedNote {
+	mentions := map[model.NoteID][]model.TaskMention{}
+	for _, taskMentions := range s.mentions {
+		for _, mention := range taskMentions {
+			mentions[mention.NoteID] = append(mentions[mention.NoteID], mention)
+		}
+	}
+
+	notes := make([]model.ParsedNote, 0, len(s.notes))
+	for _, id := range s.NoteIDs() {
+		note, _ := s.GetNote(id)
+		parsed := model.ParsedNote{
+			Note:       note,
+			Tasks:      s.GetTasksForNote(id),
+			LogEntries: s.GetLogEntriesForNote(id),
+			Mentions:   append([]model.TaskMention{}, mentions[id]...),
+			Links:      slices.Clone(s.links[id]),
+			Untracked:  slices.Clone(s.untracked[id]),
+			Warnings:   slices.Clone(s.warnings[id]),
+			Outline:    slices.Clone(s.outlines[id]),
+			WordCount:  s.notes[id].WordCount,
+		}
+		slices.SortFunc(parsed.Mentions, func(a, b model.TaskMention) int {
+			return cmp.Or(cmp.Compare(a.Line, b.Line), cmp.Compare(a.TaskID, b.TaskID))
+		})
+		notes = append(notes, parsed)
+	}
+	return notes
+}
@@ -0,0 +1,210 @@
+package index
+
+import (
+	"iter"
+	"regexp"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// IndexStore holds the structured view of the vault and answers queries
+// against it. Implementations replace a note's previous data wholesale on
+// every upsert.
+type IndexStore interface {
+	// UpsertParsedNote replaces everything indexed for the parsed note.
+	UpsertParsedNote(parsed model.ParsedNote)
+	// RemoveNote drops a note and everything extracted from it.
+	RemoveNote(id model.NoteID)
+	// Stats counts the indexed notes, tasks, log entries and tags.
+	Stats() model.IndexStats
+	// NoteIDs returns the IDs of every indexed note.
+	NoteIDs() []model.NoteID
+	// GetNote returns the indexed note, including its content. Stores that
+	// do not keep all content resident may read it back from the vault.
+	GetNote(id model.NoteID) (model.Note, bool)
+	// ResolveAlias returns the note listing alias among its frontmatter
+	// aliases, compared case-insensitively.
+	ResolveAlias(alias string) (model.NoteID, bool)
+	// GetNoteMeta returns the indexed note without its content.
+	GetNoteMeta(id model.NoteID) (model.NoteMeta, bool)
+	// ListNotes returns every indexed note, ordered by date then title with
+	// undated notes last.
+	ListNotes() []model.NoteMeta
+	// Notes yields every indexed note's metadata in no particular order,
+	// without collecting them into a slice. The store must not be modified
+	// while iterating.
+	Notes() iter.Seq[model.NoteMeta]
+	// ListRecentNotes returns the notes most recently modified on disk,
+	// newest first, capped at limit when positive.
+	ListRecentNotes(limit int) []model.NoteMeta
+	// GetTask returns the task with the given ID.
+	GetTask(id model.TaskID) (model.Task, bool)
+	// ListTasks returns the tasks matching filter, ordered by ID.
+	ListTasks(filter TaskFilter) []model.Task
+	// Tasks yields every indexed task in no particular order, without
+	// filtering, sorting or collecting them. The store must not be modified
+	// while iterating.
+	Tasks() iter.Seq[model.Task]
+	// GetLogEntriesForTask returns the log entries referencing a task,
+	// ordered by note ID then line.
+	GetLogEntriesForTask(id model.TaskID) []model.LogEntry
+	// GetMentionsForTask returns the backlinks recorded for a task, ordered
+	// by note ID then line.
+	GetMentionsForTask(id model.TaskID) []model.TaskMention
+	// ListAllMentions returns every recorded task mention, ordered by note
+	// ID then line.
+	ListAllMentions() []model.TaskMention
+	// ListNotesByDate returns the dated notes within r, ordered by date then
+	// title.
+	ListNotesByDate(r model.DateRange) []model.NoteMeta
+	// ListUndatedNotes returns the notes without a date, ordered by title.
+	ListUndatedNotes() []model.NoteMeta
+	// ListLogEntriesByDate returns the log entries of the dated notes within
+	// r, ordered by note date, timestamp, note ID then line.
+	ListLogEntriesByDate(r model.DateRange) []model.LogEntry
+	// GetTasksForNote returns the tasks defined in a note, ordered by line.
+	// Tasks whose ID another note has since claimed are left out.
+	GetTasksForNote(id model.NoteID) []model.Task
+	// GetLogEntriesForNote returns the log entries of a note, ordered by
+	// line.
+	GetLogEntriesForNote(id model.NoteID) []model.LogEntry
+	// TagKey returns the lookup key tag is filed under. Two tags are the
+	// same tag exactly when their keys are equal.
+	TagKey(tag string) string
+	// ListTags returns every known tag in sorted order.
+	ListTags() []string
+	// TagGraph returns, for every known tag, the IDs of the tasks and log
+	// entries carrying it, in one pass over the tag indexes.
+	TagGraph() map[string]model.TagItems
+	// SearchTags returns the tags containing query, ignoring case, with tags
+	// that start with it ahead of those containing it further in, each group
+	// in ListTags order. A positive limit caps the count.
+	SearchTags(query string, limit int) []string
+	// ItemsForTag returns the tasks and log entries carrying exactly tag.
+	// Tasks are ordered by creation time then ID, log entries by note ID
+	// then line.
+	ItemsForTag(tag string) model.TagResult
+	// GetNoteBacklinks returns the wikilinks whose target matches the title,
+	// file stem or an alias of the given note, case-insensitively.
+	GetNoteBacklinks(id model.NoteID) []model.NoteRef
+	// ListUnresolvedLinks returns the wikilinks whose target matches no note.
+	ListUnresolvedLinks() []model.NoteRef
+	// TaskDefinitions maps every task ID to the notes defining it, including
+	// definitions shadowed by another note indexed later.
+	TaskDefinitions() map[model.TaskID][]model.NoteID
+	// GetNoteOutline returns the headings of a note in document order.
+	GetNoteOutline(id model.NoteID) ([]model.Heading, bool)
+	// ListUntrackedTasks returns the checkbox bullets that lack a task ID.
+	ListUntrackedTasks() []model.UntrackedTask
+	// ListParseWarnings returns the lines the parser skipped although they
+	// look meant to be indexed, ordered by note ID then line.
+	ListParseWarnings() []model.ParseWarning
+	// VerifyIntegrity reports inconsistencies between the store's entities
+	// and its reverse indexes.
+	VerifyIntegrity() []model.IntegrityIssue
+	// ExportNotes reconstructs the parsed form of every indexed note, ordered
+	// by note ID. Upserting the result into an empty store reproduces this
+	// one.
+	ExportNotes() []model.ParsedNote
+	// Compact prunes dangling reverse-index entries, returning how many it
+	// removed.
+	Compact() int
+}
+
+// TagMatch selects whether a TaskFilter's Tags must all be present on a task
+// or any one of them suffices.
+type TagMatch string
+
+const (
+	// TagMatchAll keeps tasks carrying every tag in the filter.
+	TagMatchAll TagMatch = "all"
+	// TagMatchAny keeps tasks carrying at least one tag in the filter.
+	TagMatchAny TagMatch = "any"
+)
+
+// TaskFilter narrows ListTasks results. Zero-valued fields match everything.
+type TaskFilter struct {
+	// Status keeps only tasks in this state.
+	Status *model.TaskStatus
+	// SourceNoteID keeps only tasks defined in this note.
+	SourceNoteID model.NoteID
+	// Tags keeps only tasks carrying every listed tag, or any of them under
+	// TagMatchAny, compared using the store's tag normalization.
+	Tags []string
+	// TagMatch selects how Tags combine; the zero value means TagMatchAll.
+	TagMatch TagMatch
+	// ExcludeTags drops tasks carrying any listed tag, compared the same way.
+	ExcludeTags []string
+	// TextSearch keeps tasks whose title or description contains the text,
+	// ignoring case.
+	TextSearch string
+	// TextPattern keeps tasks whose title or description matches the
+	// expression.
+	TextPattern *regexp.Regexp
+	// TouchedSince keeps tasks updated on or after this day.
+	TouchedSince *time.Time
+	// ClosedBetween keeps completed tasks whose completion day falls within
+	// the range.
+	ClosedBetween *model.DateRange
+	// AfterID keeps only tasks ordered after this ID, for pagination.
+	AfterID model.TaskID
+	// Limit caps the number of tasks returned when positive.
+	Limit int
+}
+
+// matches reports whether task satisfies the filter, comparing tags by the
+// keys tagKey produces.
+func (f TaskFilter) matches(task model.Task, tagKey func(string) string) bool {
+	if f.Status != nil && task.Status != *f.Status {
+		return false
+	}
+	if f.SourceNoteID != "" && task.NoteID != f.SourceNoteID {
+		return false
+	}
+	if len(f.Tags) > 0 {
+		carries := func(tag string) bool {
+			key := tagKey(tag)
+			return slices.ContainsFunc(task.Tags, func(t string) bool { return tagKey(t) == key })
+		}
+		if f.TagMatch == TagMatchAny {
+			if !slices.ContainsFunc(f.Tags, carries) {
+				return false
+			}
+		} else {
+			for _, tag := range f.Tags {
+				if !carries(tag) {
+					return false
+				}
+			}
+		}
+	}
+	for _, tag := range f.ExcludeTags {
+		key := tagKey(tag)
+		if slices.ContainsFunc(task.Tags, func(t string) bool { return tagKey(t) == key }) {
+			return false
+		}
+	}
+	if f.TextSearch != "" {
+		needle := strings.ToLower(f.TextSearch)
+		if !strings.Contains(strings.ToLower(task.Title), needle) &&
+			!strings.Contains(strings.ToLower(task.Description), needle) {
+			return false
+		}
+	}
+	if f.TextPattern != nil && !f.TextPattern.MatchString(task.Title) && !f.TextPattern.MatchString(task.Description) {
+		return false
+	}
+	if f.TouchedSince != nil && task.UpdatedAt.Before(*f.TouchedSince) {
+		return false
+	}
+	if f.ClosedBetween != nil && (task.CompletedAt == nil || !f.ClosedBetween.Contains(*task.CompletedAt)) {
+		return false
+	}
+	if f.AfterID != "" && task.ID <= f.AfterID {
+		return false
+	}
+	return true
+}
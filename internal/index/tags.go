@@ -0,0 +1,19 @@
+package index
+
+import (
+	"strings"
+
+	"golang.org/x/text/unicode/norm"
+)
+
+// NormalizeTag returns the lookup key for a tag: its NFC form, lowercased.
+// "#Projects" and "#projects", or a precomposed and a decomposed "é", share
+// one key.
+func NormalizeTag(tag string) string {
+	return strings.ToLower(norm.NFC.String(tag))
+}
+
+// ExactTag uses the tag verbatim as its lookup key.
+func ExactTag(tag string) string {
+	return tag
+}
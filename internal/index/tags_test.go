@@ -0,0 +1,103 @@
+package index
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestNormalizeTag(t *testing.T) {
+	if NormalizeTag("Projects") != NormalizeTag("projects") {
+		t.Error("case variants normalize differently")
+	}
+	if NormalizeTag("caf\u00e9") != NormalizeTag("cafe\u0301") {
+		t.Error("precomposed and decomposed é normalize differently")
+	}
+}
+
+func TestTagsDifferingInCaseShareABucket(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"a.md": "## Tasks\n\n- [ ] [T-1] First #Projects\n",
+		"b.md": "## Tasks\n\n- [ ] [T-2] Second #projects\n\n## Log\n\n- noted #PROJECTS\n",
+	})
+
+	if tags := s.ListTags(); len(tags) != 1 {
+		t.Errorf("tags = %v, want a single bucket", tags)
+	}
+	for _, query := range []string{"projects", "Projects", "PROJECTS"} {
+		result := s.ItemsForTag(query)
+		if got := taskIDs(result.Tasks); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+			t.Errorf("ItemsForTag(%q) tasks = %v, want [T-1 T-2]", query, got)
+		}
+		if len(result.LogEntries) != 1 {
+			t.Errorf("ItemsForTag(%q) log entries = %+v, want one", query, result.LogEntries)
+		}
+	}
+
+	exact := indexNotes(t, map[model.NoteID]string{
+		"a.md": "## Tasks\n\n- [ ] [T-1] First #Projects\n- [ ] [T-2] Second #projects\n",
+	}, WithTagNormalizer(ExactTag))
+	if tags := exact.ListTags(); len(tags) != 2 {
+		t.Errorf("tags with ExactTag = %v, want two buckets", tags)
+	}
+}
+
+func TestSearchTagsRanksPrefixMatchesFirst(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"a.md": "## Tasks\n\n- [ ] [T-1] Sync #api/reproject #home\n- [ ] [T-2] Build #Projects/note-app\n",
+		"b.md": "## Log\n\n- slides #projection\n",
+	})
+
+	for _, query := range []string{"proj", "PROJ"} {
+		if got, want := s.SearchTags(query, 0), []string{"projection", "Projects/note-app", "api/reproject"}; !slices.Equal(got, want) {
+			t.Errorf("SearchTags(%q) = %v, want %v", query, got, want)
+		}
+	}
+	if got, want := s.SearchTags("note-app", 0), []string{"Projects/note-app"}; !slices.Equal(got, want) {
+		t.Errorf("SearchTags(note-app) = %v, want %v", got, want)
+	}
+	if got, want := s.SearchTags("proj", 2), []string{"projection", "Projects/note-app"}; !slices.Equal(got, want) {
+		t.Errorf("SearchTags(proj, 2) = %v, want %v", got, want)
+	}
+	if got := s.SearchTags("zzz", 0); len(got) != 0 {
+		t.Errorf("SearchTags(zzz) = %v, want none", got)
+	}
+}
+
+func TestTagGraphMatchesItemsForTag(t *testing.T) {
+	s := indexNotes(t, map[model.NoteID]string{
+		"a.md": "## Tasks\n\n- [ ] [T-2] Deploy #ops #api\n- [ ] [T-1] Design #api\n\n## Log\n\n- paged #ops\n- reviewed #api #docs\n",
+		"b.md": "## Tasks\n\n- [ ] [T-3] Write guide #docs\n\n## Log\n\n- drafted #docs\n",
+	})
+
+	graph := s.TagGraph()
+	tags := s.ListTags()
+	if len(graph) != len(tags) {
+		t.Errorf("graph has %d tags, want %d: %v", len(graph), len(tags), tags)
+	}
+	for _, tag := range tags {
+		items, ok := graph[tag]
+		if !ok {
+			t.Errorf("tag %q missing from the graph", tag)
+			continue
+		}
+		result := s.ItemsForTag(tag)
+		wantTasks := taskIDs(result.Tasks)
+		slices.Sort(wantTasks)
+		wantEntries := []model.LogEntryID{}
+		for _, entry := range result.LogEntries {
+			wantEntries = append(wantEntries, entry.ID)
+		}
+		slices.Sort(wantEntries)
+		if !slices.Equal(items.TaskIDs, wantTasks) || !slices.Equal(items.LogEntryIDs, wantEntries) {
+			t.Errorf("graph[%q] = %+v, want tasks %v and entries %v", tag, items, wantTasks, wantEntries)
+		}
+	}
+	if got := graph["api"].TaskIDs; !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("api tasks = %v, want [T-1 T-2]", got)
+	}
+	if got := graph["docs"]; !slices.Equal(got.TaskIDs, []model.TaskID{"T-3"}) || len(got.LogEntryIDs) != 2 {
+		t.Errorf("docs = %+v, want T-3 and two log entries", got)
+	}
+}
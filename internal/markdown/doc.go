@@ -0,0 +1,3 @@
+// Package markdown holds the line-level Markdown scanning shared by the vault
+// and the parser.
+package markdown
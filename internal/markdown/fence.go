@@ -0,0 +1,33 @@
+package markdown
+
+import (
+	"regexp"
+	"strings"
+)
+
+var fenceRE = regexp.MustCompile("^\\s*(`{3,}|~{3,})(.*)$")
+
+// Fence tracks whether a walk over a note's lines is inside a fenced code
+// block. The zero value is outside any block.
+type Fence struct {
+	marker string
+}
+
+// Skip reports whether line belongs to a fenced code block, either as a
+// fence or as code, and should not be parsed as Markdown structure. A block
+// opens with three or more backticks or tildes at any indentation and
+// closes with a fence of the same character at least as long.
+func (f *Fence) Skip(line string) bool {
+	m := fenceRE.FindStringSubmatch(line)
+	if f.marker == "" {
+		if m == nil || (m[1][0] == '`' && strings.Contains(m[2], "`")) {
+			return false
+		}
+		f.marker = m[1]
+		return true
+	}
+	if m != nil && m[1][0] == f.marker[0] && len(m[1]) >= len(f.marker) && strings.TrimSpace(m[2]) == "" {
+		f.marker = ""
+	}
+	return true
+}
@@ -0,0 +1,18 @@
+package markdown
+
+import "strings"
+
+// FrontmatterEnd returns the index of the first line after a frontmatter
+// block fenced by "---" lines at the very start of a note, or 0 when there
+// is none. An unclosed block is not frontmatter.
+func FrontmatterEnd(lines []string) int {
+	if len(lines) == 0 || strings.TrimSpace(lines[0]) != "---" {
+		return 0
+	}
+	for i, line := range lines[1:] {
+		if line = strings.TrimSpace(line); line == "---" || line == "..." {
+			return i + 2
+		}
+	}
+	return 0
+}
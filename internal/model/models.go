@@ -1,16 +1,43 @@
 package model
 
 import (
+	"encoding/json"
+	"path/filepath"
+	"strings"
 	"time"
 )
 
-// NoteID uniquely identifies a note.
+// NoteID uniquely identifies a note. For file-backed vaults it is the
+// slash-separated path of the note relative to the vault root, e.g.
+// "journal/2025-04-01.md".
 type NoteID string
 
+// FilePath is a file location in the host's native form. It is encoded in
+// JSON with forward slashes so serialized notes compare equal across
+// operating systems, and decoding accepts either separator.
+type FilePath string
+
+// MarshalJSON implements json.Marshaler.
+func (p FilePath) MarshalJSON() ([]byte, error) {
+	return json.Marshal(filepath.ToSlash(string(p)))
+}
+
+// UnmarshalJSON implements json.Unmarshaler.
+func (p *FilePath) UnmarshalJSON(data []byte) error {
+	var s string
+	if err := json.Unmarshal(data, &s); err != nil {
+		return err
+	}
+	*p = FilePath(filepath.FromSlash(strings.ReplaceAll(s, `\`, "/")))
+	return nil
+}
+
 // TaskID uniquely identifies a task.
 type TaskID string
 
-// LogEntryID uniquely identifies a log entry extracted from a note.
+// LogEntryID uniquely identifies a log entry extracted from a note. It is
+// derived from the note ID and the entry's content, not its line, so it is
+// stable while the entry itself is unchanged.
 type LogEntryID string
 
 // TaskStatus captures the lifecycle state for a task.
@@ -28,26 +55,44 @@ const (
 )
 
 // Note is the source-of-truth representation for a Markdown file in the vault.
+// Date is nil for notes whose location does not encode a calendar day.
+// DateRange is set for notes covering several days; their Date is the range
+// start unless given explicitly.
+// ModifiedAt is the file's modification time when the note was read.
 type Note struct {
-	ID      NoteID    `json:"id"`
-	Path    string    `json:"path"`
-	Title   string    `json:"title"`
-	Date    time.Time `json:"date"`
-	Content string    `json:"content"`
+	ID         NoteID     `json:"id"`
+	Path       FilePath   `json:"path"`
+	Title      string     `json:"title"`
+	Aliases    []string   `json:"aliases,omitempty"`
+	Date       *time.Time `json:"date,omitempty"`
+	DateRange  *DateRange `json:"date_range,omitempty"`
+	ModifiedAt time.Time  `json:"modified_at"`
+	Content    string     `json:"content"`
+	// Lossy is set when the file was not valid UTF-8 and its invalid bytes
+	// were replaced with U+FFFD in Content.
+	Lossy bool `json:"lossy,omitempty"`
 }
 
-// Task models a first-class task extracted from a note.
+// Task models a first-class task extracted from a note. Description is the
+// task's continuation text with dates stripped and lines trimmed;
+// DescriptionMD is the indented block under the task line as written,
+// paragraphs and all. BlockedBy lists the tasks this one waits on, from a
+// "blocked-by:T-1,T-2" token.
 type Task struct {
-	ID          TaskID     `json:"id"`
-	NoteID      NoteID     `json:"note_id"`
-	Title       string     `json:"title"`
-	Status      TaskStatus `json:"status"`
-	Tags        []string   `json:"tags"`
-	CreatedAt   time.Time  `json:"created_at"`
-	UpdatedAt   time.Time  `json:"updated_at"`
-	CompletedAt *time.Time `json:"completed_at,omitempty"`
-	DueDate     *time.Time `json:"due_date,omitempty"`
-	Line        int        `json:"line"`
+	ID            TaskID     `json:"id"`
+	NoteID        NoteID     `json:"note_id"`
+	Title         string     `json:"title"`
+	Description   string     `json:"description,omitempty"`
+	DescriptionMD string     `json:"description_md,omitempty"`
+	Status        TaskStatus `json:"status"`
+	Tags          []string   `json:"tags"`
+	BlockedBy     []TaskID   `json:"blocked_by,omitempty"`
+	CreatedAt     time.Time  `json:"created_at"`
+	UpdatedAt     time.Time  `json:"updated_at"`
+	CompletedAt   *time.Time `json:"completed_at,omitempty"`
+	DueDate       *time.Time `json:"due_date,omitempty"`
+	Line          int        `json:"line"`
+	Column        int        `json:"column"`
 }
 
 // LogEntry captures structured log lines parsed from a note.
@@ -69,3 +114,342 @@ type TaskMention struct {
 	Context string   `json:"context"`
 	Tags    []string `json:"tags"`
 }
+
+// NoteLink is a [[wikilink]] found in a note, recorded by its target text.
+type NoteLink struct {
+	NoteID  NoteID `json:"note_id"`
+	Target  string `json:"target"`
+	Line    int    `json:"line"`
+	Context string `json:"context"`
+}
+
+// NoteRef is a wikilink seen from its target: which note links there, on
+// which line, and an excerpt of the referencing line. Resolved is false when
+// no note title matches Target.
+type NoteRef struct {
+	SourceID NoteID `json:"source_id"`
+	TargetID NoteID `json:"target_id,omitempty"`
+	Target   string `json:"target"`
+	Line     int    `json:"line"`
+	Context  string `json:"context"`
+	Resolved bool   `json:"resolved"`
+}
+
+// UntrackedTask is a checkbox bullet in a Tasks section that carries no task
+// ID and therefore is not indexed as a task.
+type UntrackedTask struct {
+	NoteID NoteID `json:"note_id"`
+	Line   int    `json:"line"`
+	Text   string `json:"text"`
+}
+
+// ParseWarningKind classifies a ParseWarning.
+type ParseWarningKind string
+
+const (
+	// ParseWarningMissingTaskID flags a checkbox bullet in a Tasks section
+	// with no task ID.
+	ParseWarningMissingTaskID ParseWarningKind = "missing_task_id"
+	// ParseWarningInvalidTaskID flags a checkbox bullet in a Tasks section
+	// whose bracketed ID does not match the task ID pattern.
+	ParseWarningInvalidTaskID ParseWarningKind = "invalid_task_id"
+	// ParseWarningUnknownCheckbox flags a bullet in a Tasks section whose
+	// checkbox holds an unrecognized glyph.
+	ParseWarningUnknownCheckbox ParseWarningKind = "unknown_checkbox"
+	// ParseWarningDuplicateTaskID flags a task line in a Tasks section
+	// reusing an ID defined earlier in the same note.
+	ParseWarningDuplicateTaskID ParseWarningKind = "duplicate_task_id"
+)
+
+// ParseWarning flags a line the parser skipped although it looks meant to be
+// indexed, or one it indexed that conflicts with an earlier line.
+type ParseWarning struct {
+	NoteID  NoteID           `json:"note_id"`
+	Line    int              `json:"line"`
+	Kind    ParseWarningKind `json:"kind"`
+	Message string           `json:"message"`
+}
+
+// Heading is a Markdown ATX heading ("#" through "######") in a note.
+type Heading struct {
+	Level int    `json:"level"`
+	Text  string `json:"text"`
+	Line  int    `json:"line"`
+}
+
+// OutlineNode is a heading together with the headings nested beneath it.
+type OutlineNode struct {
+	Heading
+	Children []OutlineNode `json:"children"`
+}
+
+// ParsedNote is the structured result of parsing a single note.
+type ParsedNote struct {
+	Note       Note            `json:"note"`
+	Tasks      []Task          `json:"tasks"`
+	LogEntries []LogEntry      `json:"log_entries"`
+	Mentions   []TaskMention   `json:"mentions"`
+	Links      []NoteLink      `json:"links"`
+	Untracked  []UntrackedTask `json:"untracked"`
+	Warnings   []ParseWarning  `json:"warnings"`
+	Outline    []Heading       `json:"outline"`
+	WordCount  int             `json:"word_count"`
+}
+
+// Snapshot is a serialized index: every indexed note with what was
+// extracted from it.
+type Snapshot struct {
+	Version int          `json:"version"`
+	Notes   []ParsedNote `json:"notes"`
+}
+
+// NoteMeta is the lightweight view of a note kept resident in the index.
+type NoteMeta struct {
+	ID         NoteID     `json:"id"`
+	Path       FilePath   `json:"path"`
+	Title      string     `json:"title"`
+	Aliases    []string   `json:"aliases,omitempty"`
+	Date       *time.Time `json:"date,omitempty"`
+	DateRange  *DateRange `json:"date_range,omitempty"`
+	ModifiedAt time.Time  `json:"modified_at"`
+	// WordCount is the number of words in the note's prose, not counting
+	// frontmatter or fenced code. It is set when the note is indexed.
+	WordCount int `json:"word_count"`
+	// LastIndexed is when the note was last parsed into the index. A
+	// ModifiedAt on disk later than this means the index is stale.
+	LastIndexed time.Time `json:"last_indexed"`
+	// Lossy is set when the note's file was not valid UTF-8, as for Note.
+	Lossy bool `json:"lossy,omitempty"`
+}
+
+// Meta returns the note's metadata without its content, word count or
+// indexing time.
+func (n Note) Meta() NoteMeta {
+	return NoteMeta{
+		ID:         n.ID,
+		Path:       n.Path,
+		Title:      n.Title,
+		Aliases:    n.Aliases,
+		Date:       n.Date,
+		DateRange:  n.DateRange,
+		ModifiedAt: n.ModifiedAt,
+		Lossy:      n.Lossy,
+	}
+}
+
+// DateRange is an inclusive range of calendar days.
+type DateRange struct {
+	Start time.Time `json:"start"`
+	End   time.Time `json:"end"`
+}
+
+// Contains reports whether the calendar day of t falls within the range.
+func (r DateRange) Contains(t time.Time) bool {
+	day := time.Date(t.Year(), t.Month(), t.Day(), 0, 0, 0, 0, time.UTC)
+	return !day.Before(r.Start) && !day.After(r.End)
+}
+
+// Overlaps reports whether the two ranges share at least one day.
+func (r DateRange) Overlaps(other DateRange) bool {
+	return !r.End.Before(other.Start) && !other.End.Before(r.Start)
+}
+
+// TagResult groups the tasks and log entries carrying a tag.
+type TagResult struct {
+	Tag        string     `json:"tag"`
+	Tasks      []Task     `json:"tasks"`
+	LogEntries []LogEntry `json:"log_entries"`
+}
+
+// TaskDetail bundles a task with everything that references it.
+type TaskDetail struct {
+	Task       Task          `json:"task"`
+	Mentions   []TaskMention `json:"mentions"`
+	LogEntries []LogEntry    `json:"log_entries"`
+}
+
+// NoteContents groups the tasks and log entries extracted from one note.
+type NoteContents struct {
+	NoteID     NoteID     `json:"note_id"`
+	Tasks      []Task     `json:"tasks"`
+	LogEntries []LogEntry `json:"log_entries"`
+}
+
+// NoteStats reports the size of a note.
+type NoteStats struct {
+	NoteID         NoteID `json:"note_id"`
+	WordCount      int    `json:"word_count"`
+	ReadingMinutes int    `json:"reading_minutes"`
+}
+
+// ArchiveReport describes a run of ArchiveCompleted: the archive note, how
+// many tasks moved into it, and which tasks came from which note.
+type ArchiveReport struct {
+	Archive NoteID          `json:"archive"`
+	Moved   int             `json:"moved"`
+	Sources []ArchiveSource `json:"sources"`
+}
+
+// ArchiveSource lists the tasks archived out of one note, in line order.
+type ArchiveSource struct {
+	NoteID NoteID   `json:"note_id"`
+	Tasks  []TaskID `json:"tasks"`
+}
+
+// Validation is what parsing some content would produce, without any note
+// being written or indexed: its tasks and log entries, parse warnings, and
+// the Tasks and Log headings it lacks.
+type Validation struct {
+	Tasks           []Task         `json:"tasks"`
+	LogEntries      []LogEntry     `json:"log_entries"`
+	Warnings        []ParseWarning `json:"warnings"`
+	MissingSections []string       `json:"missing_sections"`
+}
+
+// WeeklySummary reports the activity recorded within a date range, along
+// with the standing backlog at its end.
+type WeeklySummary struct {
+	Range          DateRange    `json:"range"`
+	Notes          []NoteMeta   `json:"notes"`
+	NewTasks       []Task       `json:"new_tasks"`
+	CompletedTasks []Task       `json:"completed_tasks"`
+	StatusCounts   StatusCounts `json:"status_counts"`
+	// WordCount totals the word counts of Notes.
+	WordCount int `json:"word_count"`
+}
+
+// StatusCounts tallies tasks by status. The JSON keys are the TaskStatus
+// values.
+type StatusCounts struct {
+	Todo       int `json:"todo"`
+	InProgress int `json:"in_progress"`
+	Blocked    int `json:"blocked"`
+	Done       int `json:"done"`
+}
+
+// Add counts one task with the given status.
+func (c *StatusCounts) Add(status TaskStatus) {
+	switch status {
+	case TaskStatusTodo:
+		c.Todo++
+	case TaskStatusInProgress:
+		c.InProgress++
+	case TaskStatusBlocked:
+		c.Blocked++
+	case TaskStatusDone:
+		c.Done++
+	}
+}
+
+// Dashboard aggregates vault-wide figures with the task activity of the
+// Monday–Sunday week containing a day.
+type Dashboard struct {
+	Week              DateRange    `json:"week"`
+	StatusCounts      StatusCounts `json:"status_counts"`
+	Notes             int          `json:"notes"`
+	Tags              int          `json:"tags"`
+	CreatedThisWeek   int          `json:"created_this_week"`
+	CompletedThisWeek int          `json:"completed_this_week"`
+}
+
+// WriteDiff describes how replacing a note's content would change what is
+// indexed for it. Log entries are compared by content, so an edited entry
+// shows as one removed and one added.
+type WriteDiff struct {
+	NoteID            NoteID   `json:"note_id"`
+	AddedTasks        []TaskID `json:"added_tasks"`
+	RemovedTasks      []TaskID `json:"removed_tasks"`
+	ChangedTasks      []TaskID `json:"changed_tasks"`
+	AddedLogEntries   []string `json:"added_log_entries"`
+	RemovedLogEntries []string `json:"removed_log_entries"`
+	AddedTags         []string `json:"added_tags"`
+	RemovedTags       []string `json:"removed_tags"`
+}
+
+// DuplicateTaskID is a task ID defined more than once across the vault. A
+// note appears once per definition it contains.
+type DuplicateTaskID struct {
+	TaskID  TaskID   `json:"task_id"`
+	NoteIDs []NoteID `json:"note_ids"`
+}
+
+// LintReport collects read-only vault diagnostics.
+type LintReport struct {
+	DuplicateTaskIDs []DuplicateTaskID `json:"duplicate_task_ids"`
+	UntrackedTasks   []UntrackedTask   `json:"untracked_tasks"`
+	UnresolvedLinks  []NoteRef         `json:"unresolved_links"`
+	OrphanedMentions []TaskMention     `json:"orphaned_mentions"`
+	ParseWarnings    []ParseWarning    `json:"parse_warnings"`
+}
+
+// ChangeSet lists the notes and tasks affected by a change to the index,
+// including ones that were removed.
+type ChangeSet struct {
+	NoteIDs []NoteID `json:"note_ids"`
+	TaskIDs []TaskID `json:"task_ids"`
+}
+
+// IndexStats counts what is currently indexed.
+type IndexStats struct {
+	Notes      int `json:"notes"`
+	Tasks      int `json:"tasks"`
+	LogEntries int `json:"log_entries"`
+	Tags       int `json:"tags"`
+}
+
+// ReindexReport summarizes a full reindex: how many notes were indexed,
+// which could not be, and which were indexed from a lossy decode.
+type ReindexReport struct {
+	Indexed int            `json:"indexed"`
+	Errors  []ReindexError `json:"errors"`
+	Lossy   []FilePath     `json:"lossy"`
+}
+
+// ReindexError records a note a reindex skipped and why.
+type ReindexError struct {
+	Path  FilePath `json:"path"`
+	Error string   `json:"error"`
+}
+
+// TaskEventKind distinguishes the entries of a task timeline.
+type TaskEventKind string
+
+const (
+	// TaskEventCreated marks when a task was created.
+	TaskEventCreated TaskEventKind = "created"
+	// TaskEventLogged is a log entry referencing the task.
+	TaskEventLogged TaskEventKind = "logged"
+	// TaskEventCompleted marks when a task was completed.
+	TaskEventCompleted TaskEventKind = "completed"
+)
+
+// TaskEvent is one entry in a task's timeline. LogEntry is set for
+// TaskEventLogged events; NoteID and Line locate the event's source.
+type TaskEvent struct {
+	Kind     TaskEventKind `json:"kind"`
+	Time     time.Time     `json:"time"`
+	NoteID   NoteID        `json:"note_id"`
+	Line     int           `json:"line"`
+	LogEntry *LogEntry     `json:"log_entry,omitempty"`
+}
+
+// TagItems lists the IDs of the tasks and log entries carrying a tag, each
+// sorted.
+type TagItems struct {
+	TaskIDs     []TaskID     `json:"task_ids"`
+	LogEntryIDs []LogEntryID `json:"log_entry_ids"`
+}
+
+// TagCount pairs a tag with how many items it appears on.
+type TagCount struct {
+	Tag   string `json:"tag"`
+	Count int    `json:"count"`
+}
+
+// IntegrityIssue is an inconsistency found in an index's internal maps.
+// Index names the map, Key the entry and Problem describes what is wrong.
+type IntegrityIssue struct {
+	Index   string `json:"index"`
+	Key     string `json:"key"`
+	Problem string `json:"problem"`
+}
@@ -0,0 +1,50 @@
+package model
+
+import (
+	"encoding/json"
+	"path/filepath"
+	"testing"
+)
+
+func TestFilePathJSONUsesForwardSlashes(t *testing.T) {
+	// Joined with the host separator, then extended with a slash: mixed
+	// separators on Windows.
+	path := FilePath(filepath.Join("vault", "journal") + "/2025-04-01.md")
+	for name, value := range map[string]any{
+		"path":      path,
+		"note":      Note{Path: path},
+		"note meta": NoteMeta{Path: path},
+	} {
+		encoded, err := json.Marshal(value)
+		if err != nil {
+			t.Fatal(err)
+		}
+		var decoded struct {
+			Path string `json:"path"`
+		}
+		if name == "path" {
+			err = json.Unmarshal(encoded, &decoded.Path)
+		} else {
+			err = json.Unmarshal(encoded, &decoded)
+		}
+		if err != nil {
+			t.Fatal(err)
+		}
+		if decoded.Path != "vault/journal/2025-04-01.md" {
+			t.Errorf("%s path encodes as %q, want vault/journal/2025-04-01.md", name, decoded.Path)
+		}
+	}
+}
+
+func TestFilePathJSONAcceptsEitherSeparator(t *testing.T) {
+	want := FilePath(filepath.Join("vault", "journal", "2025-04-01.md"))
+	for _, encoded := range []string{`"vault/journal/2025-04-01.md"`, `"vault\\journal\\2025-04-01.md"`, `"vault\\journal/2025-04-01.md"`} {
+		var got FilePath
+		if err := json.Unmarshal([]byte(encoded), &got); err != nil {
+			t.Fatal(err)
+		}
+		if got != want {
+			t.Errorf("%s decodes as %q, want %q", encoded, got, want)
+		}
+	}
+}
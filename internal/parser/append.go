@@ -0,0 +1,52 @@
+package parser
+
+import (
+	"strings"
+
+	"github.com/weakphish/yapper/internal/markdown"
+)
+
+// AppendToTasks appends lines to the end of content. When content does not
+// already end in a Tasks section, a "## Tasks" heading is added first so the
+// lines are parsed as tasks.
+func AppendToTasks(content string, lines []string) string {
+	return appendToSection(content, lines, sectionTasks, "## Tasks")
+}
+
+// AppendToLog appends lines to the end of content. When content does not
+// already end in a Log section, a "## Log" heading is added first so the
+// lines are parsed as log entries.
+func AppendToLog(content string, lines []string) string {
+	return appendToSection(content, lines, sectionLog, "## Log")
+}
+
+// appendToSection appends lines to content, preceded by heading unless the
+// last heading outside code fences already opens sec.
+func appendToSection(content string, lines []string, sec section, heading string) string {
+	last := sectionNone
+	var fence markdown.Fence
+	for _, line := range splitLines(content) {
+		if fence.Skip(line) {
+			continue
+		}
+		if m := headingRE.FindStringSubmatch(line); m != nil {
+			last = sectionFor(m[2])
+		}
+	}
+
+	var b strings.Builder
+	b.WriteString(strings.TrimRight(content, "\r\n"))
+	if last != sec {
+		if b.Len() > 0 {
+			b.WriteString("\n\n")
+		}
+		b.WriteString(heading)
+		b.WriteString("\n")
+	}
+	for _, line := range lines {
+		b.WriteString("\n")
+		b.WriteString(line)
+	}
+	b.WriteString("\n")
+	return b.String()
+}
@@ -0,0 +1,44 @@
+package parser
+
+import (
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// TaskBlockLen reports how many lines the task id occupies starting at
+// lines[i]: its checkbox line plus the indented description block under it.
+// It reports false when lines[i] is not that task's checkbox line, which is
+// recognized as SetTaskLineStatus does.
+func TaskBlockLen(lines []string, i int, id model.TaskID) (int, bool) {
+	if i < 0 || i >= len(lines) {
+		return 0, false
+	}
+	m := checkboxRE.FindStringSubmatchIndex(lines[i])
+	if m == nil || !strings.Contains(lines[i][m[4]:], "["+string(id)+"]") {
+		return 0, false
+	}
+	_, n := collectDescriptionBlock(lines[i+1:], bulletColumn(lines[i])-1)
+	return 1 + n, true
+}
+
+// ArchivedTaskBlock prepares a task block for an archive note. The block is
+// dedented so its checkbox line sits at the top level. A "✅YYYY-MM-DD" token
+// for completed is added to that line unless the block already carries one,
+// so the completion date survives the move.
+func ArchivedTaskBlock(block []string, completed *time.Time) []string {
+	if len(block) == 0 {
+		return nil
+	}
+	indent := block[0][:bulletColumn(block[0])-1]
+	archived := make([]string, len(block))
+	for i, line := range block {
+		archived[i] = strings.TrimPrefix(line, indent)
+	}
+	if completed != nil && !strings.Contains(strings.Join(block, "\n"), "✅") {
+		first := strings.TrimRight(archived[0], "\r")
+		archived[0] = first + " ✅" + completed.Format("2006-01-02") + archived[0][len(first):]
+	}
+	return archived
+}
@@ -0,0 +1,12 @@
+package parser
+
+import "github.com/weakphish/yapper/internal/model"
+
+// NoteParser turns a note's Markdown into tasks, log entries and mentions.
+// The strategy is pluggable: RegexMarkdownParser is the line-based v1
+// implementation, and Tree-sitter or AST-based parsers can replace it without
+// touching the index or domain layers. Parsers used for concurrent reindexing
+// must be safe for concurrent use.
+type NoteParser interface {
+	Parse(note model.Note) (model.ParsedNote, error)
+}
@@ -0,0 +1,593 @@
+package parser
+
+import (
+	"cmp"
+	"crypto/sha256"
+	"fmt"
+	"regexp"
+	"slices"
+	"strings"
+	"time"
+	"unicode/utf8"
+
+	"github.com/weakphish/yapper/internal/markdown"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// DefaultExcerptRadius is the number of characters kept on each side of a
+// task reference or wikilink in an excerpt.
+const DefaultExcerptRadius = 60
+
+// maxWrappedTitleLen is the longest single continuation line still treated
+// as a wrapped part of the task title rather than as its description.
+const maxWrappedTitleLen = 40
+
+var (
+	headingRE  = regexp.MustCompile(`^(#{1,6})\s+(.*?)\s*$`)
+	checkboxRE = regexp.MustCompile(`^\s*[-*+]\s+\[(` + glyphClass + `)\]\s*(.*)$`)
+	bulletRE   = regexp.MustCompile(`^\s*[-*+]\s+(.*)$`)
+	logTimeRE  = regexp.MustCompile(`^(\d{1,2}:\d{2})\s`)
+	taskDateRE = regexp.MustCompile(`(➕|✅)\s?(\d{4}-\d{2}-\d{2})`)
+	blockedRE  = regexp.MustCompile(`(^|\s)blocked-by:(\S+)`)
+	wikilinkRE = regexp.MustCompile(`\[\[([^\[\]|#]+)(?:#[^\[\]|]*)?(?:\|[^\[\]]*)?\]\]`)
+)
+
+type section int
+
+const (
+	sectionNone section = iota
+	sectionTasks
+	sectionLog
+)
+
+// RegexMarkdownParser is the v1 NoteParser. It walks a note line by line,
+// treating checkbox bullets under a "Tasks" heading as tasks and bullets under
+// a "Log" heading as log entries. Line and column numbers are 1-based; a
+// task's position is that of its bullet marker, even when continuation lines
+// follow it. Lines inside ``` or ~~~ fenced code blocks are skipped, so code
+// never opens a section or yields a task or log entry.
+//
+// Task timestamps come from the note rather than the moment of parsing, so
+// they stay stable across reindexes:
+//
+//   - CreatedAt is an explicit "➕YYYY-MM-DD" token on the task line, else the
+//     note's date, else the file's modification time.
+//   - UpdatedAt is the file's modification time.
+//   - CompletedAt is an explicit "✅YYYY-MM-DD" token on the task line, else,
+//     for done tasks, the file's modification time.
+//
+// Indented lines following a task form its continuation. A lone short
+// continuation line (at most maxWrappedTitleLen characters) is taken as a
+// wrapped title and appended to it; any other continuation becomes the task's
+// Description, leaving the title as the bullet's own text. Tags and date
+// tokens are read from both.
+//
+// Date tokens are stripped from the task title and description.
+//
+// The current time is only used for notes that were not read from disk.
+//
+// Mention and link excerpts are centered on the reference, keeping up to the
+// excerpt radius in characters on either side and marking cut text with "…".
+//
+// With WithLogTasks, checkbox bullets in the Log section that carry a task
+// reference ("- [ ] follow up on X [T-5]") are also indexed as tasks. They
+// remain log entries too, so the entry mentions the task it defines.
+//
+// RegexMarkdownParser holds only its configuration and is safe for
+// concurrent use.
+type RegexMarkdownParser struct {
+	excerptRadius int
+	logTasks      bool
+	taskIDs       TaskIDPattern
+}
+
+// Option configures a RegexMarkdownParser.
+type Option func(*RegexMarkdownParser)
+
+// WithExcerptRadius sets how many characters of context are kept on each side
+// of a reference in mention and link excerpts. Negative values count as 0.
+func WithExcerptRadius(radius int) Option {
+	return func(p *RegexMarkdownParser) {
+		p.excerptRadius = max(radius, 0)
+	}
+}
+
+// WithLogTasks enables indexing checkbox bullets in the Log section as tasks.
+// It is off by default.
+func WithLogTasks(enabled bool) Option {
+	return func(p *RegexMarkdownParser) {
+		p.logTasks = enabled
+	}
+}
+
+// NewRegexMarkdownParser returns the v1 regex-based parser.
+func NewRegexMarkdownParser(opts ...Option) *RegexMarkdownParser {
+	p := &RegexMarkdownParser{excerptRadius: DefaultExcerptRadius, taskIDs: defaultTaskIDs}
+	for _, opt := range opts {
+		opt(p)
+	}
+	return p
+}
+
+// Parse implements NoteParser.
+func (p *RegexMarkdownParser) Parse(note model.Note) (model.ParsedNote, error) {
+	lines := splitLines(note.Content)
+
+	stamps := noteTimestamps(note)
+	parsed := model.ParsedNote{
+		Note:       note,
+		Tasks:      []model.Task{},
+		LogEntries: []model.LogEntry{},
+		Mentions:   []model.TaskMention{},
+		Links:      p.extractLinks(note, lines),
+		Untracked:  []model.UntrackedTask{},
+		Warnings:   []model.ParseWarning{},
+		Outline:    []model.Heading{},
+		WordCount:  countWords(lines),
+	}
+
+	current := sectionNone
+	var fence markdown.Fence
+	logIDs := map[string]int{}
+	taskLines := map[model.TaskID]int{}
+	for i := 0; i < len(lines); i++ {
+		line := lines[i]
+		lineNumber := i + 1
+		if fence.Skip(line) {
+			continue
+		}
+		if m := headingRE.FindStringSubmatch(line); m != nil {
+			parsed.Outline = append(parsed.Outline, model.Heading{Level: len(m[1]), Text: m[2], Line: lineNumber})
+			current = sectionFor(m[2])
+			continue
+		}
+
+		switch current {
+		case sectionTasks:
+			m := p.taskIDs.taskLine.FindStringSubmatch(line)
+			if m == nil {
+				if cb := checkboxRE.FindStringSubmatch(line); cb != nil {
+					parsed.Untracked = append(parsed.Untracked, model.UntrackedTask{
+						NoteID: note.ID,
+						Line:   lineNumber,
+						Text:   strings.TrimSpace(cb[2]),
+					})
+				}
+				if kind, message, ok := p.taskLineWarning(line); ok {
+					parsed.Warnings = append(parsed.Warnings, model.ParseWarning{
+						NoteID:  note.ID,
+						Line:    lineNumber,
+						Kind:    kind,
+						Message: message,
+					})
+				}
+				continue
+			}
+			continuation, _ := collectContinuation(lines[i+1:])
+			descriptionMD, consumed := collectDescriptionBlock(lines[i+1:], bulletColumn(line)-1)
+			i += consumed
+			task := buildTask(note, m, continuation, lineNumber, stamps)
+			task.DescriptionMD = descriptionMD
+			task.Column = bulletColumn(line)
+			if first, ok := taskLines[task.ID]; ok {
+				parsed.Warnings = append(parsed.Warnings, model.ParseWarning{
+					NoteID:  note.ID,
+					Line:    lineNumber,
+					Kind:    model.ParseWarningDuplicateTaskID,
+					Message: fmt.Sprintf("task ID %q is already defined on line %d", task.ID, first),
+				})
+			} else {
+				taskLines[task.ID] = lineNumber
+			}
+			parsed.Tasks = append(parsed.Tasks, task)
+		case sectionLog:
+			entry, mentions, ok := p.parseLogLine(note, line, lineNumber, logIDs)
+			if !ok {
+				continue
+			}
+			parsed.LogEntries = append(parsed.LogEntries, entry)
+			parsed.Mentions = append(parsed.Mentions, mentions...)
+			if p.logTasks {
+				if task, ok := p.parseLogTask(note, line, lineNumber, stamps); ok {
+					parsed.Tasks = append(parsed.Tasks, task)
+				}
+			}
+		}
+	}
+	return parsed, nil
+}
+
+// splitLines splits content into lines without their terminators, whether
+// the note was saved with LF or CRLF line endings, so no stray '\r' reaches
+// titles, tags, links or excerpts. Lines are numbered as for a plain split
+// on '\n', which is how writers locate them.
+func splitLines(content string) []string {
+	lines := strings.Split(content, "\n")
+	for i, line := range lines {
+		lines[i] = strings.TrimSuffix(line, "\r")
+	}
+	return lines
+}
+
+// MissingSections returns the section headings ("Tasks", "Log") that content
+// lacks. Tasks and log entries are only parsed under those headings, so a
+// note template without them yields notes the index cannot read.
+func MissingSections(content string) []string {
+	found := map[section]bool{}
+	var fence markdown.Fence
+	for _, line := range splitLines(content) {
+		if fence.Skip(line) {
+			continue
+		}
+		if m := headingRE.FindStringSubmatch(line); m != nil {
+			found[sectionFor(m[2])] = true
+		}
+	}
+	var missing []string
+	if !found[sectionTasks] {
+		missing = append(missing, "Tasks")
+	}
+	if !found[sectionLog] {
+		missing = append(missing, "Log")
+	}
+	return missing
+}
+
+func sectionFor(heading string) section {
+	switch {
+	case strings.EqualFold(heading, "tasks"):
+		return sectionTasks
+	case strings.EqualFold(heading, "log"):
+		return sectionLog
+	default:
+		return sectionNone
+	}
+}
+
+type timestamps struct {
+	created time.Time
+	updated time.Time
+}
+
+func noteTimestamps(note model.Note) timestamps {
+	updated := note.ModifiedAt
+	if updated.IsZero() {
+		updated = time.Now().UTC()
+	}
+	created := updated
+	if note.Date != nil {
+		created = *note.Date
+	}
+	return timestamps{created: created, updated: updated}
+}
+
+// collectContinuation gathers the indented, non-bullet lines that follow a
+// task line and returns them joined by newlines along with how many lines were
+// consumed.
+func collectContinuation(rest []string) (string, int) {
+	var parts []string
+	for _, next := range rest {
+		if !strings.HasPrefix(next, " ") && !strings.HasPrefix(next, "\t") {
+			break
+		}
+		trimmed := strings.TrimSpace(next)
+		if trimmed == "" || bulletRE.MatchString(next) {
+			break
+		}
+		parts = append(parts, trimmed)
+	}
+	return strings.Join(parts, "\n"), len(parts)
+}
+
+// collectDescriptionBlock gathers the Markdown block under a task line whose
+// bullet is indented by indent: the following lines indented further than
+// the bullet, blank lines between them included, up to the next bullet or
+// less indented line. Lines inside a fenced code block in the block are
+// taken whatever they contain. The block is returned verbatim apart from
+// its common indentation, along with how many lines it spans.
+func collectDescriptionBlock(rest []string, indent int) (string, int) {
+	var block []string
+	end := 0
+	var fence markdown.Fence
+	for i, next := range rest {
+		if strings.TrimSpace(next) == "" {
+			block = append(block, "")
+			continue
+		}
+		lineIndent := bulletColumn(next) - 1
+		if lineIndent <= indent {
+			break
+		}
+		if !fence.Skip(next) && bulletRE.MatchString(next) {
+			break
+		}
+		block = append(block, next)
+		end = i + 1
+	}
+	block = block[:end]
+
+	common := -1
+	for _, line := range block {
+		if line != "" && (common < 0 || bulletColumn(line)-1 < common) {
+			common = bulletColumn(line) - 1
+		}
+	}
+	for i, line := range block {
+		if line != "" {
+			block[i] = line[common:]
+		}
+	}
+	return strings.Join(block, "\n"), end
+}
+
+func buildTask(note model.Note, m []string, continuation string, line int, stamps timestamps) model.Task {
+	status, _ := StatusForGlyph(m[1])
+
+	text, description := splitContinuation(m[3], continuation)
+	text, created, completed := extractTaskDates(text)
+	text, blockedBy := extractBlockedBy(text)
+	title, tags := splitTitleAndTags(text)
+	if description != "" {
+		var descCreated, descCompleted *time.Time
+		var descBlockedBy []model.TaskID
+		description, descCreated, descCompleted = extractTaskDates(description)
+		description, descBlockedBy = extractBlockedBy(description)
+		created = cmp.Or(created, descCreated)
+		completed = cmp.Or(completed, descCompleted)
+		blockedBy = append(blockedBy, descBlockedBy...)
+		_, descTags := splitTitleAndTags(description)
+		tags = append(tags, descTags...)
+		description = strings.TrimSpace(description)
+	}
+
+	task := model.Task{
+		ID:          model.TaskID(m[2]),
+		NoteID:      note.ID,
+		Title:       title,
+		Description: description,
+		Status:      status,
+		Tags:        tags,
+		BlockedBy:   blockedBy,
+		CreatedAt:   stamps.created,
+		UpdatedAt:   stamps.updated,
+		Line:        line,
+	}
+	if created != nil {
+		task.CreatedAt = *created
+	}
+	switch {
+	case completed != nil:
+		task.CompletedAt = completed
+	case status == model.TaskStatusDone:
+		fallback := stamps.updated
+		task.CompletedAt = &fallback
+	}
+	return task
+}
+
+// splitContinuation decides where a task's continuation lines belong,
+// returning the title text and the description. A single continuation line
+// of at most maxWrappedTitleLen characters is joined to the title; anything
+// else becomes the description.
+func splitContinuation(first, continuation string) (string, string) {
+	switch {
+	case continuation == "":
+		return first, ""
+	case !strings.Contains(continuation, "\n") && utf8.RuneCountInString(continuation) <= maxWrappedTitleLen:
+		return first + " " + continuation, ""
+	default:
+		return first, continuation
+	}
+}
+
+// bulletColumn returns the 1-based column of the first non-blank character
+// of line. Tabs count as a single column.
+func bulletColumn(line string) int {
+	return len(line) - len(strings.TrimLeft(line, " \t")) + 1
+}
+
+// tagFromToken returns the tag spelled by a "#tag" token. Trailing slashes are
+// dropped, so "#projects/" is the tag "projects"; tokens that are empty after
+// that or contain an empty path segment ("#a//b", "#/a") are not tags.
+func tagFromToken(word string) (string, bool) {
+	tag, ok := strings.CutPrefix(word, "#")
+	if !ok {
+		return "", false
+	}
+	tag = strings.TrimRight(tag, "/")
+	if tag == "" || slices.Contains(strings.Split(tag, "/"), "") {
+		return "", false
+	}
+	return tag, true
+}
+
+// extractTaskDates strips "➕YYYY-MM-DD" (created) and "✅YYYY-MM-DD"
+// (completed) tokens from text and returns the dates they carry. Tokens with
+// an invalid date are left in place.
+func extractTaskDates(text string) (string, *time.Time, *time.Time) {
+	var created, completed *time.Time
+	text = taskDateRE.ReplaceAllStringFunc(text, func(token string) string {
+		m := taskDateRE.FindStringSubmatch(token)
+		date, err := time.Parse("2006-01-02", m[2])
+		if err != nil {
+			return token
+		}
+		if m[1] == "➕" {
+			created = &date
+		} else {
+			completed = &date
+		}
+		return ""
+	})
+	return text, created, completed
+}
+
+// extractBlockedBy strips "blocked-by:T-1,T-2" tokens from text and returns
+// the task IDs they list, in order and without duplicates.
+func extractBlockedBy(text string) (string, []model.TaskID) {
+	var ids []model.TaskID
+	text = blockedRE.ReplaceAllStringFunc(text, func(token string) string {
+		m := blockedRE.FindStringSubmatch(token)
+		for _, id := range strings.Split(m[2], ",") {
+			if id != "" && !slices.Contains(ids, model.TaskID(id)) {
+				ids = append(ids, model.TaskID(id))
+			}
+		}
+		return m[1]
+	})
+	return text, ids
+}
+
+// splitTitleAndTags removes "#tag" tokens from text, returning the remaining
+// title and the tags without their leading '#'. Line breaks are preserved.
+// Tokens that do not form a valid tag, such as a lone "#", stay in the title.
+func splitTitleAndTags(text string) (string, []string) {
+	tags := []string{}
+	var lines []string
+	for _, line := range strings.Split(text, "\n") {
+		var words []string
+		for _, word := range strings.Fields(line) {
+			if tag, ok := tagFromToken(word); ok {
+				tags = append(tags, tag)
+				continue
+			}
+			words = append(words, word)
+		}
+		lines = append(lines, strings.Join(words, " "))
+	}
+	return strings.TrimSpace(strings.Join(lines, "\n")), tags
+}
+
+func (p *RegexMarkdownParser) parseLogLine(note model.Note, line string, lineNumber int, logIDs map[string]int) (model.LogEntry, []model.TaskMention, bool) {
+	m := bulletRE.FindStringSubmatch(line)
+	if m == nil {
+		return model.LogEntry{}, nil, false
+	}
+	content := strings.TrimSpace(m[1])
+	if content == "" {
+		return model.LogEntry{}, nil, false
+	}
+
+	var timestamp time.Time
+	if note.Date != nil {
+		timestamp = *note.Date
+		if tm := logTimeRE.FindStringSubmatch(content); tm != nil {
+			if clock, err := time.Parse("15:04", tm[1]); err == nil {
+				timestamp = timestamp.Add(time.Duration(clock.Hour())*time.Hour + time.Duration(clock.Minute())*time.Minute)
+			}
+		}
+	}
+
+	_, tags := splitTitleAndTags(content)
+	entry := model.LogEntry{
+		ID:        logEntryID(note.ID, content, logIDs),
+		NoteID:    note.ID,
+		Line:      lineNumber,
+		Timestamp: timestamp,
+		Content:   content,
+		Tags:      tags,
+		TaskRefs:  []model.TaskID{},
+	}
+
+	// A task referenced more than once on the line is mentioned once, with
+	// the excerpt around its first reference.
+	var mentions []model.TaskMention
+	for _, ref := range p.taskIDs.ref.FindAllStringSubmatchIndex(content, -1) {
+		taskID := model.TaskID(content[ref[2]:ref[3]])
+		if slices.Contains(entry.TaskRefs, taskID) {
+			continue
+		}
+		entry.TaskRefs = append(entry.TaskRefs, taskID)
+		mentions = append(mentions, model.TaskMention{
+			TaskID:  taskID,
+			NoteID:  note.ID,
+			Line:    lineNumber,
+			Context: excerptAround(content, ref[0], ref[1], p.excerptRadius),
+			Tags:    tags,
+		})
+	}
+	return entry, mentions, true
+}
+
+// logEntryID derives a log entry's ID from its note and content rather than
+// its line, so it survives lines being inserted or removed around it. The
+// content is compared with whitespace collapsed; the nth repeat of the same
+// content within a note gets a "-n" suffix. seen counts the contents already
+// given IDs in the note.
+func logEntryID(note model.NoteID, content string, seen map[string]int) model.LogEntryID {
+	normalized := strings.Join(strings.Fields(content), " ")
+	sum := sha256.Sum256([]byte(normalized))
+	id := fmt.Sprintf("%s:%x", note, sum[:6])
+	if n := seen[normalized]; n > 0 {
+		id = fmt.Sprintf("%s-%d", id, n)
+	}
+	seen[normalized]++
+	return model.LogEntryID(id)
+}
+
+// parseLogTask reads a task from a checkbox bullet in the Log section. The
+// first task reference anywhere on the line is the task's ID; it and any
+// leading "HH:MM" time are dropped from the title.
+func (p *RegexMarkdownParser) parseLogTask(note model.Note, line string, lineNumber int, stamps timestamps) (model.Task, bool) {
+	cb := checkboxRE.FindStringSubmatch(line)
+	if cb == nil {
+		return model.Task{}, false
+	}
+	text := strings.TrimSpace(cb[2])
+	ref := p.taskIDs.ref.FindStringSubmatchIndex(text)
+	if ref == nil {
+		return model.Task{}, false
+	}
+	id := text[ref[2]:ref[3]]
+	text = strings.TrimSpace(text[:ref[0]] + text[ref[1]:])
+	if tm := logTimeRE.FindStringIndex(text); tm != nil {
+		text = text[tm[1]:]
+	}
+	task := buildTask(note, []string{line, cb[1], id, text}, "", lineNumber, stamps)
+	task.Column = bulletColumn(line)
+	return task, true
+}
+
+// extractLinks records every [[wikilink]] in the note, whatever its section,
+// outside fenced code blocks. Heading anchors and display aliases
+// ("[[Note#Heading|alias]]") are dropped from the target.
+func (p *RegexMarkdownParser) extractLinks(note model.Note, lines []string) []model.NoteLink {
+	links := []model.NoteLink{}
+	var fence markdown.Fence
+	for i, line := range lines {
+		if fence.Skip(line) {
+			continue
+		}
+		line = strings.TrimSpace(line)
+		for _, m := range wikilinkRE.FindAllStringSubmatchIndex(line, -1) {
+			target := strings.TrimSpace(line[m[2]:m[3]])
+			if target == "" {
+				continue
+			}
+			links = append(links, model.NoteLink{
+				NoteID:  note.ID,
+				Target:  target,
+				Line:    i + 1,
+				Context: excerptAround(line, m[0], m[1], p.excerptRadius),
+			})
+		}
+	}
+	return links
+}
+
+// excerptAround returns the part of text spanning the byte range [start, end)
+// plus up to radius characters on each side, with "…" marking any text cut
+// from either end.
+func excerptAround(text string, start, end, radius int) string {
+	runes := []rune(text)
+	from := utf8.RuneCountInString(text[:start]) - radius
+	to := utf8.RuneCountInString(text[:end]) + radius
+	prefix, suffix := "…", "…"
+	if from <= 0 {
+		from, prefix = 0, ""
+	}
+	if to >= len(runes) {
+		to, suffix = len(runes), ""
+	}
+	return prefix + string(runes[from:to]) + suffix
+}
@@ -0,0 +1,305 @@
+package parser
+
+import (
+	"slices"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// parse parses content as the note note.md, failing the test on error.
+func parse(t *testing.T, content string, opts ...Option) model.ParsedNote {
+	t.Helper()
+	return parseNote(t, model.Note{ID: "note.md", Path: "note.md", Title: "Note", Content: content}, opts...)
+}
+
+// parseNote parses note, failing the test on error.
+func parseNote(t *testing.T, note model.Note, opts ...Option) model.ParsedNote {
+	t.Helper()
+	parsed, err := NewRegexMarkdownParser(opts...).Parse(note)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return parsed
+}
+
+// day returns midnight UTC on the given date.
+func day(year int, month time.Month, d int) time.Time {
+	return time.Date(year, month, d, 0, 0, 0, 0, time.UTC)
+}
+
+// taskIDs lists the IDs of the tasks in parsed, in order.
+func taskIDs(parsed model.ParsedNote) []model.TaskID {
+	var ids []model.TaskID
+	for _, task := range parsed.Tasks {
+		ids = append(ids, task.ID)
+	}
+	return ids
+}
+
+const fencedNote = "# Note\n\n## Tasks\n\n- [ ] [T-1] Real task\n\n```markdown\n- [ ] [T-999] Fake task\n## Log\n- see [[Hidden]]\n```\n\n  ~~~\n  - [ ] [T-998] Indented fake\n  ~~~\n\n- [ ] [T-2] Another real task, see [[Shown]]\n"
+
+func TestFencedCodeIsNotParsed(t *testing.T) {
+	parsed := parse(t, fencedNote)
+	if got := taskIDs(parsed); len(got) != 2 || got[0] != "T-1" || got[1] != "T-2" {
+		t.Errorf("tasks = %v, want [T-1 T-2]", got)
+	}
+	if len(parsed.LogEntries) != 0 {
+		t.Errorf("log entries = %+v, want none from the fenced heading", parsed.LogEntries)
+	}
+	if len(parsed.Untracked) != 0 {
+		t.Errorf("untracked = %+v, want none", parsed.Untracked)
+	}
+}
+
+func TestFencedCodeLinksAreNotRecorded(t *testing.T) {
+	links := parse(t, fencedNote).Links
+	if len(links) != 1 || links[0].Target != "Shown" {
+		t.Errorf("links = %+v, want only [[Shown]]", links)
+	}
+}
+
+func TestTaskTimestampsFollowNoteDateAndModTime(t *testing.T) {
+	date := day(2025, time.March, 3)
+	modified := time.Date(2025, time.March, 5, 17, 0, 0, 0, time.UTC)
+	content := "# Note\n\n## Tasks\n\n- [ ] [T-1] Open\n- [x] [T-2] Finished\n"
+
+	dated := parseNote(t, model.Note{ID: "2025-03-03.md", Date: &date, ModifiedAt: modified, Content: content})
+	if len(dated.Tasks) != 2 {
+		t.Fatalf("tasks = %v, want [T-1 T-2]", taskIDs(dated))
+	}
+	open, done := dated.Tasks[0], dated.Tasks[1]
+	if !open.CreatedAt.Equal(date) || !open.UpdatedAt.Equal(modified) || open.CompletedAt != nil {
+		t.Errorf("open task times = %v, %v, %v, want created on the note date and updated at mtime", open.CreatedAt, open.UpdatedAt, open.CompletedAt)
+	}
+	if done.CompletedAt == nil || !done.CompletedAt.Equal(modified) {
+		t.Errorf("done task completed at %v, want the mtime %v", done.CompletedAt, modified)
+	}
+
+	undated := parseNote(t, model.Note{ID: "ideas.md", ModifiedAt: modified, Content: content})
+	if created := undated.Tasks[0].CreatedAt; !created.Equal(modified) {
+		t.Errorf("undated note task created at %v, want the mtime %v", created, modified)
+	}
+}
+
+func TestTaskDateTokens(t *testing.T) {
+	modified := time.Date(2025, time.March, 5, 17, 0, 0, 0, time.UTC)
+	parsed := parseNote(t, model.Note{ID: "note.md", ModifiedAt: modified, Content: "## Tasks\n\n- [x] [T-1] Ship it ➕2025-01-02 ✅ 2025-02-03 #release\n- [x] [T-2] No tokens\n"})
+	if len(parsed.Tasks) != 2 {
+		t.Fatalf("tasks = %v, want [T-1 T-2]", taskIDs(parsed))
+	}
+
+	both := parsed.Tasks[0]
+	if both.Title != "Ship it" {
+		t.Errorf("title = %q, want the tokens stripped", both.Title)
+	}
+	if !both.CreatedAt.Equal(day(2025, time.January, 2)) {
+		t.Errorf("created at %v, want 2025-01-02", both.CreatedAt)
+	}
+	if both.CompletedAt == nil || !both.CompletedAt.Equal(day(2025, time.February, 3)) {
+		t.Errorf("completed at %v, want 2025-02-03", both.CompletedAt)
+	}
+
+	neither := parsed.Tasks[1]
+	if !neither.CreatedAt.Equal(modified) || neither.CompletedAt == nil || !neither.CompletedAt.Equal(modified) {
+		t.Errorf("created %v, completed %v, want both at the mtime", neither.CreatedAt, neither.CompletedAt)
+	}
+}
+
+func TestContinuationLinesSplitBetweenTitleAndDescription(t *testing.T) {
+	parsed := parse(t, "## Tasks\n\n"+
+		"- [ ] [T-1] Write the\n  quarterly report\n"+
+		"- [ ] [T-2] Plan offsite\n  Book the venue near the office for forty people\n"+
+		"- [ ] [T-3] Migrate\n  copy the data\n  then switch traffic\n")
+	if len(parsed.Tasks) != 3 {
+		t.Fatalf("tasks = %v, want [T-1 T-2 T-3]", taskIDs(parsed))
+	}
+	want := []struct{ title, description string }{
+		{"Write the quarterly report", ""},
+		{"Plan offsite", "Book the venue near the office for forty people"},
+		{"Migrate", "copy the data\nthen switch traffic"},
+	}
+	for i, task := range parsed.Tasks {
+		if task.Title != want[i].title || task.Description != want[i].description {
+			t.Errorf("%s: title %q, description %q, want %q, %q", task.ID, task.Title, task.Description, want[i].title, want[i].description)
+		}
+	}
+}
+
+func TestMentionExcerptsCenterOnTheReference(t *testing.T) {
+	parsed := parse(t, "## Log\n\n- [T-1] aaaaaaaaaa bbbbbbbbbb [T-2] cccccccccc dddddddddd [T-3]\n", WithExcerptRadius(5))
+	want := map[model.TaskID]string{
+		"T-1": "[T-1] aaaa…",
+		"T-2": "…bbbb [T-2] cccc…",
+		"T-3": "…dddd [T-3]",
+	}
+	if len(parsed.Mentions) != len(want) {
+		t.Fatalf("mentions = %+v, want three", parsed.Mentions)
+	}
+	for _, mention := range parsed.Mentions {
+		if mention.Context != want[mention.TaskID] {
+			t.Errorf("%s context = %q, want %q", mention.TaskID, mention.Context, want[mention.TaskID])
+		}
+	}
+}
+
+func TestMalformedTagTokens(t *testing.T) {
+	for _, tc := range []struct {
+		line, title string
+		tags        []string
+	}{
+		{"Plan #projects/", "Plan", []string{"projects"}},
+		{"Plan #projects//", "Plan", []string{"projects"}},
+		{"Read # note", "Read # note", nil},
+		{"Split #a//b", "Split #a//b", nil},
+		{"Lead #/a", "Lead #/a", nil},
+		{"Slashes #/ only", "Slashes #/ only", nil},
+		{"Nested #projects/app/ #home", "Nested", []string{"projects/app", "home"}},
+	} {
+		parsed := parse(t, "## Tasks\n\n- [ ] [T-1] "+tc.line+"\n")
+		if len(parsed.Tasks) != 1 {
+			t.Fatalf("%q: tasks = %v, want [T-1]", tc.line, taskIDs(parsed))
+		}
+		task := parsed.Tasks[0]
+		if task.Title != tc.title || !slices.Equal(task.Tags, tc.tags) {
+			t.Errorf("%q: title %q, tags %q, want %q, %q", tc.line, task.Title, task.Tags, tc.title, tc.tags)
+		}
+	}
+}
+
+func TestMissingSections(t *testing.T) {
+	if got := MissingSections("# {date}\n\n## Notes\n\n## Tasks\n\n## Log\n"); len(got) != 0 {
+		t.Errorf("complete template missing %v", got)
+	}
+	if got := MissingSections("# {date}\n\n## Tasks\n\n```\n## Log\n```\n"); !slices.Equal(got, []string{"Log"}) {
+		t.Errorf("missing = %v, want [Log]", got)
+	}
+}
+
+func TestCheckboxLogLinesWithLogTasks(t *testing.T) {
+	content := "## Log\n\n- [ ] follow up on X [T-5] #ops\n- [x] shipped [T-6]\n- plain note\n"
+
+	if parsed := parse(t, content); len(parsed.Tasks) != 0 || len(parsed.LogEntries) != 3 {
+		t.Errorf("by default: tasks %v, %d log entries, want no tasks and 3 entries", taskIDs(parsed), len(parsed.LogEntries))
+	}
+
+	parsed := parse(t, content, WithLogTasks(true))
+	if len(parsed.LogEntries) != 3 {
+		t.Errorf("log entries = %d, want the checkbox lines kept as entries", len(parsed.LogEntries))
+	}
+	if got := taskIDs(parsed); !slices.Equal(got, []model.TaskID{"T-5", "T-6"}) {
+		t.Fatalf("tasks = %v, want [T-5 T-6]", got)
+	}
+	followUp, shipped := parsed.Tasks[0], parsed.Tasks[1]
+	if followUp.Title != "follow up on X" || followUp.Status != model.TaskStatusTodo || !slices.Equal(followUp.Tags, []string{"ops"}) {
+		t.Errorf("T-5 = %q, %s, %v, want an open task tagged ops", followUp.Title, followUp.Status, followUp.Tags)
+	}
+	if shipped.Status != model.TaskStatusDone || shipped.Line != 4 {
+		t.Errorf("T-6 = %s on line %d, want done on line 4", shipped.Status, shipped.Line)
+	}
+	if !slices.ContainsFunc(parsed.Mentions, func(m model.TaskMention) bool { return m.TaskID == "T-5" && m.Line == 3 }) {
+		t.Errorf("mentions = %+v, want the log entry to mention T-5", parsed.Mentions)
+	}
+}
+
+func TestRepeatedReferenceYieldsOneMention(t *testing.T) {
+	parsed := parse(t, "## Log\n\n- fixed [T-1] and re-fixed [T-1], then [T-2]\n")
+	if len(parsed.LogEntries) != 1 {
+		t.Fatalf("log entries = %+v, want one", parsed.LogEntries)
+	}
+	if refs := parsed.LogEntries[0].TaskRefs; !slices.Equal(refs, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("task refs = %v, want [T-1 T-2]", refs)
+	}
+	var mentioned []model.TaskID
+	for _, mention := range parsed.Mentions {
+		mentioned = append(mentioned, mention.TaskID)
+	}
+	if !slices.Equal(mentioned, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("mentions = %v, want one each of T-1 and T-2", mentioned)
+	}
+}
+
+func TestCRLFContent(t *testing.T) {
+	parsed := parse(t, "# Note\r\n\r\n## Tasks\r\n\r\n- [ ] [T-1] Write the\r\n  report #work\r\n- [ ] [T-2] Review\r\n  Check the figures against last quarter's\r\n\r\n## Log\r\n\r\n- drafted [T-1] #work\r\n")
+	if got := taskIDs(parsed); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Fatalf("tasks = %v, want [T-1 T-2]", got)
+	}
+	for _, task := range parsed.Tasks {
+		if strings.Contains(task.Title+task.Description+strings.Join(task.Tags, ""), "\r") {
+			t.Errorf("%s: title %q, description %q, tags %q contain \\r", task.ID, task.Title, task.Description, task.Tags)
+		}
+	}
+	if title := parsed.Tasks[0].Title; title != "Write the report" {
+		t.Errorf("T-1 title = %q, want the continuation joined", title)
+	}
+	if len(parsed.LogEntries) != 1 {
+		t.Fatalf("log entries = %+v, want one", parsed.LogEntries)
+	}
+	if entry := parsed.LogEntries[0]; entry.Content != "drafted [T-1] #work" || !slices.Equal(entry.Tags, []string{"work"}) {
+		t.Errorf("log entry = %q tagged %q", entry.Content, entry.Tags)
+	}
+}
+
+func TestLogEntryIDsSurviveLineShifts(t *testing.T) {
+	before := parse(t, "## Log\n\n- standup\n- shipped [T-1]\n- standup\n").LogEntries
+	after := parse(t, "## Log\n\n\n- standup\n\n- shipped  [T-1]\n- standup\n").LogEntries
+	if len(before) != 3 || len(after) != 3 {
+		t.Fatalf("entries before %+v, after %+v, want three each", before, after)
+	}
+	for i := range before {
+		if before[i].ID != after[i].ID {
+			t.Errorf("entry %d ID changed from %s to %s", i, before[i].ID, after[i].ID)
+		}
+		if before[i].Line == after[i].Line {
+			t.Errorf("entry %d stayed on line %d, want it moved", i, before[i].Line)
+		}
+	}
+	if before[0].ID == before[2].ID {
+		t.Errorf("repeated entries share the ID %s", before[0].ID)
+	}
+}
+
+const describedTask = "## Tasks\n\n" +
+	"- [ ] [T-1] Plan offsite\n" +
+	"  Book the venue.\n\n" +
+	"  **Budget**: tight.\n\n" +
+	"  ```\n  - [ ] [T-9] not a task\n  ```\n" +
+	"- [ ] [T-2] Next\n"
+
+func TestDescriptionBlockIsKeptVerbatim(t *testing.T) {
+	parsed := parse(t, describedTask)
+	if got := taskIDs(parsed); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Fatalf("tasks = %v, want [T-1 T-2]", got)
+	}
+	want := "Book the venue.\n\n**Budget**: tight.\n\n```\n- [ ] [T-9] not a task\n```"
+	if got := parsed.Tasks[0].DescriptionMD; got != want {
+		t.Errorf("description_md = %q, want %q", got, want)
+	}
+	if got := parsed.Tasks[1].DescriptionMD; got != "" {
+		t.Errorf("T-2 description_md = %q, want none", got)
+	}
+}
+
+func TestParseBlockedByToken(t *testing.T) {
+	parsed := parse(t, "## Tasks\n\n"+
+		"- [!] [T-3] Deploy blocked-by:T-1,T-2,T-1 #ops\n"+
+		"- [ ] [T-4] Announce\n"+
+		"  Waiting on the deploy and the review blocked-by:T-3 blocked-by:T-5\n"+
+		"- [ ] [T-5] Unblocked-by:T-9 is not a token\n")
+
+	tasks := map[model.TaskID]model.Task{}
+	for _, task := range parsed.Tasks {
+		tasks[task.ID] = task
+	}
+	if got := tasks["T-3"]; got.Title != "Deploy" || !slices.Equal(got.BlockedBy, []model.TaskID{"T-1", "T-2"}) || !slices.Equal(got.Tags, []string{"ops"}) {
+		t.Errorf("T-3 = %q blocked by %v tagged %v, want Deploy blocked by [T-1 T-2] tagged [ops]", got.Title, got.BlockedBy, got.Tags)
+	}
+	if got := tasks["T-4"]; strings.Contains(got.Description, "blocked-by") || !slices.Equal(got.BlockedBy, []model.TaskID{"T-3", "T-5"}) {
+		t.Errorf("T-4 description %q blocked by %v, want the tokens stripped and [T-3 T-5]", got.Description, got.BlockedBy)
+	}
+	if got := tasks["T-5"]; len(got.BlockedBy) != 0 {
+		t.Errorf("T-5 blocked by %v, want none", got.BlockedBy)
+	}
+}
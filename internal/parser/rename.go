@@ -0,0 +1,85 @@
+package parser
+
+import (
+	"regexp"
+	"strings"
+	"unicode"
+
+	"github.com/weakphish/yapper/internal/markdown"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+var tokenRE = regexp.MustCompile(`\S+`)
+
+// RenameTag rewrites every "#from" tag token in content to "#to", returning
+// the new content and how many tokens changed. Tags are the same when key
+// maps them to the same string, and only whole tokens match, so renaming
+// "old" leaves "#oldish" alone. With children set, nested tags such as
+// "#from/sub" become "#to/sub" as well. Fenced code blocks are left
+// untouched.
+func RenameTag(content, from, to string, children bool, key func(string) string) (string, int) {
+	fromKey := key(from)
+	lines := strings.Split(content, "\n")
+	renamed := 0
+	var fence markdown.Fence
+	for i, line := range lines {
+		if fence.Skip(strings.TrimSuffix(line, "\r")) {
+			continue
+		}
+		lines[i] = tokenRE.ReplaceAllStringFunc(line, func(token string) string {
+			tag, ok := tagFromToken(token)
+			if !ok {
+				return token
+			}
+			// Whatever follows the old name, a nested path or trailing
+			// slashes, is kept.
+			if key(tag) == fromKey {
+				renamed++
+				return "#" + to + token[1+len(tag):]
+			}
+			if !children {
+				return token
+			}
+			for i := range len(tag) {
+				if tag[i] == '/' && key(tag[:i]) == fromKey {
+					renamed++
+					return "#" + to + token[1+i:]
+				}
+			}
+			return token
+		})
+	}
+	return strings.Join(lines, "\n"), renamed
+}
+
+// ValidTag reports whether name, given without its leading "#", would be
+// read back as exactly that tag.
+func ValidTag(name string) bool {
+	if strings.ContainsFunc(name, unicode.IsSpace) {
+		return false
+	}
+	tag, ok := tagFromToken("#" + name)
+	return ok && tag == name
+}
+
+// RenameTaskIDs rewrites every "[id]" reference in content, on task lines
+// and in log entries alike, to the ID it maps to in ids. All references are
+// replaced in one pass, so mapping T-1 to T-2 and T-2 to T-3 does not chain.
+// Fenced code blocks are left untouched.
+func RenameTaskIDs(content string, ids map[model.TaskID]model.TaskID) string {
+	pairs := make([]string, 0, 2*len(ids))
+	for from, to := range ids {
+		pairs = append(pairs, "["+string(from)+"]", "["+string(to)+"]")
+	}
+	replacer := strings.NewReplacer(pairs...)
+
+	lines := strings.Split(content, "\n")
+	var fence markdown.Fence
+	for i, line := range lines {
+		if fence.Skip(strings.TrimSuffix(line, "\r")) {
+			continue
+		}
+		lines[i] = replacer.Replace(line)
+	}
+	return strings.Join(lines, "\n")
+}
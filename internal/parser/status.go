@@ -0,0 +1,58 @@
+package parser
+
+import (
+	"strings"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// glyphClass matches the character inside a task checkbox. It must accept
+// every glyph in statusGlyphs.
+const glyphClass = `[ xX/!]`
+
+// statusGlyphs is the canonical checkbox glyph for each task status. Plain
+// Markdown only defines "[ ]" and "[x]", so in-progress tasks are written
+// "[/]" and blocked tasks "[!]". Parsing additionally accepts "[X]" as done.
+// Both parsing and write-back go through this table, so a status written to
+// a note reads back unchanged.
+var statusGlyphs = map[model.TaskStatus]string{
+	model.TaskStatusTodo:       " ",
+	model.TaskStatusInProgress: "/",
+	model.TaskStatusBlocked:    "!",
+	model.TaskStatusDone:       "x",
+}
+
+// StatusGlyph returns the checkbox glyph written for status.
+func StatusGlyph(status model.TaskStatus) (string, bool) {
+	glyph, ok := statusGlyphs[status]
+	return glyph, ok
+}
+
+// StatusForGlyph returns the status a checkbox glyph stands for.
+func StatusForGlyph(glyph string) (model.TaskStatus, bool) {
+	if glyph == "X" {
+		return model.TaskStatusDone, true
+	}
+	for status, g := range statusGlyphs {
+		if g == glyph {
+			return status, true
+		}
+	}
+	return "", false
+}
+
+// SetTaskLineStatus rewrites the checkbox of the task line for id to show
+// status. Both task-section lines ("- [ ] [T-1] ...") and log task lines
+// ("- [ ] ... [T-1]") are recognized by the literal "[id]" reference, so any
+// task ID pattern works. It reports false when line is not that task's line.
+func SetTaskLineStatus(line string, id model.TaskID, status model.TaskStatus) (string, bool) {
+	glyph, ok := StatusGlyph(status)
+	if !ok {
+		return line, false
+	}
+	m := checkboxRE.FindStringSubmatchIndex(line)
+	if m == nil || !strings.Contains(line[m[4]:], "["+string(id)+"]") {
+		return line, false
+	}
+	return line[:m[2]] + glyph + line[m[3]:], true
+}
@@ -0,0 +1,80 @@
+package parser
+
+import (
+	"fmt"
+	"regexp"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// DefaultTaskIDPattern is the task ID shape recognized unless
+// WithTaskIDPattern says otherwise, e.g. "T-42".
+const DefaultTaskIDPattern = `T-[0-9A-Za-z_-]+`
+
+// TaskIDPattern is a compiled task ID shape, used to find task IDs on task
+// lines and task references in log entries.
+type TaskIDPattern struct {
+	fragment string
+	taskLine *regexp.Regexp
+	ref      *regexp.Regexp
+}
+
+var defaultTaskIDs = MustCompileTaskIDPattern(DefaultTaskIDPattern)
+
+// CompileTaskIDPattern compiles a regular expression fragment matching a
+// whole task ID, such as `[A-Z]+-[0-9]+` for "PROJ-123". The fragment is
+// matched between the brackets of a "[ID]" reference and must not contain
+// capturing groups; use (?:...) instead.
+func CompileTaskIDPattern(fragment string) (TaskIDPattern, error) {
+	ref, err := regexp.Compile(`\[(` + fragment + `)\]`)
+	if err != nil {
+		return TaskIDPattern{}, fmt.Errorf("invalid task ID pattern %q: %w", fragment, err)
+	}
+	if ref.NumSubexp() != 1 {
+		return TaskIDPattern{}, fmt.Errorf("invalid task ID pattern %q: capturing groups are not allowed, use (?:...)", fragment)
+	}
+	if ref.MatchString("[]") {
+		return TaskIDPattern{}, fmt.Errorf("invalid task ID pattern %q: it matches an empty ID", fragment)
+	}
+	return TaskIDPattern{
+		fragment: fragment,
+		taskLine: regexp.MustCompile(`^\s*[-*+]\s+\[(` + glyphClass + `)\]\s+\[(` + fragment + `)\]\s*(.*)$`),
+		ref:      ref,
+	}, nil
+}
+
+// MustCompileTaskIDPattern is CompileTaskIDPattern panicking on error.
+func MustCompileTaskIDPattern(fragment string) TaskIDPattern {
+	p, err := CompileTaskIDPattern(fragment)
+	if err != nil {
+		panic(err)
+	}
+	return p
+}
+
+// String returns the fragment the pattern was compiled from.
+func (p TaskIDPattern) String() string {
+	return p.fragment
+}
+
+// Matches reports whether id is a whole task ID of this shape.
+func (p TaskIDPattern) Matches(id model.TaskID) bool {
+	ref := "[" + string(id) + "]"
+	loc := p.ref.FindStringIndex(ref)
+	return loc != nil && loc[0] == 0 && loc[1] == len(ref)
+}
+
+// TaskIDPattern returns the task ID shape the parser recognizes.
+func (p *RegexMarkdownParser) TaskIDPattern() TaskIDPattern {
+	return p.taskIDs
+}
+
+// WithTaskIDPattern sets the task ID shape, replacing DefaultTaskIDPattern.
+// A zero TaskIDPattern keeps the default.
+func WithTaskIDPattern(pattern TaskIDPattern) Option {
+	return func(p *RegexMarkdownParser) {
+		if pattern.ref != nil {
+			p.taskIDs = pattern
+		}
+	}
+}
@@ -0,0 +1,34 @@
+package parser
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestCustomTaskIDPattern(t *testing.T) {
+	jira, err := CompileTaskIDPattern(`[A-Z]+-[0-9]+`)
+	if err != nil {
+		t.Fatal(err)
+	}
+	parsed := parse(t, "## Tasks\n\n- [ ] [PROJ-123] Fix login\n- [x] [OPS-7] Rotate keys\n- [ ] [T-1] Old style\n\n## Log\n\n- paired on [PROJ-123] and [T-1]\n", WithTaskIDPattern(jira))
+
+	if got := taskIDs(parsed); !slices.Equal(got, []model.TaskID{"PROJ-123", "OPS-7"}) {
+		t.Errorf("tasks = %v, want [PROJ-123 OPS-7]", got)
+	}
+	if len(parsed.LogEntries) != 1 || !slices.Equal(parsed.LogEntries[0].TaskRefs, []model.TaskID{"PROJ-123"}) {
+		t.Errorf("log entries = %+v, want one referencing PROJ-123", parsed.LogEntries)
+	}
+	if got := taskIDs(parse(t, "## Tasks\n\n- [ ] [PROJ-123] Fix login\n")); len(got) != 0 {
+		t.Errorf("default pattern found %v", got)
+	}
+}
+
+func TestCompileTaskIDPatternRejectsBadFragments(t *testing.T) {
+	for _, fragment := range []string{`[A-Z`, `([A-Z]+)-[0-9]+`, `[0-9]*`} {
+		if _, err := CompileTaskIDPattern(fragment); err == nil {
+			t.Errorf("CompileTaskIDPattern(%q) succeeded", fragment)
+		}
+	}
+}
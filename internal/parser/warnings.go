@@ -0,0 +1,32 @@
+package parser
+
+import (
+	"fmt"
+	"regexp"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+var (
+	anyCheckboxRE = regexp.MustCompile(`^\s*[-*+]\s+\[([^\]])\]`)
+	bracketIDRE   = regexp.MustCompile(`^\[([^\]]*)\]`)
+)
+
+// taskLineWarning explains why a line in a Tasks section that is not a task
+// line was skipped, if it looks like an attempted task: a checkbox without a
+// task ID, with an ID the task ID pattern rejects, or with an unknown glyph.
+func (p *RegexMarkdownParser) taskLineWarning(line string) (model.ParseWarningKind, string, bool) {
+	if cb := checkboxRE.FindStringSubmatch(line); cb != nil {
+		if m := bracketIDRE.FindStringSubmatch(cb[2]); m != nil {
+			if ref := p.taskIDs.ref.FindStringSubmatch(m[0]); ref != nil && ref[1] == m[1] {
+				return model.ParseWarningInvalidTaskID, fmt.Sprintf("task ID %q must be separated from the checkbox by a space", m[1]), true
+			}
+			return model.ParseWarningInvalidTaskID, fmt.Sprintf("task ID %q does not match the task ID pattern %s", m[1], p.taskIDs), true
+		}
+		return model.ParseWarningMissingTaskID, "checkbox without task ID", true
+	}
+	if m := anyCheckboxRE.FindStringSubmatch(line); m != nil {
+		return model.ParseWarningUnknownCheckbox, fmt.Sprintf("unknown checkbox [%s]; use [ ], [/], [!] or [x]", m[1]), true
+	}
+	return "", "", false
+}
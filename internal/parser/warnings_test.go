@@ -0,0 +1,41 @@
+package parser
+
+import (
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestMalformedTaskLinesWarn(t *testing.T) {
+	parsed := parse(t, "## Tasks\n\n"+
+		"- [ ] Buy milk\n"+
+		"- [ ] [t 1] Lowercase ID\n"+
+		"- [ ][T-2] Squashed\n"+
+		"- [?] [T-3] Odd glyph\n"+
+		"- [ ] [T-1] Fine\n"+
+		"- [ ] [T-1] Again\n"+
+		"- a plain bullet\n"+
+		"\n## Log\n\n- [ ] jotted without an ID\n")
+
+	want := []struct {
+		line int
+		kind model.ParseWarningKind
+	}{
+		{3, model.ParseWarningMissingTaskID},
+		{4, model.ParseWarningInvalidTaskID},
+		{5, model.ParseWarningInvalidTaskID},
+		{6, model.ParseWarningUnknownCheckbox},
+		{8, model.ParseWarningDuplicateTaskID},
+	}
+	if len(parsed.Warnings) != len(want) {
+		t.Fatalf("warnings = %+v, want %d", parsed.Warnings, len(want))
+	}
+	for i, w := range parsed.Warnings {
+		if w.Line != want[i].line || w.Kind != want[i].kind || w.NoteID != "note.md" || w.Message == "" {
+			t.Errorf("warning %d = %+v, want %s on line %d", i, w, want[i].kind, want[i].line)
+		}
+	}
+	if parsed.Warnings[0].Message != "checkbox without task ID" {
+		t.Errorf("missing ID message = %q", parsed.Warnings[0].Message)
+	}
+}
@@ -0,0 +1,31 @@
+package parser
+
+import (
+	"strings"
+	"unicode"
+
+	"github.com/weakphish/yapper/internal/markdown"
+)
+
+// countWords counts the words of a note's prose: whitespace-separated
+// tokens containing a letter or digit, so list markers and rules are not
+// words. Leading YAML frontmatter and fenced code blocks are not counted.
+func countWords(lines []string) int {
+	words := 0
+	var fence markdown.Fence
+	for _, line := range lines[markdown.FrontmatterEnd(lines):] {
+		if fence.Skip(line) {
+			continue
+		}
+		for _, field := range strings.Fields(line) {
+			if strings.ContainsFunc(field, isWordRune) {
+				words++
+			}
+		}
+	}
+	return words
+}
+
+func isWordRune(r rune) bool {
+	return unicode.IsLetter(r) || unicode.IsNumber(r)
+}
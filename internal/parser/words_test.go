@@ -0,0 +1,14 @@
+package parser
+
+import "testing"
+
+func TestWordCountIgnoresFrontmatterAndCode(t *testing.T) {
+	parsed := parse(t, "---\ntitle: Many words in the frontmatter\n---\n"+
+		"# Hello world\n\nOne two three.\n\n"+
+		"```go\nfunc main() { fmt.Println(\"ignored words\") }\n```\n\n"+
+		"  ~~~\n  indented fence words\n  ~~~\n\n"+
+		"- four five\n---\n")
+	if parsed.WordCount != 7 {
+		t.Errorf("word count = %d, want 7", parsed.WordCount)
+	}
+}
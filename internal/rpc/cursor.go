@@ -0,0 +1,45 @@
+package rpc
+
+import (
+	"encoding/base64"
+	"encoding/json"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// maxPageSize bounds the page size accepted by paginated methods.
+const maxPageSize = 1000
+
+// TaskPage is one page of core.list_tasks results. NextCursor is empty on the
+// last page.
+type TaskPage struct {
+	Tasks      []model.Task `json:"tasks"`
+	NextCursor string       `json:"next_cursor,omitempty"`
+}
+
+// taskCursor is the decoded form of a pagination cursor. It carries the
+// original filters so that core.list_tasks_next only needs the token, and the
+// last task ID returned so that pages resume after it under the ID ordering
+// even if tasks are added or removed in between.
+type taskCursor struct {
+	Params ListTasksParams `json:"params"`
+	After  model.TaskID    `json:"after"`
+}
+
+func encodeTaskCursor(cursor taskCursor) string {
+	encoded, err := json.Marshal(cursor)
+	if err != nil {
+		// taskCursor only holds plain strings and ints.
+		panic(err)
+	}
+	return base64.RawURLEncoding.EncodeToString(encoded)
+}
+
+func decodeTaskCursor(token string) (taskCursor, *Error) {
+	var cursor taskCursor
+	raw, err := base64.RawURLEncoding.DecodeString(token)
+	if err != nil || json.Unmarshal(raw, &cursor) != nil || cursor.Params.PageSize <= 0 {
+		return taskCursor{}, InvalidParams("invalid cursor")
+	}
+	return cursor, nil
+}
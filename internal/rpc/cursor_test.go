@@ -0,0 +1,51 @@
+package rpc
+
+import (
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestListTasksPagesYieldEveryTaskOnce(t *testing.T) {
+	var note strings.Builder
+	note.WriteString("# Backlog\n\n## Tasks\n\n")
+	for i := 1; i <= 7; i++ {
+		fmt.Fprintf(&note, "- [ ] [T-%d] Task %d\n", i, i)
+	}
+	s := newTestServer(t, writeVault(t, map[string]string{"backlog.md": note.String()}))
+
+	seen := map[model.TaskID]int{}
+	page := decodeResult[TaskPage](t, call(t, s, "core.list_tasks", map[string]any{"page_size": 3}))
+	pages := 1
+	for {
+		if len(page.Tasks) > 3 {
+			t.Fatalf("page %d has %d tasks, want at most 3", pages, len(page.Tasks))
+		}
+		for _, task := range page.Tasks {
+			seen[task.ID]++
+		}
+		if page.NextCursor == "" {
+			break
+		}
+		page = decodeResult[TaskPage](t, call(t, s, "core.list_tasks_next", map[string]any{"cursor": page.NextCursor}))
+		pages++
+	}
+	if pages != 3 {
+		t.Errorf("got %d pages, want 3", pages)
+	}
+	for i := 1; i <= 7; i++ {
+		if id := model.TaskID(fmt.Sprintf("T-%d", i)); seen[id] != 1 {
+			t.Errorf("%s returned %d times, want once", id, seen[id])
+		}
+	}
+	if len(seen) != 7 {
+		t.Errorf("saw %d distinct tasks, want 7", len(seen))
+	}
+}
+
+func TestListTasksNextRejectsBadCursor(t *testing.T) {
+	s := newTestServer(t, writeVault(t, nil))
+	wantCode(t, call(t, s, "core.list_tasks_next", map[string]any{"cursor": "not-a-cursor"}), CodeInvalidParams)
+}
@@ -0,0 +1,225 @@
+package rpc
+
+import (
+	"encoding/json"
+	"maps"
+	"reflect"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// methodSpec describes one method for core.describe_method. Params and result
+// hold zero values of the types exchanged; params is nil for methods taking
+// none besides VaultParams, and a oneOf result lists alternative shapes.
+type methodSpec struct {
+	name    string
+	summary string
+	params  any
+	result  any
+}
+
+// oneOf marks a result that takes one of several shapes.
+type oneOf []any
+
+// methodSpecs describes every method dispatch handles, in the order of its
+// cases. core.capabilities and core.describe_method are derived from it, so
+// it must be kept in step with dispatch.
+var methodSpecs = []methodSpec{
+	{"core.capabilities", "Daemon version and supported methods.", nil, Capabilities{}},
+	{"core.describe_method", "Params and result shapes of one method, or of all when method is empty.", DescribeMethodParams{}, oneOf{MethodDescription{}, []MethodDescription{}}},
+	{"core.list_vaults", "The vaults served by the daemon.", nil, []VaultInfo{}},
+	{"core.ping", "Liveness check with index counts.", nil, PingResult{}},
+	{"core.reindex", "Rebuild the index from the vault, sending core.reindex_progress notifications; notes that fail to read are skipped and reported.", nil, ReindexResult{}},
+	{"core.open_daily", "Open, creating if needed, the daily note for a date.", DateParams{}, model.Note{}},
+	{"core.open_weekly", "Open, creating if needed, the weekly note for the ISO week containing a date.", DateParams{}, model.Note{}},
+	{"core.list_tasks", "Tasks matching filters; a TaskPage when page_size is set.", ListTasksParams{}, oneOf{[]model.Task{}, TaskPage{}}},
+	{"core.list_tasks_next", "The page of tasks following a cursor.", CursorParams{}, TaskPage{}},
+	{"core.task_detail", "A task with its mentions and log entries.", TaskParams{}, model.TaskDetail{}},
+	{"core.task_timeline", "A task's creation, log entries and completion in order.", TaskParams{}, []model.TaskEvent{}},
+	{"core.task_blockers", "Tasks a task is blocked by, directly or transitively.", TaskBlockersParams{}, []model.Task{}},
+	{"core.set_task_status", "Rewrite a task's checkbox in its note.", SetTaskStatusParams{}, model.Task{}},
+	{"core.archive_completed", "Move done tasks completed before a date into the archive note.", ArchiveCompletedParams{}, model.ArchiveReport{}},
+	{"core.items_for_tag", "Tasks and log entries carrying a tag.", TagParams{}, model.TagResult{}},
+	{"core.items_under_tag", "Tasks and log entries carrying a tag or one nested below it.", TagParams{}, model.TagResult{}},
+	{"core.tag_cooccurrence", "Tags most often found alongside a tag.", TagCooccurrenceParams{}, []model.TagCount{}},
+	{"core.search_tags", "Tags containing a partial name, prefix matches first.", SearchTagsParams{}, []string{}},
+	{"core.tag_graph", "Every tag with the IDs of the tasks and log entries carrying it.", nil, map[string]model.TagItems{}},
+	{"core.rename_tag", "Rename a tag throughout the vault, rewriting the notes that carry it.", RenameTagParams{}, RenameTagResult{}},
+	{"core.recent_mentions", "Task mentions across the vault, most recently dated note first.", LimitParams{}, []model.TaskMention{}},
+	{"core.notes_in_range", "Notes dated within a range.", NotesInRangeParams{}, []model.NoteMeta{}},
+	{"core.log_entries_in_range", "Log entries of notes dated within a range.", LogEntriesInRangeParams{}, []model.LogEntry{}},
+	{"core.list_undated_notes", "Notes without a date.", nil, []model.NoteMeta{}},
+	{"core.weekly_summary", "Notes and task activity within a range.", DateRangeParams{}, model.WeeklySummary{}},
+	{"core.dashboard", "Task counts, note and tag totals, and this week's task activity.", DateParams{}, model.Dashboard{}},
+	{"core.read_note", "A note with its content.", NoteParams{}, model.Note{}},
+	{"core.note_meta", "A note's metadata without its content.", NoteParams{}, model.NoteMeta{}},
+	{"core.note_contents", "The tasks and log entries extracted from a note.", NoteParams{}, model.NoteContents{}},
+	{"core.note_stats", "A note's word count and reading time.", NoteStatsParams{}, model.NoteStats{}},
+	{"core.list_notes", "Metadata of every note.", nil, []model.NoteMeta{}},
+	{"core.recent_notes", "Notes most recently modified on disk, newest first.", LimitParams{}, []model.NoteMeta{}},
+	{"core.write_note", "Replace a note's content; a WriteDiff preview with dry_run.", WriteNoteParams{}, oneOf{model.Note{}, model.WriteDiff{}}},
+	{"core.create_note", "Create a note at a vault-relative path.", CreateNoteParams{}, model.Note{}},
+	{"core.duplicate_note", "Copy a note to a new vault-relative path, optionally with fresh task IDs.", DuplicateNoteParams{}, model.Note{}},
+	{"core.note_backlinks", "Wikilinks pointing at a note.", NoteParams{}, []model.NoteRef{}},
+	{"core.note_outline", "A note's headings as a tree.", NoteParams{}, []model.OutlineNode{}},
+	{"core.lint_vault", "Duplicate task IDs, untracked tasks, unresolved links and orphaned task references.", nil, model.LintReport{}},
+	{"core.validate", "Parse content without saving it: tasks, log entries, warnings and missing sections.", ValidateParams{}, model.Validation{}},
+	{"core.scratch_append", "Capture text as a bullet in the in-memory scratch buffer.", ScratchAppendParams{}, []string{}},
+	{"core.scratch_read", "The scratch buffer's bullets, oldest first.", nil, []string{}},
+	{"core.scratch_flush", "Append the scratch buffer to a note's Log section and clear it.", NoteParams{}, model.Note{}},
+	{"core.index_integrity", "Internal index consistency report.", IndexIntegrityParams{}, []model.IntegrityIssue{}},
+	{"core.export", "Every indexed note with its tasks, log entries, mentions and links.", nil, model.Snapshot{}},
+	{"core.import", "Replace the index with a core.export snapshot without touching the vault.", ImportParams{}, model.IndexStats{}},
+	{"core.subscribe", "Receive core.changed notifications on this connection.", nil, map[string]bool{}},
+}
+
+// methods lists every method dispatch handles, in the order of its cases.
+var methods = func() []string {
+	names := make([]string, len(methodSpecs))
+	for i, spec := range methodSpecs {
+		names[i] = spec.name
+	}
+	return names
+}()
+
+// MethodDescription is the machine-readable description of a method.
+type MethodDescription struct {
+	Method  string  `json:"method"`
+	Summary string  `json:"summary"`
+	Params  *Schema `json:"params"`
+	Result  *Schema `json:"result"`
+}
+
+// Schema is a JSON Schema subset describing a params or result shape.
+// Required lists the fields sent without omitempty. Recursive types are cut
+// off at their second occurrence, which repeats only the type's Title.
+type Schema struct {
+	Type                 string             `json:"type,omitempty"`
+	Title                string             `json:"title,omitempty"`
+	Format               string             `json:"format,omitempty"`
+	Enum                 []string           `json:"enum,omitempty"`
+	Properties           map[string]*Schema `json:"properties,omitempty"`
+	Required             []string           `json:"required,omitempty"`
+	Items                *Schema            `json:"items,omitempty"`
+	AdditionalProperties *Schema            `json:"additionalProperties,omitempty"`
+	OneOf                []*Schema          `json:"oneOf,omitempty"`
+	Nullable             bool               `json:"nullable,omitempty"`
+}
+
+// describeMethod returns the description of the named method.
+func describeMethod(name string) (MethodDescription, bool) {
+	for _, spec := range methodSpecs {
+		if spec.name == name {
+			return spec.describe(), true
+		}
+	}
+	return MethodDescription{}, false
+}
+
+// describe reports the method's params with the vault member every method
+// accepts.
+func (spec methodSpec) describe() MethodDescription {
+	params := schemaOf(VaultParams{})
+	if spec.params != nil {
+		params = schemaOf(spec.params)
+		maps.Copy(params.Properties, schemaOf(VaultParams{}).Properties)
+	}
+	return MethodDescription{
+		Method:  spec.name,
+		Summary: spec.summary,
+		Params:  params,
+		Result:  schemaOf(spec.result),
+	}
+}
+
+func schemaOf(value any) *Schema {
+	if alternatives, ok := value.(oneOf); ok {
+		schema := &Schema{}
+		for _, alt := range alternatives {
+			schema.OneOf = append(schema.OneOf, schemaOf(alt))
+		}
+		return schema
+	}
+	return schemaFor(reflect.TypeOf(value), map[reflect.Type]bool{})
+}
+
+var (
+	timeType   = reflect.TypeFor[time.Time]()
+	statusType = reflect.TypeFor[model.TaskStatus]()
+	rawType    = reflect.TypeFor[json.RawMessage]()
+)
+
+func schemaFor(t reflect.Type, visiting map[reflect.Type]bool) *Schema {
+	switch t {
+	case timeType:
+		return &Schema{Type: "string", Format: "date-time"}
+	case statusType:
+		return &Schema{Type: "string", Enum: []string{
+			string(model.TaskStatusTodo),
+			string(model.TaskStatusInProgress),
+			string(model.TaskStatusBlocked),
+			string(model.TaskStatusDone),
+		}}
+	case rawType:
+		return &Schema{}
+	}
+
+	switch t.Kind() {
+	case reflect.Pointer:
+		schema := schemaFor(t.Elem(), visiting)
+		schema.Nullable = true
+		return schema
+	case reflect.String:
+		return &Schema{Type: "string"}
+	case reflect.Bool:
+		return &Schema{Type: "boolean"}
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64,
+		reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64:
+		return &Schema{Type: "integer"}
+	case reflect.Float32, reflect.Float64:
+		return &Schema{Type: "number"}
+	case reflect.Slice, reflect.Array:
+		return &Schema{Type: "array", Items: schemaFor(t.Elem(), visiting)}
+	case reflect.Map:
+		return &Schema{Type: "object", AdditionalProperties: schemaFor(t.Elem(), visiting)}
+	case reflect.Struct:
+		if visiting[t] {
+			return &Schema{Type: "object", Title: t.Name()}
+		}
+		visiting[t] = true
+		defer delete(visiting, t)
+		schema := &Schema{Type: "object", Title: t.Name(), Properties: map[string]*Schema{}}
+		addFields(schema, t, visiting)
+		return schema
+	default:
+		return &Schema{}
+	}
+}
+
+// addFields adds the JSON-encoded fields of struct type t to schema,
+// flattening embedded structs as encoding/json does.
+func addFields(schema *Schema, t reflect.Type, visiting map[reflect.Type]bool) {
+	for i := range t.NumField() {
+		field := t.Field(i)
+		if !field.IsExported() {
+			continue
+		}
+		name, opts, _ := strings.Cut(field.Tag.Get("json"), ",")
+		if name == "-" {
+			continue
+		}
+		if field.Anonymous && name == "" && field.Type.Kind() == reflect.Struct {
+			addFields(schema, field.Type, visiting)
+			continue
+		}
+		if name == "" {
+			name = field.Name
+		}
+		schema.Properties[name] = schemaFor(field.Type, visiting)
+		if !strings.Contains(opts, "omitempty") && field.Type.Kind() != reflect.Pointer {
+			schema.Required = append(schema.Required, name)
+		}
+	}
+}
@@ -0,0 +1,68 @@
+package rpc
+
+import (
+	"os"
+	"regexp"
+	"slices"
+	"testing"
+)
+
+// dispatchedMethods returns the methods named by the cases of dispatch's
+// switch, read from its source.
+func dispatchedMethods(t *testing.T) []string {
+	t.Helper()
+	src, err := os.ReadFile("dispatch.go")
+	if err != nil {
+		t.Fatal(err)
+	}
+	var names []string
+	for _, m := range regexp.MustCompile(`(?m)^\tcase "(core\.[a-z_]+)":$`).FindAllSubmatch(src, -1) {
+		names = append(names, string(m[1]))
+	}
+	if len(names) == 0 {
+		t.Fatal("no dispatch cases found")
+	}
+	return names
+}
+
+func TestCapabilitiesListDispatchedMethods(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{"note.md": taskNote}))
+	ping := decodeResult[PingResult](t, call(t, s, "core.ping", nil))
+	if !ping.Pong || ping.IndexedNotes != 1 || ping.IndexedTasks != 1 {
+		t.Errorf("ping = %+v, want pong with one note and one task", ping)
+	}
+
+	caps := decodeResult[Capabilities](t, call(t, s, "core.capabilities", nil))
+	if want := dispatchedMethods(t); !slices.Equal(caps.Methods, want) {
+		t.Errorf("capabilities = %v\nwant the dispatch cases %v", caps.Methods, want)
+	}
+	for _, method := range caps.Methods {
+		if resp := call(t, s, method, nil); resp.Error != nil && resp.Error.Code == CodeMethodNotFound {
+			t.Errorf("%s is listed but not handled", method)
+		}
+	}
+}
+
+func TestEveryDispatchedMethodIsDescribed(t *testing.T) {
+	s := newTestServer(t, writeVault(t, nil))
+	for _, method := range dispatchedMethods(t) {
+		desc := decodeResult[MethodDescription](t, call(t, s, "core.describe_method", map[string]any{"method": method}))
+		if desc.Method != method || desc.Summary == "" || desc.Params == nil || desc.Params.Type != "object" || desc.Result == nil {
+			t.Errorf("%s description = %+v", method, desc)
+			continue
+		}
+		if _, ok := desc.Params.Properties["vault"]; !ok {
+			t.Errorf("%s params lack the vault member", method)
+		}
+	}
+
+	all := decodeResult[[]MethodDescription](t, call(t, s, "core.describe_method", nil))
+	if len(all) != len(dispatchedMethods(t)) {
+		t.Errorf("describe_method without a method returned %d descriptions, want one per dispatched method", len(all))
+	}
+	detail := decodeResult[MethodDescription](t, call(t, s, "core.describe_method", map[string]any{"method": "core.task_detail"}))
+	if !slices.Contains(detail.Params.Required, "task_id") || detail.Params.Properties["task_id"].Type != "string" {
+		t.Errorf("task_detail params = %+v, want a required string task_id", detail.Params)
+	}
+	wantCode(t, call(t, s, "core.describe_method", map[string]any{"method": "core.no_such_method"}), CodeInvalidParams)
+}
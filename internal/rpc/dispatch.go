@@ -0,0 +1,892 @@
+package rpc
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/index"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// Capabilities is the result of core.capabilities.
+type Capabilities struct {
+	Version  string   `json:"version"`
+	Methods  []string `json:"methods"`
+	ReadOnly bool     `json:"read_only"`
+}
+
+// VaultInfo describes a vault served by the daemon, for core.list_vaults.
+type VaultInfo struct {
+	Name    string `json:"name"`
+	Root    string `json:"root"`
+	Primary bool   `json:"primary"`
+}
+
+// PingResult is the result of core.ping.
+type PingResult struct {
+	Pong         bool `json:"pong"`
+	IndexedNotes int  `json:"indexed_notes"`
+	IndexedTasks int  `json:"indexed_tasks"`
+}
+
+// ReindexResult is the result of core.reindex.
+type ReindexResult struct {
+	OK bool `json:"ok"`
+	model.ReindexReport
+}
+
+// RenameTagResult is the result of core.rename_tag.
+type RenameTagResult struct {
+	NotesChanged int `json:"notes_changed"`
+}
+
+// readMethods are the methods that only read a vault and its index. They
+// share the vault's lock and run on a read-only server. Any method not
+// listed is taken to write, holding the lock exclusively.
+var readMethods = map[string]bool{
+	"core.capabilities":         true,
+	"core.describe_method":      true,
+	"core.list_vaults":          true,
+	"core.ping":                 true,
+	"core.list_tasks":           true,
+	"core.list_tasks_next":      true,
+	"core.task_detail":          true,
+	"core.task_timeline":        true,
+	"core.task_blockers":        true,
+	"core.items_for_tag":        true,
+	"core.items_under_tag":      true,
+	"core.tag_cooccurrence":     true,
+	"core.search_tags":          true,
+	"core.tag_graph":            true,
+	"core.recent_mentions":      true,
+	"core.notes_in_range":       true,
+	"core.log_entries_in_range": true,
+	"core.list_undated_notes":   true,
+	"core.weekly_summary":       true,
+	"core.dashboard":            true,
+	"core.read_note":            true,
+	"core.note_meta":            true,
+	"core.note_contents":        true,
+	"core.note_stats":           true,
+	"core.list_notes":           true,
+	"core.recent_notes":         true,
+	"core.note_backlinks":       true,
+	"core.note_outline":         true,
+	"core.lint_vault":           true,
+	"core.validate":             true,
+	"core.scratch_read":         true,
+	"core.export":               true,
+	"core.subscribe":            true,
+}
+
+// indexOnlyMethods are the methods outside readMethods that change only the
+// in-memory index or scratch buffer, never a note on disk, and so still run
+// on a read-only server.
+var indexOnlyMethods = map[string]bool{
+	"core.reindex":         true,
+	"core.index_integrity": true,
+	"core.import":          true,
+	"core.scratch_append":  true,
+}
+
+// openMethods are the methods that only write when the note they open is
+// missing. On a read-only server they return an existing note and refuse to
+// create one.
+var openMethods = map[string]bool{
+	"core.open_daily":  true,
+	"core.open_weekly": true,
+}
+
+// readsOnly reports whether calling method with params only reads: it is
+// one of readMethods, or a core.write_note dry run, which previews a write
+// without making it.
+func readsOnly(method string, params json.RawMessage) bool {
+	if readMethods[method] {
+		return true
+	}
+	if method == "core.write_note" {
+		write, rpcErr := decodeParams[WriteNoteParams](params)
+		return rpcErr == nil && write.DryRun
+	}
+	return false
+}
+
+// dispatch routes a method call to its handler, against the vault named by
+// the params' vault member, holding that vault's lock shared for calls that
+// only read and exclusively for anything else.
+// A read-only server refuses every known method that does not only read,
+// unless it is one of indexOnlyMethods or openMethods; unknown methods are
+// left to fail as not found.
+func (s *Server) dispatch(conn *connection, method string, params json.RawMessage) (any, *Error) {
+	name, d, rpcErr := s.target(params)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	reads := readsOnly(method, params)
+	if s.readOnly && !reads && !indexOnlyMethods[method] && !openMethods[method] && slices.Contains(methods, method) {
+		return nil, ReadOnly(method)
+	}
+	if lock := s.locks[name]; reads {
+		lock.RLock()
+		defer lock.RUnlock()
+	} else {
+		lock.Lock()
+		defer lock.Unlock()
+	}
+	switch method {
+	case "core.capabilities":
+		return Capabilities{Version: s.version, Methods: slices.Clone(methods), ReadOnly: s.readOnly}, nil
+	case "core.describe_method":
+		return s.describe(params)
+	case "core.list_vaults":
+		return s.listVaults(), nil
+	case "core.ping":
+		stats := d.Stats()
+		return PingResult{Pong: true, IndexedNotes: stats.Notes, IndexedTasks: stats.Tasks}, nil
+	case "core.reindex":
+		return s.reindex(conn, name, d)
+	case "core.open_daily":
+		return s.openDaily(d, params)
+	case "core.open_weekly":
+		return s.openWeekly(d, params)
+	case "core.list_tasks":
+		return s.listTasks(d, params)
+	case "core.list_tasks_next":
+		return s.listTasksNext(d, params)
+	case "core.task_detail":
+		return s.taskDetail(d, params)
+	case "core.task_timeline":
+		return s.taskTimeline(d, params)
+	case "core.task_blockers":
+		return s.taskBlockers(d, params)
+	case "core.set_task_status":
+		return s.setTaskStatus(d, params)
+	case "core.archive_completed":
+		return s.archiveCompleted(d, params)
+	case "core.items_for_tag":
+		return s.itemsForTag(d, params)
+	case "core.items_under_tag":
+		return s.itemsUnderTag(d, params)
+	case "core.tag_cooccurrence":
+		return s.tagCooccurrence(d, params)
+	case "core.search_tags":
+		return s.searchTags(d, params)
+	case "core.tag_graph":
+		return d.TagGraph(), nil
+	case "core.rename_tag":
+		return s.renameTag(d, params)
+	case "core.recent_mentions":
+		return s.recentMentions(d, params)
+	case "core.notes_in_range":
+		return s.notesInRange(d, params)
+	case "core.log_entries_in_range":
+		return s.logEntriesInRange(d, params)
+	case "core.list_undated_notes":
+		return d.UndatedNotes(), nil
+	case "core.weekly_summary":
+		return s.weeklySummary(d, params)
+	case "core.dashboard":
+		return s.dashboard(d, params)
+	case "core.read_note":
+		return s.readNote(d, params)
+	case "core.note_meta":
+		return s.noteMeta(d, params)
+	case "core.note_contents":
+		return s.noteContents(d, params)
+	case "core.note_stats":
+		return s.noteStats(d, params)
+	case "core.list_notes":
+		return d.ListNotes(), nil
+	case "core.recent_notes":
+		return s.recentNotes(d, params)
+	case "core.write_note":
+		return s.writeNote(d, params)
+	case "core.create_note":
+		return s.createNote(d, params)
+	case "core.duplicate_note":
+		return s.duplicateNote(d, params)
+	case "core.note_backlinks":
+		return s.noteBacklinks(d, params)
+	case "core.note_outline":
+		return s.noteOutline(d, params)
+	case "core.lint_vault":
+		return d.LintVault(), nil
+	case "core.validate":
+		return s.validate(d, params)
+	case "core.scratch_append":
+		return s.scratchAppend(d, params)
+	case "core.scratch_read":
+		return d.ScratchRead(), nil
+	case "core.scratch_flush":
+		return s.scratchFlush(d, params)
+	case "core.index_integrity":
+		return s.indexIntegrity(d, params)
+	case "core.export":
+		return d.ExportSnapshot(), nil
+	case "core.import":
+		return s.importSnapshot(d, params)
+	case "core.subscribe":
+		return s.subscribeChanges(conn, name, d)
+	default:
+		return nil, MethodNotFound(method)
+	}
+}
+
+// target resolves the vault named by the params' vault member. Params that
+// are absent or not an object select the primary vault.
+func (s *Server) target(raw json.RawMessage) (string, *domain.Domain, *Error) {
+	name := s.primary
+	if trimmed := bytes.TrimSpace(raw); len(trimmed) > 0 && trimmed[0] == '{' {
+		params, rpcErr := decodeParams[VaultParams](raw)
+		if rpcErr != nil {
+			return "", nil, rpcErr
+		}
+		if params.Vault != "" {
+			name = params.Vault
+		}
+	}
+	d, ok := s.vaults[name]
+	if !ok {
+		return "", nil, InvalidParams("unknown vault: " + name)
+	}
+	return name, d, nil
+}
+
+func (s *Server) listVaults() []VaultInfo {
+	vaults := make([]VaultInfo, len(s.vaultNames))
+	for i, name := range s.vaultNames {
+		vaults[i] = VaultInfo{Name: name, Root: s.vaults[name].RootPath(), Primary: name == s.primary}
+	}
+	return vaults
+}
+
+func (s *Server) subscribeChanges(conn *connection, name string, d *domain.Domain) (any, *Error) {
+	if conn == nil {
+		return nil, InvalidRequest("core.subscribe requires a connection")
+	}
+	s.subscribe(conn, name, d)
+	return map[string]bool{"ok": true}, nil
+}
+
+func (s *Server) indexIntegrity(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[IndexIntegrityParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return d.IndexIntegrity(params.Compact), nil
+}
+
+func (s *Server) validate(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[ValidateParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	validation, err := d.Validate(params.Content)
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return validation, nil
+}
+
+func (s *Server) scratchAppend(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[ScratchAppendParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if strings.TrimSpace(params.Text) == "" {
+		return nil, InvalidParams("text is required")
+	}
+	return d.ScratchAppend(params.Text, s.now()), nil
+}
+
+func (s *Server) scratchFlush(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	note, err := d.ScratchFlush(params.NoteID)
+	switch {
+	case errors.Is(err, domain.ErrNotFound):
+		return nil, NotFound("note not found")
+	case errors.Is(err, domain.ErrInvalidPath):
+		return nil, InvalidParams("note_id must be relative and inside the vault")
+	case err != nil:
+		return nil, InternalError(err.Error())
+	}
+	return note, nil
+}
+
+func (s *Server) describe(raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[DescribeMethodParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Method == "" {
+		descriptions := make([]MethodDescription, len(methodSpecs))
+		for i, spec := range methodSpecs {
+			descriptions[i] = spec.describe()
+		}
+		return descriptions, nil
+	}
+	desc, ok := describeMethod(params.Method)
+	if !ok {
+		return nil, InvalidParams("unknown method: " + params.Method)
+	}
+	return desc, nil
+}
+
+// reindex rebuilds the vault's index. Over a connection, progress is sent as
+// core.reindex_progress notifications at most every ReindexProgressInterval,
+// and once all notes are processed.
+func (s *Server) reindex(conn *connection, name string, d *domain.Domain) (any, *Error) {
+	var progress index.ProgressFunc
+	if conn != nil {
+		var last time.Time
+		progress = func(processed, total int) {
+			if now := s.now(); processed == total || now.Sub(last) >= ReindexProgressInterval {
+				last = now
+				s.notify(conn, ReindexProgressMethod, ReindexProgressParams{Vault: name, Processed: processed, Total: total})
+			}
+		}
+	}
+	report, err := d.ReindexAllWithProgress(progress)
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	for _, failed := range report.Errors {
+		s.logger.Warn("note skipped", "vault", name, "path", failed.Path, "err", failed.Error)
+	}
+	for _, lossy := range report.Lossy {
+		s.logger.Warn("note is not valid UTF-8; invalid bytes replaced", "vault", name, "path", lossy)
+	}
+	return ReindexResult{OK: true, ReindexReport: report}, nil
+}
+
+func (s *Server) openDaily(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[DateParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	date, rpcErr := parseDayKeyword(params.Date, s.now())
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if s.readOnly {
+		if note, ok := d.Daily(date); ok {
+			return note, nil
+		}
+		return nil, ReadOnly("core.open_daily")
+	}
+	note, err := d.OpenDaily(date)
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return note, nil
+}
+
+func (s *Server) openWeekly(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[DateParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	date, rpcErr := parseDayKeyword(params.Date, s.now())
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if s.readOnly {
+		if note, ok := d.Weekly(date); ok {
+			return note, nil
+		}
+		return nil, ReadOnly("core.open_weekly")
+	}
+	note, err := d.OpenWeekly(date)
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return note, nil
+}
+
+func (s *Server) listTasks(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[ListTasksParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.PageSize != 0 {
+		return s.taskPage(d, params, "")
+	}
+	filter, rpcErr := s.taskFilter(params)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return d.ListTasks(filter), nil
+}
+
+func (s *Server) listTasksNext(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[CursorParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	cursor, rpcErr := decodeTaskCursor(params.Cursor)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return s.taskPage(d, cursor.Params, cursor.After)
+}
+
+// taskPage returns the page of tasks ordered after the given ID. One extra
+// task is fetched to learn whether another page follows.
+func (s *Server) taskPage(d *domain.Domain, params ListTasksParams, after model.TaskID) (any, *Error) {
+	if params.PageSize < 0 || params.PageSize > maxPageSize {
+		return nil, InvalidParams(fmt.Sprintf("page_size must be between 1 and %d", maxPageSize))
+	}
+	filter, rpcErr := s.taskFilter(params)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	filter.AfterID = after
+	filter.Limit = params.PageSize + 1
+
+	tasks := d.ListTasks(filter)
+	page := TaskPage{Tasks: tasks}
+	if len(tasks) > params.PageSize {
+		page.Tasks = tasks[:params.PageSize]
+		page.NextCursor = encodeTaskCursor(taskCursor{
+			Params: params,
+			After:  page.Tasks[len(page.Tasks)-1].ID,
+		})
+	}
+	return page, nil
+}
+
+// taskFilter builds the store filter for params, resolving touched_within
+// against the server's current local date.
+func (s *Server) taskFilter(params ListTasksParams) (index.TaskFilter, *Error) {
+	filter := index.TaskFilter{
+		Status:       params.Status,
+		Tags:         params.Tags,
+		ExcludeTags:  params.ExcludeTags,
+		SourceNoteID: params.SourceNoteID,
+	}
+	switch params.TagMatch {
+	case "", index.TagMatchAll, index.TagMatchAny:
+		filter.TagMatch = params.TagMatch
+	default:
+		return index.TaskFilter{}, InvalidParams("unknown tag_match: " + string(params.TagMatch))
+	}
+	switch params.TextSearchMode {
+	case "", "substring":
+		filter.TextSearch = params.TextSearch
+	case "whole_word", "regex":
+		if params.TextSearch != "" {
+			pattern, rpcErr := textPattern(params.TextSearch, params.TextSearchMode)
+			if rpcErr != nil {
+				return index.TaskFilter{}, rpcErr
+			}
+			filter.TextPattern = pattern
+		}
+	default:
+		return index.TaskFilter{}, InvalidParams("unknown text_search_mode: " + params.TextSearchMode)
+	}
+	if filter.Status != nil && !validStatus(*filter.Status) {
+		return index.TaskFilter{}, InvalidParams("unknown task status: " + string(*filter.Status))
+	}
+	switch {
+	case params.TouchedSince != "" && params.TouchedWithin != "":
+		return index.TaskFilter{}, InvalidParams("touched_since and touched_within are mutually exclusive")
+	case params.TouchedSince != "":
+		since, rpcErr := parseDate(params.TouchedSince)
+		if rpcErr != nil {
+			return index.TaskFilter{}, rpcErr
+		}
+		filter.TouchedSince = &since
+	case params.TouchedWithin != "":
+		since, rpcErr := parseRelativeDate(params.TouchedWithin, s.now())
+		if rpcErr != nil {
+			return index.TaskFilter{}, rpcErr
+		}
+		filter.TouchedSince = &since
+	}
+	if params.ClosedStart != "" || params.ClosedEnd != "" {
+		if params.ClosedStart == "" || params.ClosedEnd == "" {
+			return index.TaskFilter{}, InvalidParams("closed_start and closed_end must be given together")
+		}
+		closed, rpcErr := parseDateRange(DateRangeParams{Start: params.ClosedStart, End: params.ClosedEnd})
+		if rpcErr != nil {
+			return index.TaskFilter{}, rpcErr
+		}
+		filter.ClosedBetween = &closed
+	}
+	return filter, nil
+}
+
+func (s *Server) taskDetail(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[TaskParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	detail, ok := d.TaskDetail(params.TaskID)
+	if !ok {
+		return nil, NotFound("task not found")
+	}
+	return detail, nil
+}
+
+func (s *Server) taskTimeline(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[TaskParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	events, ok := d.TaskTimeline(params.TaskID)
+	if !ok {
+		return nil, NotFound("task not found")
+	}
+	return events, nil
+}
+
+func (s *Server) taskBlockers(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[TaskBlockersParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	blockers, ok := d.BlockersOf(params.TaskID)
+	if params.Transitive {
+		blockers, ok = d.BlockedByTransitive(params.TaskID)
+	}
+	if !ok {
+		return nil, NotFound("task not found")
+	}
+	return blockers, nil
+}
+
+func (s *Server) setTaskStatus(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[SetTaskStatusParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if !validStatus(params.Status) {
+		return nil, InvalidParams("unknown task status: " + string(params.Status))
+	}
+	task, err := d.SetTaskStatus(params.TaskID, params.Status)
+	if errors.Is(err, domain.ErrNotFound) {
+		return nil, NotFound("task not found")
+	}
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return task, nil
+}
+
+func (s *Server) archiveCompleted(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[ArchiveCompletedParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	before, rpcErr := parseDayKeyword(params.Before, s.now())
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	report, err := d.ArchiveCompleted(before)
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return report, nil
+}
+
+func (s *Server) itemsForTag(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[TagParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Tag == "" {
+		return nil, InvalidParams("tag is required")
+	}
+	return d.ItemsForTag(params.Tag), nil
+}
+
+func (s *Server) itemsUnderTag(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[TagParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Tag == "" {
+		return nil, InvalidParams("tag is required")
+	}
+	return d.ItemsUnderTag(params.Tag), nil
+}
+
+func (s *Server) tagCooccurrence(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[TagCooccurrenceParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Tag == "" {
+		return nil, InvalidParams("tag is required")
+	}
+	if params.Limit < 0 {
+		return nil, InvalidParams("limit must not be negative")
+	}
+	return d.TagCooccurrence(params.Tag, params.Limit), nil
+}
+
+func (s *Server) searchTags(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[SearchTagsParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Limit < 0 {
+		return nil, InvalidParams("limit must not be negative")
+	}
+	return d.SearchTags(params.Query, params.Limit), nil
+}
+
+func (s *Server) renameTag(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[RenameTagParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	n, err := d.RenameTag(params.Old, params.New, params.IncludeChildren)
+	if errors.Is(err, domain.ErrInvalidTag) {
+		return nil, InvalidParams("old and new must be valid tags")
+	}
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return RenameTagResult{NotesChanged: n}, nil
+}
+
+func (s *Server) recentMentions(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[LimitParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Limit < 0 {
+		return nil, InvalidParams("limit must not be negative")
+	}
+	return d.RecentMentions(params.Limit), nil
+}
+
+func (s *Server) notesInRange(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NotesInRangeParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	r, rpcErr := parseDateRange(params.DateRangeParams)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return d.NotesInRange(r, params.IncludeUndated), nil
+}
+
+func (s *Server) logEntriesInRange(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[LogEntriesInRangeParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	r, rpcErr := parseDateRange(params.DateRangeParams)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return d.LogEntriesInRange(r, params.IncludeUndated), nil
+}
+
+func (s *Server) weeklySummary(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[DateRangeParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	r, rpcErr := parseDateRange(params)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return d.WeeklySummary(r), nil
+}
+
+func (s *Server) dashboard(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[DateParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Date == "" {
+		params.Date = "today"
+	}
+	today, rpcErr := parseDayKeyword(params.Date, s.now())
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	return d.Dashboard(today), nil
+}
+
+func (s *Server) readNote(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	note, ok := d.ReadNote(params.NoteID)
+	if !ok {
+		return nil, NotFound("note not found")
+	}
+	return note, nil
+}
+
+func (s *Server) noteMeta(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	meta, ok := d.NoteMeta(params.NoteID)
+	if !ok {
+		return nil, NotFound("note not found")
+	}
+	return meta, nil
+}
+
+func (s *Server) noteContents(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	contents, ok := d.NoteContents(params.NoteID)
+	if !ok {
+		return nil, NotFound("note not found")
+	}
+	return contents, nil
+}
+
+func (s *Server) noteStats(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteStatsParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.WordsPerMinute < 0 {
+		return nil, InvalidParams("words_per_minute must not be negative")
+	}
+	stats, ok := d.NoteStats(params.NoteID, params.WordsPerMinute)
+	if !ok {
+		return nil, NotFound("note not found")
+	}
+	return stats, nil
+}
+
+func (s *Server) recentNotes(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[LimitParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Limit < 0 {
+		return nil, InvalidParams("limit must not be negative")
+	}
+	return d.RecentNotes(params.Limit), nil
+}
+
+func (s *Server) writeNote(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[WriteNoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.DryRun {
+		diff, err := d.PreviewWrite(params.NoteID, params.Content)
+		if errors.Is(err, domain.ErrNotFound) {
+			return nil, NotFound("note not found")
+		}
+		if err != nil {
+			return nil, InternalError(err.Error())
+		}
+		return diff, nil
+	}
+	note, err := d.WriteNote(params.NoteID, params.Content)
+	if errors.Is(err, domain.ErrInvalidPath) {
+		return nil, InvalidParams("note_id must be a path inside the vault")
+	}
+	if errors.Is(err, domain.ErrNotFound) {
+		return nil, NotFound("note not found")
+	}
+	if err != nil {
+		return nil, InternalError(err.Error())
+	}
+	return note, nil
+}
+
+func (s *Server) createNote(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[CreateNoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Path == "" {
+		return nil, InvalidParams("path is required")
+	}
+	note, err := d.CreateNote(params.Path, params.Content)
+	switch {
+	case errors.Is(err, domain.ErrInvalidPath):
+		return nil, InvalidParams("path must be relative, inside the vault, and a note the vault indexes")
+	case errors.Is(err, domain.ErrExists):
+		return nil, InvalidParams("note already exists")
+	case err != nil:
+		return nil, InternalError(err.Error())
+	}
+	return note, nil
+}
+
+func (s *Server) duplicateNote(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[DuplicateNoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if params.Path == "" {
+		return nil, InvalidParams("path is required")
+	}
+	note, err := d.DuplicateNote(params.NoteID, params.Path, params.RegenerateTaskIDs, s.now())
+	switch {
+	case errors.Is(err, domain.ErrNotFound):
+		return nil, NotFound("note not found")
+	case errors.Is(err, domain.ErrInvalidPath):
+		return nil, InvalidParams("path must be relative, inside the vault, and a note the vault indexes")
+	case errors.Is(err, domain.ErrExists):
+		return nil, InvalidParams("note already exists")
+	case errors.Is(err, domain.ErrInvalidTaskID):
+		return nil, InvalidParams("regenerate_task_ids mints T-YYYY-NNN IDs, which the configured task ID pattern does not match")
+	case err != nil:
+		return nil, InternalError(err.Error())
+	}
+	return note, nil
+}
+
+func (s *Server) importSnapshot(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[ImportParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	if err := d.ImportSnapshot(params.Snapshot); err != nil {
+		return nil, InvalidParams(err.Error())
+	}
+	return d.Stats(), nil
+}
+
+func (s *Server) noteBacklinks(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	refs, ok := d.NoteBacklinks(params.NoteID)
+	if !ok {
+		return nil, NotFound("note not found")
+	}
+	return refs, nil
+}
+
+func (s *Server) noteOutline(d *domain.Domain, raw json.RawMessage) (any, *Error) {
+	params, rpcErr := decodeParams[NoteParams](raw)
+	if rpcErr != nil {
+		return nil, rpcErr
+	}
+	outline, ok := d.NoteOutline(params.NoteID)
+	if !ok {
+		return nil, NotFound("note not found")
+	}
+	return outline, nil
+}
@@ -0,0 +1,156 @@
+package rpc
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"sync"
+	"testing"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+const taskNote = "# Note\n\n## Tasks\n\n- [ ] [T-1] Write the report\n\n## Log\n\n- drafted [T-1]\n"
+
+func TestReadOnlyRefusesWritesAndServesReads(t *testing.T) {
+	root := writeVault(t, map[string]string{"note.md": taskNote})
+	s := newTestServer(t, root, WithReadOnly(true))
+
+	wantCode(t, call(t, s, "core.write_note", map[string]any{"note_id": "note.md", "content": "# Replaced\n"}), CodeReadOnly)
+	wantCode(t, call(t, s, "core.set_task_status", map[string]any{"task_id": "T-1", "status": "done"}), CodeReadOnly)
+	raw, err := os.ReadFile(filepath.Join(root, "note.md"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if string(raw) != taskNote {
+		t.Errorf("note was rewritten:\n%s", raw)
+	}
+
+	tasks := decodeResult[[]model.Task](t, call(t, s, "core.list_tasks", nil))
+	if len(tasks) != 1 || tasks[0].ID != "T-1" {
+		t.Errorf("list_tasks = %+v, want T-1", tasks)
+	}
+}
+
+func TestReadOnlyAllowsWriteNoteDryRun(t *testing.T) {
+	root := writeVault(t, map[string]string{"note.md": taskNote})
+	s := newTestServer(t, root, WithReadOnly(true))
+
+	diff := decodeResult[model.WriteDiff](t, call(t, s, "core.write_note", map[string]any{
+		"note_id": "note.md",
+		"content": "# Note\n\n## Tasks\n",
+		"dry_run": true,
+	}))
+	if !slices.Equal(diff.RemovedTasks, []model.TaskID{"T-1"}) {
+		t.Errorf("removed tasks = %v, want [T-1]", diff.RemovedTasks)
+	}
+}
+
+func TestReadOnlyLeavesUnknownMethodsNotFound(t *testing.T) {
+	s := newTestServer(t, writeVault(t, nil), WithReadOnly(true))
+	wantCode(t, call(t, s, "core.no_such_method", nil), CodeMethodNotFound)
+}
+
+func TestReadMethodsAreDispatched(t *testing.T) {
+	for method := range readMethods {
+		if !slices.Contains(methods, method) {
+			t.Errorf("read method %s is not dispatched", method)
+		}
+	}
+}
+
+func TestConcurrentReadsDuringWrites(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{"note.md": taskNote}))
+	requests := []string{
+		`{"jsonrpc":"2.0","id":1,"method":"core.list_tasks"}`,
+		`{"jsonrpc":"2.0","id":2,"method":"core.read_note","params":{"note_id":"note.md"}}`,
+		`{"jsonrpc":"2.0","id":3,"method":"core.set_task_status","params":{"task_id":"T-1","status":"done"}}`,
+		`{"jsonrpc":"2.0","id":4,"method":"core.task_detail","params":{"task_id":"T-1"}}`,
+		`{"jsonrpc":"2.0","id":5,"method":"core.set_task_status","params":{"task_id":"T-1","status":"todo"}}`,
+	}
+
+	var wg sync.WaitGroup
+	for i := range 8 {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for j := range 25 {
+				req := requests[(i+j)%len(requests)]
+				resp := s.HandleRequest([]byte(req))
+				if resp == nil {
+					t.Errorf("%s: no response", req)
+					continue
+				}
+				if resp.Error != nil {
+					t.Errorf("%s: %v", req, resp.Error)
+				}
+			}
+		}()
+	}
+	wg.Wait()
+}
+
+func TestNoteMetaAndListNotes(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{
+		"2025-04-02.md": "# Wednesday\n",
+		"2025-04-01.md": "# Tuesday\n\nA long day.\n",
+		"zebra.md":      "# Zebra\n",
+		"apple.md":      "# Apple\n",
+	}))
+
+	meta := decodeResult[map[string]any](t, call(t, s, "core.note_meta", map[string]any{"note_id": "2025-04-01.md"}))
+	if meta["title"] != "Tuesday" || meta["date"] == nil {
+		t.Errorf("note_meta = %v, want the dated note titled Tuesday", meta)
+	}
+	if _, ok := meta["content"]; ok {
+		t.Error("note_meta included the note content")
+	}
+	wantCode(t, call(t, s, "core.note_meta", map[string]any{"note_id": "missing.md"}), CodeNotFound)
+
+	var ids []model.NoteID
+	for _, note := range decodeResult[[]model.NoteMeta](t, call(t, s, "core.list_notes", nil)) {
+		ids = append(ids, note.ID)
+	}
+	if want := []model.NoteID{"2025-04-01.md", "2025-04-02.md", "apple.md", "zebra.md"}; !slices.Equal(ids, want) {
+		t.Errorf("list_notes = %v, want %v", ids, want)
+	}
+}
+
+func TestMissingEntitiesAreNotFound(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{"note.md": taskNote}))
+
+	wantCode(t, call(t, s, "core.read_note", map[string]any{"note_id": "missing.md"}), CodeNotFound)
+	wantCode(t, call(t, s, "core.task_detail", map[string]any{"task_id": "T-404"}), CodeNotFound)
+	wantCode(t, call(t, s, "core.read_note", map[string]any{"note_id": 42}), CodeInvalidParams)
+}
+
+func TestRequestsRouteToNamedVaults(t *testing.T) {
+	personal, _, err := domain.OpenInMemory(writeVault(t, map[string]string{"home.md": "# Home\n\n## Tasks\n\n- [ ] [T-2] Fix the sink\n"}), nil, nil, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	s := newTestServer(t, writeVault(t, map[string]string{"note.md": taskNote}), WithPrimaryVault("work"), WithVault("personal", personal))
+
+	if tasks := decodeResult[[]model.Task](t, call(t, s, "core.list_tasks", map[string]any{"vault": "personal"})); len(tasks) != 1 || tasks[0].ID != "T-2" {
+		t.Errorf("personal tasks = %+v, want T-2", tasks)
+	}
+	if tasks := decodeResult[[]model.Task](t, call(t, s, "core.list_tasks", nil)); len(tasks) != 1 || tasks[0].ID != "T-1" {
+		t.Errorf("primary tasks = %+v, want T-1", tasks)
+	}
+	wantCode(t, call(t, s, "core.task_detail", map[string]any{"vault": "personal", "task_id": "T-1"}), CodeNotFound)
+	wantCode(t, call(t, s, "core.list_tasks", map[string]any{"vault": "archive"}), CodeInvalidParams)
+
+	vaults := decodeResult[[]VaultInfo](t, call(t, s, "core.list_vaults", nil))
+	if len(vaults) != 2 || vaults[0].Name != "work" || !vaults[0].Primary || vaults[1].Name != "personal" || vaults[1].Primary {
+		t.Errorf("vaults = %+v, want the primary work vault then personal", vaults)
+	}
+}
+
+func TestTaskDetailIncludesDescriptionMarkdown(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{"plan.md": "## Tasks\n\n- [ ] [T-1] Plan offsite\n  Book the venue.\n\n  **Budget**: tight.\n"}))
+	detail := decodeResult[model.TaskDetail](t, call(t, s, "core.task_detail", map[string]any{"task_id": "T-1"}))
+	if got := detail.Task.DescriptionMD; got != "Book the venue.\n\n**Budget**: tight." {
+		t.Errorf("description_md = %q", got)
+	}
+}
@@ -1,3 +1,3 @@
-// Package rpc will provide JSON-RPC handlers and wiring.
+// Package rpc provides the JSON-RPC 2.0 protocol types, method dispatch and
+// the line-delimited server loop.
 package rpc
-
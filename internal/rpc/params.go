@@ -0,0 +1,306 @@
+package rpc
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"strconv"
+	"time"
+
+	"github.com/weakphish/yapper/internal/index"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// dateLayout is the wire format for calendar days.
+const dateLayout = "2006-01-02"
+
+var relativeDateRE = regexp.MustCompile(`^([0-9]+)([dwm])$`)
+
+// VaultParams selects the vault a request targets. Every method accepts it
+// alongside its own params; an empty Vault selects the primary vault.
+type VaultParams struct {
+	Vault string `json:"vault,omitempty"`
+}
+
+// DescribeMethodParams names the method core.describe_method describes. An
+// empty Method describes every method.
+type DescribeMethodParams struct {
+	Method string `json:"method,omitempty"`
+}
+
+// DateParams carries a single day, e.g. for core.open_daily and
+// core.open_weekly, which also accept "today", "yesterday" and "tomorrow".
+type DateParams struct {
+	Date string `json:"date"`
+}
+
+// DateRangeParams carries an inclusive range of days.
+type DateRangeParams struct {
+	Start string `json:"start"`
+	End   string `json:"end"`
+}
+
+// NotesInRangeParams are the params of core.notes_in_range. With
+// IncludeUndated set, notes without a date are appended to the result.
+type NotesInRangeParams struct {
+	DateRangeParams
+	IncludeUndated bool `json:"include_undated,omitempty"`
+}
+
+// LogEntriesInRangeParams are the params of core.log_entries_in_range. With
+// IncludeUndated set, entries from notes without a date are appended.
+type LogEntriesInRangeParams struct {
+	DateRangeParams
+	IncludeUndated bool `json:"include_undated,omitempty"`
+}
+
+// ListTasksParams are the filters accepted by core.list_tasks. TagMatch is
+// "all" (the default) to require every tag in Tags, or "any" to require one.
+// TextSearchMode selects how TextSearch matches: "substring" (the default),
+// "whole_word" or "regex"; all ignore case. TouchedWithin is a relative
+// alternative to TouchedSince, such as "7d", "2w" or "1m". ClosedStart and
+// ClosedEnd must be given together. Setting PageSize switches the result to
+// a TaskPage.
+type ListTasksParams struct {
+	Status         *model.TaskStatus `json:"status,omitempty"`
+	Tags           []string          `json:"tags,omitempty"`
+	TagMatch       index.TagMatch    `json:"tag_match,omitempty"`
+	ExcludeTags    []string          `json:"exclude_tags,omitempty"`
+	SourceNoteID   model.NoteID      `json:"source_note_id,omitempty"`
+	TextSearch     string            `json:"text_search,omitempty"`
+	TextSearchMode string            `json:"text_search_mode,omitempty"`
+	TouchedSince   string            `json:"touched_since,omitempty"`
+	TouchedWithin  string            `json:"touched_within,omitempty"`
+	ClosedStart    string            `json:"closed_start,omitempty"`
+	ClosedEnd      string            `json:"closed_end,omitempty"`
+	PageSize       int               `json:"page_size,omitempty"`
+}
+
+// CursorParams carries a pagination cursor for core.list_tasks_next.
+type CursorParams struct {
+	Cursor string `json:"cursor"`
+}
+
+// TaskParams identifies a task.
+type TaskParams struct {
+	TaskID model.TaskID `json:"task_id"`
+}
+
+// TaskBlockersParams identifies a task whose blockers core.task_blockers
+// lists. With Transitive set, blockers of blockers are included.
+type TaskBlockersParams struct {
+	TaskParams
+	Transitive bool `json:"transitive,omitempty"`
+}
+
+// ArchiveCompletedParams selects the done tasks core.archive_completed moves:
+// those completed before Before, a YYYY-MM-DD date or a day keyword.
+type ArchiveCompletedParams struct {
+	Before string `json:"before"`
+}
+
+// SetTaskStatusParams carries the new status for a task.
+type SetTaskStatusParams struct {
+	TaskID model.TaskID     `json:"task_id"`
+	Status model.TaskStatus `json:"status"`
+}
+
+// TagParams identifies a tag.
+type TagParams struct {
+	Tag string `json:"tag"`
+}
+
+// TagCooccurrenceParams selects a tag and how many co-occurring tags to
+// return. A zero Limit returns them all.
+type TagCooccurrenceParams struct {
+	Tag   string `json:"tag"`
+	Limit int    `json:"limit,omitempty"`
+}
+
+// SearchTagsParams carries a partial tag to complete. A zero Limit returns
+// every match.
+type SearchTagsParams struct {
+	Query string `json:"query"`
+	Limit int    `json:"limit,omitempty"`
+}
+
+// RenameTagParams names the tag to rename and its replacement. With
+// IncludeChildren set, tags nested below Old are renamed too.
+type RenameTagParams struct {
+	Old             string `json:"old"`
+	New             string `json:"new"`
+	IncludeChildren bool   `json:"include_children,omitempty"`
+}
+
+// LimitParams caps the number of results. A zero Limit returns them all.
+type LimitParams struct {
+	Limit int `json:"limit,omitempty"`
+}
+
+// NoteParams identifies a note.
+type NoteParams struct {
+	NoteID model.NoteID `json:"note_id"`
+}
+
+// NoteStatsParams identifies a note and the reading speed to assume. A zero
+// WordsPerMinute uses domain.DefaultWordsPerMinute.
+type NoteStatsParams struct {
+	NoteParams
+	WordsPerMinute int `json:"words_per_minute,omitempty"`
+}
+
+// CreateNoteParams carries the vault-relative path and content of a new
+// note.
+type CreateNoteParams struct {
+	Path    string `json:"path"`
+	Content string `json:"content"`
+}
+
+// DuplicateNoteParams names the note to copy and the vault-relative path of
+// the copy. With RegenerateTaskIDs set, the copy's tasks get fresh IDs.
+type DuplicateNoteParams struct {
+	NoteID            model.NoteID `json:"note_id"`
+	Path              string       `json:"path"`
+	RegenerateTaskIDs bool         `json:"regenerate_task_ids,omitempty"`
+}
+
+// WriteNoteParams carries the new content for a note. With DryRun set the
+// write is only previewed and a model.WriteDiff is returned.
+type WriteNoteParams struct {
+	NoteID  model.NoteID `json:"note_id"`
+	Content string       `json:"content"`
+	DryRun  bool         `json:"dry_run,omitempty"`
+}
+
+// ScratchAppendParams carries text to capture in the scratch buffer.
+type ScratchAppendParams struct {
+	Text string `json:"text"`
+}
+
+// ValidateParams carries content for core.validate to parse.
+type ValidateParams struct {
+	Content string `json:"content"`
+}
+
+// IndexIntegrityParams asks core.index_integrity to prune dangling entries
+// before reporting.
+type IndexIntegrityParams struct {
+	Compact bool `json:"compact,omitempty"`
+}
+
+// ImportParams carries the snapshot core.import loads.
+type ImportParams struct {
+	Snapshot model.Snapshot `json:"snapshot"`
+}
+
+// decodeParams unmarshals raw into a T. Missing params decode to the zero
+// value so methods with only optional fields accept an absent params member.
+func decodeParams[T any](raw json.RawMessage) (T, *Error) {
+	var params T
+	if len(raw) == 0 {
+		return params, nil
+	}
+	if err := json.Unmarshal(raw, &params); err != nil {
+		return params, InvalidParams(fmt.Sprintf("invalid params: %v", err))
+	}
+	return params, nil
+}
+
+func parseDate(value string) (time.Time, *Error) {
+	date, err := time.Parse(dateLayout, value)
+	if err != nil {
+		return time.Time{}, InvalidParams(fmt.Sprintf("invalid date %q, expected YYYY-MM-DD", value))
+	}
+	return date, nil
+}
+
+// maxSearchPatternLen bounds regex text searches. RE2 matches in linear
+// time, so only the size of the compiled program needs guarding.
+const maxSearchPatternLen = 256
+
+// textPattern compiles a whole_word or regex text search into a
+// case-insensitive expression.
+func textPattern(query, mode string) (*regexp.Regexp, *Error) {
+	var expr string
+	switch mode {
+	case "whole_word":
+		expr = `\b` + regexp.QuoteMeta(query) + `\b`
+	case "regex":
+		if len(query) > maxSearchPatternLen {
+			return nil, InvalidParams(fmt.Sprintf("text_search pattern exceeds %d bytes", maxSearchPatternLen))
+		}
+		expr = query
+	default:
+		return nil, InvalidParams("unknown text_search_mode: " + mode)
+	}
+	re, err := regexp.Compile("(?i)" + expr)
+	if err != nil {
+		return nil, InvalidParams(fmt.Sprintf("invalid text_search pattern: %v", err))
+	}
+	return re, nil
+}
+
+// parseDayKeyword resolves "today", "yesterday" and "tomorrow" against the
+// local date of now, and parses anything else as a YYYY-MM-DD date.
+func parseDayKeyword(value string, now time.Time) (time.Time, *Error) {
+	today := time.Date(now.Year(), now.Month(), now.Day(), 0, 0, 0, 0, time.UTC)
+	switch value {
+	case "today":
+		return today, nil
+	case "yesterday":
+		return today.AddDate(0, 0, -1), nil
+	case "tomorrow":
+		return today.AddDate(0, 0, 1), nil
+	}
+	date, err := time.Parse(dateLayout, value)
+	if err != nil {
+		return time.Time{}, InvalidParams(fmt.Sprintf("invalid date %q, expected YYYY-MM-DD, today, yesterday or tomorrow", value))
+	}
+	return date, nil
+}
+
+// parseRelativeDate resolves a span such as "7d", "2w" or "3m" (days, weeks
+// or months) to the day that far before today.
+func parseRelativeDate(spec string, today time.Time) (time.Time, *Error) {
+	m := relativeDateRE.FindStringSubmatch(spec)
+	if m == nil {
+		return time.Time{}, InvalidParams(fmt.Sprintf("invalid relative date %q, expected e.g. 7d, 2w or 1m", spec))
+	}
+	n, err := strconv.Atoi(m[1])
+	if err != nil {
+		return time.Time{}, InvalidParams(fmt.Sprintf("invalid relative date %q: %v", spec, err))
+	}
+	day := time.Date(today.Year(), today.Month(), today.Day(), 0, 0, 0, 0, time.UTC)
+	switch m[2] {
+	case "d":
+		return day.AddDate(0, 0, -n), nil
+	case "w":
+		return day.AddDate(0, 0, -7*n), nil
+	default:
+		return day.AddDate(0, -n, 0), nil
+	}
+}
+
+func parseDateRange(params DateRangeParams) (model.DateRange, *Error) {
+	start, rpcErr := parseDate(params.Start)
+	if rpcErr != nil {
+		return model.DateRange{}, rpcErr
+	}
+	end, rpcErr := parseDate(params.End)
+	if rpcErr != nil {
+		return model.DateRange{}, rpcErr
+	}
+	if end.Before(start) {
+		return model.DateRange{}, InvalidParams("range end is before start")
+	}
+	return model.DateRange{Start: start, End: end}, nil
+}
+
+func validStatus(status model.TaskStatus) bool {
+	switch status {
+	case model.TaskStatusTodo, model.TaskStatusInProgress, model.TaskStatusBlocked, model.TaskStatusDone:
+		return true
+	default:
+		return false
+	}
+}
@@ -0,0 +1,143 @@
+package rpc
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+func TestParseRelativeDate(t *testing.T) {
+	today := time.Date(2025, time.April, 10, 15, 30, 0, 0, time.UTC)
+	for spec, want := range map[string]time.Time{
+		"7d":  time.Date(2025, time.April, 3, 0, 0, 0, 0, time.UTC),
+		"0d":  time.Date(2025, time.April, 10, 0, 0, 0, 0, time.UTC),
+		"2w":  time.Date(2025, time.March, 27, 0, 0, 0, 0, time.UTC),
+		"1m":  time.Date(2025, time.March, 10, 0, 0, 0, 0, time.UTC),
+		"12m": time.Date(2024, time.April, 10, 0, 0, 0, 0, time.UTC),
+	} {
+		got, rpcErr := parseRelativeDate(spec, today)
+		if rpcErr != nil || !got.Equal(want) {
+			t.Errorf("parseRelativeDate(%q) = %v, %v, want %v", spec, got, rpcErr, want)
+		}
+	}
+	for _, spec := range []string{"", "7", "d7", "7x", "-7d", "7 d", "1y"} {
+		if _, rpcErr := parseRelativeDate(spec, today); rpcErr == nil || rpcErr.Code != CodeInvalidParams {
+			t.Errorf("parseRelativeDate(%q) error = %v, want invalid params", spec, rpcErr)
+		}
+	}
+}
+
+func TestListTasksTouchedWithin(t *testing.T) {
+	now := time.Date(2025, time.April, 10, 15, 30, 0, 0, time.UTC)
+	root := writeVault(t, map[string]string{
+		"recent.md": "## Tasks\n\n- [ ] [T-1] Recent\n",
+		"stale.md":  "## Tasks\n\n- [ ] [T-2] Stale\n",
+	})
+	for name, modified := range map[string]time.Time{"recent.md": now.AddDate(0, 0, -2), "stale.md": now.AddDate(0, 0, -30)} {
+		if err := os.Chtimes(filepath.Join(root, name), modified, modified); err != nil {
+			t.Fatal(err)
+		}
+	}
+	s := newTestServer(t, root, WithClock(func() time.Time { return now }))
+
+	tasks := decodeResult[[]model.Task](t, call(t, s, "core.list_tasks", map[string]any{"touched_within": "7d"}))
+	if len(tasks) != 1 || tasks[0].ID != "T-1" {
+		t.Errorf("touched within 7d = %+v, want T-1", tasks)
+	}
+	wantCode(t, call(t, s, "core.list_tasks", map[string]any{"touched_within": "soon"}), CodeInvalidParams)
+	wantCode(t, call(t, s, "core.list_tasks", map[string]any{"touched_within": "7d", "touched_since": "2025-04-01"}), CodeInvalidParams)
+}
+
+func TestParseDayKeywordUsesLocalDate(t *testing.T) {
+	// Late evening west of Greenwich is already the next day in UTC.
+	now := time.Date(2025, time.April, 1, 23, 30, 0, 0, time.FixedZone("EST", -5*60*60))
+	for value, want := range map[string]time.Time{
+		"today":      time.Date(2025, time.April, 1, 0, 0, 0, 0, time.UTC),
+		"yesterday":  time.Date(2025, time.March, 31, 0, 0, 0, 0, time.UTC),
+		"tomorrow":   time.Date(2025, time.April, 2, 0, 0, 0, 0, time.UTC),
+		"2024-02-29": time.Date(2024, time.February, 29, 0, 0, 0, 0, time.UTC),
+	} {
+		got, rpcErr := parseDayKeyword(value, now)
+		if rpcErr != nil || !got.Equal(want) {
+			t.Errorf("parseDayKeyword(%q) = %v, %v, want %v", value, got, rpcErr, want)
+		}
+	}
+}
+
+func TestOpenDailyAcceptsDayKeywords(t *testing.T) {
+	now := time.Date(2025, time.April, 1, 9, 0, 0, 0, time.UTC)
+	root := writeVault(t, nil)
+	s := newTestServer(t, root, WithClock(func() time.Time { return now }))
+
+	for value, want := range map[string]model.NoteID{
+		"today":      "2025-04-01.md",
+		"yesterday":  "2025-03-31.md",
+		"2025-05-05": "2025-05-05.md",
+	} {
+		if note := decodeResult[model.Note](t, call(t, s, "core.open_daily", map[string]any{"date": value})); note.ID != want {
+			t.Errorf("open_daily %q = %s, want %s", value, note.ID, want)
+		}
+	}
+	wantCode(t, call(t, s, "core.open_daily", map[string]any{"date": "someday"}), CodeInvalidParams)
+}
+
+func TestListTasksTextSearchModes(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{"work.md": "## Tasks\n\n" +
+		"- [ ] [T-1] Fix login bug\n" +
+		"- [ ] [T-2] Debugging session\n" +
+		"- [ ] [T-3] Release v2.1\n  Run the bug bash with the whole team before tagging\n"}))
+	search := func(query, mode string) []model.TaskID {
+		t.Helper()
+		var ids []model.TaskID
+		for _, task := range decodeResult[[]model.Task](t, call(t, s, "core.list_tasks", map[string]any{"text_search": query, "text_search_mode": mode})) {
+			ids = append(ids, task.ID)
+		}
+		return ids
+	}
+
+	for _, tc := range []struct {
+		query, mode string
+		want        []model.TaskID
+	}{
+		{"BUG", "", []model.TaskID{"T-1", "T-2", "T-3"}},
+		{"bug", "whole_word", []model.TaskID{"T-1", "T-3"}},
+		{`^fix|v\d+\.\d+`, "regex", []model.TaskID{"T-1", "T-3"}},
+	} {
+		if got := search(tc.query, tc.mode); !slices.Equal(got, tc.want) {
+			t.Errorf("%s search for %q = %v, want %v", tc.mode, tc.query, got, tc.want)
+		}
+	}
+	for _, params := range []map[string]any{
+		{"text_search": "(unclosed", "text_search_mode": "regex"},
+		{"text_search": strings.Repeat("a", maxSearchPatternLen+1), "text_search_mode": "regex"},
+		{"text_search": "bug", "text_search_mode": "fuzzy"},
+	} {
+		wantCode(t, call(t, s, "core.list_tasks", params), CodeInvalidParams)
+	}
+}
+
+func TestListTasksTagMatchParam(t *testing.T) {
+	s := newTestServer(t, writeVault(t, map[string]string{
+		"tags.md": "## Tasks\n\n- [ ] [T-1] Both #api #ops\n- [ ] [T-2] Only api #api\n- [ ] [T-3] Neither\n",
+	}))
+	ids := func(params map[string]any) []model.TaskID {
+		var got []model.TaskID
+		for _, task := range decodeResult[[]model.Task](t, call(t, s, "core.list_tasks", params)) {
+			got = append(got, task.ID)
+		}
+		return got
+	}
+
+	if got := ids(map[string]any{"tags": []string{"api", "ops"}}); !slices.Equal(got, []model.TaskID{"T-1"}) {
+		t.Errorf("default tag match = %v, want [T-1]", got)
+	}
+	if got := ids(map[string]any{"tags": []string{"api", "ops"}, "tag_match": "any"}); !slices.Equal(got, []model.TaskID{"T-1", "T-2"}) {
+		t.Errorf("tag_match any = %v, want [T-1 T-2]", got)
+	}
+	wantCode(t, call(t, s, "core.list_tasks", map[string]any{"tags": []string{"api"}, "tag_match": "some"}), CodeInvalidParams)
+}
@@ -0,0 +1,130 @@
+package rpc
+
+import (
+	"encoding/json"
+	"fmt"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// Version is the JSON-RPC protocol version spoken by the daemon.
+const Version = "2.0"
+
+// Request is a JSON-RPC 2.0 request. Requests without an ID are
+// notifications and receive no response.
+type Request struct {
+	JSONRPC string          `json:"jsonrpc"`
+	ID      json.RawMessage `json:"id,omitempty"`
+	Method  string          `json:"method"`
+	Params  json.RawMessage `json:"params,omitempty"`
+}
+
+// Response is a JSON-RPC 2.0 response carrying either a result or an error.
+type Response struct {
+	JSONRPC string          `json:"jsonrpc"`
+	ID      json.RawMessage `json:"id"`
+	Result  any             `json:"result,omitempty"`
+	Error   *Error          `json:"error,omitempty"`
+}
+
+// Notification is a server-initiated JSON-RPC 2.0 message. It carries no ID
+// and expects no response.
+type Notification struct {
+	JSONRPC string `json:"jsonrpc"`
+	Method  string `json:"method"`
+	Params  any    `json:"params,omitempty"`
+}
+
+// ChangedMethod is the notification sent to subscribed clients whenever the
+// index changes.
+const ChangedMethod = "core.changed"
+
+// ChangedParams is the payload of a core.changed notification: the vault and
+// the notes and tasks in it whose indexed state may have changed, including
+// removed ones.
+type ChangedParams struct {
+	Vault   string         `json:"vault"`
+	NoteIDs []model.NoteID `json:"note_ids"`
+	TaskIDs []model.TaskID `json:"task_ids"`
+}
+
+// ReindexProgressMethod is the notification sent to the client driving a
+// core.reindex as notes are indexed.
+const ReindexProgressMethod = "core.reindex_progress"
+
+// ReindexProgressParams is the payload of a core.reindex_progress
+// notification.
+type ReindexProgressParams struct {
+	Vault     string `json:"vault"`
+	Processed int    `json:"processed"`
+	Total     int    `json:"total"`
+}
+
+// ErrorCode is a JSON-RPC error code.
+type ErrorCode int
+
+const (
+	// CodeParseError indicates the request was not valid JSON.
+	CodeParseError ErrorCode = -32700
+	// CodeInvalidRequest indicates the JSON was not a valid request object.
+	CodeInvalidRequest ErrorCode = -32600
+	// CodeMethodNotFound indicates the method does not exist.
+	CodeMethodNotFound ErrorCode = -32601
+	// CodeInvalidParams indicates the method parameters were invalid.
+	CodeInvalidParams ErrorCode = -32602
+	// CodeInternalError indicates the daemon failed to process the request.
+	CodeInternalError ErrorCode = -32603
+	// CodeNotFound indicates the request was valid but named a note or task
+	// that does not exist. It is in the implementation-defined server range.
+	CodeNotFound ErrorCode = -32004
+	// CodeReadOnly indicates the method would change a vault's notes while
+	// the daemon is read-only.
+	CodeReadOnly ErrorCode = -32005
+)
+
+// Error is a JSON-RPC error object.
+type Error struct {
+	Code    ErrorCode `json:"code"`
+	Message string    `json:"message"`
+	Data    any       `json:"data,omitempty"`
+}
+
+// Error implements the error interface.
+func (e *Error) Error() string {
+	return fmt.Sprintf("rpc error %d: %s", e.Code, e.Message)
+}
+
+// ParseError reports malformed JSON.
+func ParseError(message string) *Error {
+	return &Error{Code: CodeParseError, Message: message}
+}
+
+// InvalidRequest reports a request that violates the protocol.
+func InvalidRequest(message string) *Error {
+	return &Error{Code: CodeInvalidRequest, Message: message}
+}
+
+// MethodNotFound reports an unknown method.
+func MethodNotFound(method string) *Error {
+	return &Error{Code: CodeMethodNotFound, Message: fmt.Sprintf("method not found: %s", method)}
+}
+
+// InvalidParams reports parameters that could not be decoded or validated.
+func InvalidParams(message string) *Error {
+	return &Error{Code: CodeInvalidParams, Message: message}
+}
+
+// NotFound reports that the entity a request refers to does not exist.
+func NotFound(message string) *Error {
+	return &Error{Code: CodeNotFound, Message: message}
+}
+
+// ReadOnly reports that method was refused because the daemon is read-only.
+func ReadOnly(method string) *Error {
+	return &Error{Code: CodeReadOnly, Message: fmt.Sprintf("%s: daemon is read-only", method)}
+}
+
+// InternalError reports a failure while handling an otherwise valid request.
+func InternalError(message string) *Error {
+	return &Error{Code: CodeInternalError, Message: message}
+}
@@ -0,0 +1,356 @@
+package rpc
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"log/slog"
+	"net"
+	"runtime/debug"
+	"slices"
+	"sync"
+	"time"
+
+	"github.com/weakphish/yapper/internal/domain"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// DefaultSlowThreshold is how long a request may take before it is logged
+// at Warn.
+const DefaultSlowThreshold = 500 * time.Millisecond
+
+// ReindexProgressInterval is the minimum time between core.reindex_progress
+// notifications.
+const ReindexProgressInterval = 250 * time.Millisecond
+
+// DefaultMaxRequestBytes is the longest request line Serve accepts unless
+// WithMaxRequestBytes says otherwise.
+const DefaultMaxRequestBytes = 16 << 20
+
+// DefaultVaultName names the primary vault unless WithPrimaryVault says
+// otherwise.
+const DefaultVaultName = "default"
+
+// Server answers JSON-RPC requests against one or more named vaults, each
+// with its own Domain. Requests without a vault param target the primary.
+//
+// Requests from concurrent clients are coordinated per vault with a
+// read-write lock: the methods in readMethods run side by side, while every
+// other method runs alone. Vaults do not block each other.
+type Server struct {
+	vaults        map[string]*domain.Domain
+	locks         map[string]*sync.RWMutex
+	vaultNames    []string
+	primary       string
+	logger        *slog.Logger
+	slowThreshold time.Duration
+	now           func() time.Time
+	version       string
+	readOnly      bool
+	maxRequest    int
+}
+
+// ServerOption configures a Server.
+type ServerOption func(*Server)
+
+// WithSlowThreshold sets the duration above which a request is logged at
+// Warn. A zero or negative threshold disables the warning.
+func WithSlowThreshold(threshold time.Duration) ServerOption {
+	return func(s *Server) {
+		s.slowThreshold = threshold
+	}
+}
+
+// WithClock overrides the clock used to time requests and resolve relative
+// dates.
+func WithClock(now func() time.Time) ServerOption {
+	return func(s *Server) {
+		s.now = now
+	}
+}
+
+// WithVersion sets the daemon version reported by core.capabilities.
+func WithVersion(version string) ServerOption {
+	return func(s *Server) {
+		s.version = version
+	}
+}
+
+// WithMaxRequestBytes sets the longest request line Serve accepts. Longer
+// lines are discarded without being buffered whole and answered with a parse
+// error, and the connection carries on with the next line. Zero or a
+// negative limit accepts lines of any length.
+func WithMaxRequestBytes(limit int) ServerOption {
+	return func(s *Server) {
+		s.maxRequest = limit
+	}
+}
+
+// WithReadOnly refuses, with CodeReadOnly, every method that would write a
+// vault's notes. Queries and index-only methods such as core.reindex still
+// run, and core.open_daily and core.open_weekly return existing notes.
+func WithReadOnly(readOnly bool) ServerOption {
+	return func(s *Server) {
+		s.readOnly = readOnly
+	}
+}
+
+// WithPrimaryVault names the vault passed to NewServer, replacing
+// DefaultVaultName.
+func WithPrimaryVault(name string) ServerOption {
+	return func(s *Server) {
+		s.primary = name
+	}
+}
+
+// WithVault registers an additional vault that requests select by name.
+// Registering a name again replaces its Domain; the primary's name always
+// refers to the Domain passed to NewServer.
+func WithVault(name string, d *domain.Domain) ServerOption {
+	return func(s *Server) {
+		if _, ok := s.vaults[name]; !ok {
+			s.vaultNames = append(s.vaultNames, name)
+		}
+		s.vaults[name] = d
+	}
+}
+
+// NewServer returns a server dispatching to d as its primary vault.
+func NewServer(d *domain.Domain, logger *slog.Logger, opts ...ServerOption) *Server {
+	s := &Server{
+		vaults:        map[string]*domain.Domain{},
+		primary:       DefaultVaultName,
+		logger:        logger,
+		slowThreshold: DefaultSlowThreshold,
+		now:           time.Now,
+		version:       "dev",
+		maxRequest:    DefaultMaxRequestBytes,
+	}
+	for _, opt := range opts {
+		opt(s)
+	}
+	s.vaultNames = slices.DeleteFunc(s.vaultNames, func(name string) bool { return name == s.primary })
+	s.vaultNames = slices.Insert(s.vaultNames, 0, s.primary)
+	s.vaults[s.primary] = d
+	s.locks = make(map[string]*sync.RWMutex, len(s.vaults))
+	for name := range s.vaults {
+		s.locks[name] = &sync.RWMutex{}
+	}
+	return s
+}
+
+// HandleRequest processes a single encoded request. It returns nil for
+// notifications, which never receive a response. Requests handled this way
+// have no connection to push notifications to, so core.subscribe fails.
+func (s *Server) HandleRequest(line []byte) *Response {
+	return s.handle(nil, line)
+}
+
+func (s *Server) handle(conn *connection, line []byte) *Response {
+	var req Request
+	if err := json.Unmarshal(line, &req); err != nil {
+		return &Response{JSONRPC: Version, Error: ParseError(err.Error())}
+	}
+	if req.JSONRPC != Version {
+		return &Response{JSONRPC: Version, ID: req.ID, Error: InvalidRequest(`jsonrpc must be "2.0"`)}
+	}
+	if req.Method == "" {
+		return &Response{JSONRPC: Version, ID: req.ID, Error: InvalidRequest("method is required")}
+	}
+
+	start := s.now()
+	result, rpcErr := s.safeDispatch(conn, req.Method, req.Params)
+	s.logRequest(req, s.now().Sub(start), rpcErr)
+	if req.ID == nil {
+		return nil
+	}
+	if rpcErr != nil {
+		return &Response{JSONRPC: Version, ID: req.ID, Error: rpcErr}
+	}
+	return &Response{JSONRPC: Version, ID: req.ID, Result: result}
+}
+
+// safeDispatch dispatches a request, turning a panic in its handler into an
+// internal error so that one bad request cannot take down the server. The
+// vault lock is released as the panic unwinds through dispatch.
+func (s *Server) safeDispatch(conn *connection, method string, params json.RawMessage) (result any, rpcErr *Error) {
+	defer func() {
+		if r := recover(); r != nil {
+			s.logger.Error("handler panicked", "method", method, "panic", r, "stack", string(debug.Stack()))
+			result, rpcErr = nil, InternalError(fmt.Sprintf("%s failed unexpectedly", method))
+		}
+	}()
+	return s.dispatch(conn, method, params)
+}
+
+// logRequest records the method, ID and processing time of a request, at
+// Warn when it exceeded the slow-request threshold.
+func (s *Server) logRequest(req Request, elapsed time.Duration, rpcErr *Error) {
+	level := slog.LevelDebug
+	if s.slowThreshold > 0 && elapsed > s.slowThreshold {
+		level = slog.LevelWarn
+	}
+	attrs := []any{"method", req.Method, "id", string(req.ID), "elapsed", elapsed}
+	if rpcErr != nil {
+		attrs = append(attrs, "error_code", int(rpcErr.Code))
+	}
+	if level == slog.LevelWarn {
+		s.logger.Warn("slow request", attrs...)
+		return
+	}
+	s.logger.Debug("handled request", attrs...)
+}
+
+// Serve reads newline-delimited requests from r and writes one response line
+// per request to w until r is exhausted. Once the client calls
+// core.subscribe, core.changed notifications are written to w as well.
+// Lines longer than the WithMaxRequestBytes limit get a parse error.
+func (s *Server) Serve(r io.Reader, w io.Writer) error {
+	conn := &connection{w: w}
+	defer conn.unsubscribe()
+
+	reader := bufio.NewReader(r)
+	for {
+		line, tooLong, readErr := readLine(reader, s.maxRequest)
+		if tooLong {
+			s.logger.Warn("request too large; discarded", "limit", s.maxRequest)
+			resp := &Response{JSONRPC: Version, Error: ParseError(fmt.Sprintf("request exceeds %d bytes", s.maxRequest))}
+			if err := s.send(conn, resp); err != nil {
+				return err
+			}
+		} else if trimmed := bytes.TrimSpace(line); len(trimmed) > 0 {
+			if err := s.respond(conn, trimmed); err != nil {
+				return err
+			}
+		}
+		if errors.Is(readErr, io.EOF) {
+			return nil
+		}
+		if readErr != nil {
+			return fmt.Errorf("read request: %w", readErr)
+		}
+	}
+}
+
+// readLine reads up to and including the next newline. Once the line
+// exceeds limit bytes, not counting the newline, the rest of it is read and
+// dropped rather than buffered, and tooLong is reported instead. A
+// non-positive limit reads lines of any length.
+func readLine(reader *bufio.Reader, limit int) (line []byte, tooLong bool, err error) {
+	for {
+		chunk, readErr := reader.ReadSlice('\n')
+		if !tooLong {
+			line = append(line, chunk...)
+			if limit > 0 && len(bytes.TrimSuffix(line, []byte("\n"))) > limit {
+				line, tooLong = nil, true
+			}
+		}
+		if !errors.Is(readErr, bufio.ErrBufferFull) {
+			return line, tooLong, readErr
+		}
+	}
+}
+
+// ServeListener accepts clients from ln, serving each on its own goroutine
+// as Serve does, until ln is closed.
+func (s *Server) ServeListener(ln net.Listener) error {
+	for {
+		c, err := ln.Accept()
+		if errors.Is(err, net.ErrClosed) {
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("accept client: %w", err)
+		}
+		go func() {
+			defer c.Close()
+			s.logger.Debug("client connected")
+			if err := s.Serve(c, c); err != nil {
+				s.logger.Warn("client connection failed", "err", err)
+			}
+			s.logger.Debug("client disconnected")
+		}()
+	}
+}
+
+func (s *Server) respond(conn *connection, line []byte) error {
+	resp := s.handle(conn, line)
+	if resp == nil {
+		return nil
+	}
+	return s.send(conn, resp)
+}
+
+// send encodes resp and writes it to conn.
+func (s *Server) send(conn *connection, resp *Response) error {
+	encoded, err := json.Marshal(resp)
+	if err != nil {
+		s.logger.Error("failed to encode response", "err", err)
+		encoded, err = json.Marshal(&Response{JSONRPC: Version, ID: resp.ID, Error: InternalError("failed to encode result")})
+		if err != nil {
+			return fmt.Errorf("encode response: %w", err)
+		}
+	}
+	s.logger.Debug(fmt.Sprintf("responded with %s", encoded))
+	if err := conn.write(encoded); err != nil {
+		return fmt.Errorf("write response: %w", err)
+	}
+	return nil
+}
+
+// connection is the per-client state of Serve: where to write messages and
+// the client's change subscriptions, keyed by vault name.
+type connection struct {
+	mu     sync.Mutex
+	w      io.Writer
+	cancel map[string]func()
+}
+
+// write sends one newline-terminated message. Responses and notifications
+// may be written from different goroutines.
+func (c *connection) write(encoded []byte) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	_, err := c.w.Write(append(encoded, '\n'))
+	return err
+}
+
+// subscribe forwards changes in the named vault to the client as
+// core.changed notifications. Subscribing to a vault again is a no-op.
+func (s *Server) subscribe(conn *connection, name string, d *domain.Domain) {
+	conn.mu.Lock()
+	defer conn.mu.Unlock()
+	if _, ok := conn.cancel[name]; ok {
+		return
+	}
+	if conn.cancel == nil {
+		conn.cancel = map[string]func(){}
+	}
+	conn.cancel[name] = d.Subscribe(func(changes model.ChangeSet) {
+		s.notify(conn, ChangedMethod, ChangedParams{Vault: name, NoteIDs: changes.NoteIDs, TaskIDs: changes.TaskIDs})
+	})
+}
+
+// notify writes a notification to conn, logging rather than returning
+// failures since no request awaits them.
+func (s *Server) notify(conn *connection, method string, params any) {
+	encoded, err := json.Marshal(Notification{JSONRPC: Version, Method: method, Params: params})
+	if err == nil {
+		err = conn.write(encoded)
+	}
+	if err != nil {
+		s.logger.Warn("failed to send notification", "method", method, "err", err)
+	}
+}
+
+func (c *connection) unsubscribe() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	for _, cancel := range c.cancel {
+		cancel()
+	}
+	c.cancel = nil
+}
@@ -0,0 +1,162 @@
+package rpc
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"log/slog"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/weakphish/yapper/internal/domain"
+)
+
+// writeVault creates files, keyed by slash-separated path, in a fresh vault
+// directory and returns its root.
+func writeVault(t *testing.T, files map[string]string) string {
+	t.Helper()
+	root := t.TempDir()
+	for rel, content := range files {
+		path := filepath.Join(root, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	return root
+}
+
+// newTestServer indexes the vault at root and serves it as the primary
+// vault, discarding logs.
+func newTestServer(t *testing.T, root string, opts ...ServerOption) *Server {
+	t.Helper()
+	d, _, err := domain.OpenInMemory(root, nil, nil, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return NewServer(d, slog.New(slog.NewTextHandler(io.Discard, nil)), opts...)
+}
+
+// call sends a request for method with params, omitted when nil, and
+// returns the response.
+func call(t *testing.T, s *Server, method string, params any) *Response {
+	t.Helper()
+	req := map[string]any{"jsonrpc": Version, "id": 1, "method": method}
+	if params != nil {
+		req["params"] = params
+	}
+	line, err := json.Marshal(req)
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp := s.HandleRequest(line)
+	if resp == nil {
+		t.Fatalf("%s: no response", method)
+	}
+	return resp
+}
+
+// decodeResult converts a successful response's result to T through its
+// JSON encoding, as a client would see it.
+func decodeResult[T any](t *testing.T, resp *Response) T {
+	t.Helper()
+	var result T
+	if resp.Error != nil {
+		t.Fatalf("unexpected error: %v", resp.Error)
+	}
+	encoded, err := json.Marshal(resp.Result)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := json.Unmarshal(encoded, &result); err != nil {
+		t.Fatal(err)
+	}
+	return result
+}
+
+// wantCode fails the test unless resp is an error with the given code.
+func wantCode(t *testing.T, resp *Response, code ErrorCode) {
+	t.Helper()
+	if resp.Error == nil || resp.Error.Code != code {
+		t.Fatalf("error = %v, want code %d", resp.Error, code)
+	}
+}
+
+func TestHandlerPanicBecomesInternalError(t *testing.T) {
+	var logs bytes.Buffer
+	// A domain without an index manager panics on any lookup.
+	s := NewServer(domain.New(nil), slog.New(slog.NewTextHandler(&logs, nil)))
+
+	wantCode(t, call(t, s, "core.ping", nil), CodeInternalError)
+	if !strings.Contains(logs.String(), "handler panicked") || !strings.Contains(logs.String(), "method=core.ping") {
+		t.Errorf("panic was not logged:\n%s", logs.String())
+	}
+	// The vault lock was released, so later requests are still served.
+	wantCode(t, call(t, s, "core.reindex", nil), CodeInternalError)
+	wantCode(t, call(t, s, "core.ping", nil), CodeInternalError)
+}
+
+// steppingClock returns a clock that moves forward by step on every call.
+func steppingClock(step time.Duration) func() time.Time {
+	now := time.Date(2025, time.April, 1, 9, 0, 0, 0, time.UTC)
+	return func() time.Time {
+		now = now.Add(step)
+		return now
+	}
+}
+
+func TestSlowRequestsAreLoggedAtWarn(t *testing.T) {
+	d, _, err := domain.OpenInMemory(writeVault(t, nil), nil, nil, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, tc := range []struct {
+		step time.Duration
+		slow bool
+	}{
+		{300 * time.Millisecond, true},
+		{10 * time.Millisecond, false},
+	} {
+		var logs bytes.Buffer
+		logger := slog.New(slog.NewTextHandler(&logs, &slog.HandlerOptions{Level: slog.LevelWarn}))
+		s := NewServer(d, logger, WithSlowThreshold(250*time.Millisecond), WithClock(steppingClock(tc.step)))
+		call(t, s, "core.ping", nil)
+		logged := strings.Contains(logs.String(), "slow request") && strings.Contains(logs.String(), "method=core.ping")
+		if logged != tc.slow {
+			t.Errorf("request taking %v: slow request logged = %v, want %v\n%s", tc.step, logged, tc.slow, logs.String())
+		}
+	}
+}
+
+func TestOverLimitRequestGetsParseError(t *testing.T) {
+	s := newTestServer(t, writeVault(t, nil), WithMaxRequestBytes(100))
+	huge := `{"jsonrpc":"2.0","id":1,"method":"core.ping","params":{"pad":"` + strings.Repeat("x", 10000) + `"}}`
+	input := huge + "\n" + `{"jsonrpc":"2.0","id":2,"method":"core.ping"}` + "\n"
+
+	var out bytes.Buffer
+	if err := s.Serve(strings.NewReader(input), &out); err != nil {
+		t.Fatal(err)
+	}
+	lines := strings.Split(strings.TrimSpace(out.String()), "\n")
+	if len(lines) != 2 {
+		t.Fatalf("got %d responses, want 2:\n%s", len(lines), out.String())
+	}
+	var rejected, served Response
+	if err := json.Unmarshal([]byte(lines[0]), &rejected); err != nil {
+		t.Fatal(err)
+	}
+	if rejected.Error == nil || rejected.Error.Code != CodeParseError || !strings.Contains(rejected.Error.Message, "100 bytes") {
+		t.Errorf("over-limit response = %s, want a parse error naming the limit", lines[0])
+	}
+	if err := json.Unmarshal([]byte(lines[1]), &served); err != nil {
+		t.Fatal(err)
+	}
+	if served.Error != nil || string(served.ID) != "2" {
+		t.Errorf("following request got %s, want a result for id 2", lines[1])
+	}
+}
@@ -0,0 +1,146 @@
+package vault
+
+import (
+	"fmt"
+	"regexp"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// DefaultDailyPattern keeps daily notes as YYYY-MM-DD.md at the vault root.
+const DefaultDailyPattern = "{date}.md"
+
+var placeholderRE = regexp.MustCompile(`\{([a-z]+)\}`)
+
+// placeholderPatterns maps each supported placeholder to the regex fragment
+// it matches during discovery.
+var placeholderPatterns = map[string]string{
+	"date":  `(\d{4}-\d{2}-\d{2})`,
+	"year":  `(\d{4})`,
+	"month": `(\d{2})`,
+	"day":   `(\d{2})`,
+}
+
+// DailyTemplate describes where daily notes live relative to the vault root,
+// e.g. "journal/{year}/{date}.md". Supported placeholders are {date}
+// (YYYY-MM-DD), {year} (YYYY), {month} (MM) and {day} (DD). The same template
+// is used to discover the date of existing notes and to place new ones.
+type DailyTemplate struct {
+	pattern string
+	re      *regexp.Regexp
+	fields  []string
+}
+
+// ParseDailyTemplate validates pattern and compiles it for matching. The
+// pattern must pin down a full date, either through {date} or through all of
+// {year}, {month} and {day}.
+func ParseDailyTemplate(pattern string) (DailyTemplate, error) {
+	pattern = strings.TrimPrefix(pattern, "/")
+	if pattern == "" {
+		return DailyTemplate{}, fmt.Errorf("daily template is empty")
+	}
+
+	var (
+		expr   strings.Builder
+		fields []string
+		seen   = map[string]bool{}
+		last   int
+	)
+	expr.WriteString("^")
+	for _, loc := range placeholderRE.FindAllStringSubmatchIndex(pattern, -1) {
+		name := pattern[loc[2]:loc[3]]
+		fragment, ok := placeholderPatterns[name]
+		if !ok {
+			return DailyTemplate{}, fmt.Errorf("daily template %q: unknown placeholder {%s}", pattern, name)
+		}
+		expr.WriteString(regexp.QuoteMeta(pattern[last:loc[0]]))
+		expr.WriteString(fragment)
+		fields = append(fields, name)
+		seen[name] = true
+		last = loc[1]
+	}
+	expr.WriteString(regexp.QuoteMeta(pattern[last:]))
+	expr.WriteString("$")
+
+	if !seen["date"] && !(seen["year"] && seen["month"] && seen["day"]) {
+		return DailyTemplate{}, fmt.Errorf("daily template %q must contain {date} or {year}, {month} and {day}", pattern)
+	}
+
+	re, err := regexp.Compile(expr.String())
+	if err != nil {
+		return DailyTemplate{}, fmt.Errorf("daily template %q: %w", pattern, err)
+	}
+	return DailyTemplate{pattern: pattern, re: re, fields: fields}, nil
+}
+
+// DefaultDailyTemplate returns the template for DefaultDailyPattern.
+func DefaultDailyTemplate() DailyTemplate {
+	t, err := ParseDailyTemplate(DefaultDailyPattern)
+	if err != nil {
+		panic(err)
+	}
+	return t
+}
+
+// String returns the template pattern.
+func (t DailyTemplate) String() string {
+	return t.pattern
+}
+
+// Format renders the slash-separated path of the daily note for date.
+func (t DailyTemplate) Format(date time.Time) string {
+	return strings.NewReplacer(
+		"{date}", date.Format("2006-01-02"),
+		"{year}", date.Format("2006"),
+		"{month}", date.Format("01"),
+		"{day}", date.Format("02"),
+	).Replace(t.pattern)
+}
+
+// Match extracts the date encoded in rel, a slash-separated path relative to
+// the vault root. Every placeholder contributes to the date, so the {year}
+// directory of "journal/{year}/{date}.md" must agree with the file name.
+func (t DailyTemplate) Match(rel string) (time.Time, bool) {
+	m := t.re.FindStringSubmatch(rel)
+	if m == nil {
+		return time.Time{}, false
+	}
+
+	year, month, day := -1, -1, -1
+	set := func(dst *int, value int) bool {
+		if *dst >= 0 && *dst != value {
+			return false
+		}
+		*dst = value
+		return true
+	}
+	for i, field := range t.fields {
+		value := m[i+1]
+		switch field {
+		case "date":
+			date, err := time.Parse("2006-01-02", value)
+			if err != nil {
+				return time.Time{}, false
+			}
+			if !set(&year, date.Year()) || !set(&month, int(date.Month())) || !set(&day, date.Day()) {
+				return time.Time{}, false
+			}
+		default:
+			n, err := strconv.Atoi(value)
+			if err != nil {
+				return time.Time{}, false
+			}
+			dst := map[string]*int{"year": &year, "month": &month, "day": &day}[field]
+			if !set(dst, n) {
+				return time.Time{}, false
+			}
+		}
+	}
+
+	date := time.Date(year, time.Month(month), day, 0, 0, 0, 0, time.UTC)
+	if date.Year() != year || int(date.Month()) != month || date.Day() != day {
+		return time.Time{}, false
+	}
+	return date, true
+}
@@ -0,0 +1,68 @@
+package vault
+
+import (
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestDailyTemplateNestedRoundTrip(t *testing.T) {
+	tmpl, err := ParseDailyTemplate("journal/{year}/{month}/{date}.md")
+	if err != nil {
+		t.Fatal(err)
+	}
+	date := time.Date(2024, time.February, 29, 0, 0, 0, 0, time.UTC)
+	rel := tmpl.Format(date)
+	if rel != "journal/2024/02/2024-02-29.md" {
+		t.Fatalf("Format = %q", rel)
+	}
+	got, ok := tmpl.Match(rel)
+	if !ok || !got.Equal(date) {
+		t.Errorf("Match(%q) = %v, %v, want %v", rel, got, ok, date)
+	}
+	for _, bad := range []string{"journal/2023/02/2024-02-29.md", "journal/2024/02/2024-02-30.md", "2024-02-29.md"} {
+		if got, ok := tmpl.Match(bad); ok {
+			t.Errorf("Match(%q) = %v, want no match", bad, got)
+		}
+	}
+}
+
+func TestDailyTemplateRequiresFullDate(t *testing.T) {
+	for _, pattern := range []string{"", "journal/{year}/{month}.md", "{date}/{week}.md"} {
+		if _, err := ParseDailyTemplate(pattern); err == nil {
+			t.Errorf("ParseDailyTemplate(%q) succeeded", pattern)
+		}
+	}
+}
+
+func TestVaultDatesNestedDailyNotes(t *testing.T) {
+	tmpl, err := ParseDailyTemplate("journal/{year}/{date}.md")
+	if err != nil {
+		t.Fatal(err)
+	}
+	root := t.TempDir()
+	v := NewFileSystemVault(root, WithDailyTemplate(tmpl))
+	date := time.Date(2025, time.March, 14, 0, 0, 0, 0, time.UTC)
+
+	path := v.DailyNotePath(date)
+	if want := filepath.Join(root, "journal", "2025", "2025-03-14.md"); path != want {
+		t.Fatalf("DailyNotePath = %s, want %s", path, want)
+	}
+	if err := v.WriteNote(path, "# Friday\n"); err != nil {
+		t.Fatal(err)
+	}
+	note, err := v.ReadNote(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if note.ID != "journal/2025/2025-03-14.md" || note.Date == nil || !note.Date.Equal(date) {
+		t.Errorf("note = %s dated %v, want journal/2025/2025-03-14.md dated %v", note.ID, note.Date, date)
+	}
+	paths, err := v.ListNotePaths()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(paths) != 1 || paths[0] != path {
+		t.Errorf("ListNotePaths = %v, want [%s]", paths, path)
+	}
+}
@@ -0,0 +1,344 @@
+package vault
+
+import (
+	"fmt"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"sort"
+	"strings"
+	"time"
+	"unicode/utf8"
+
+	"github.com/weakphish/yapper/internal/markdown"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+var h1RE = regexp.MustCompile(`^ {0,3}#[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*\r?$`)
+
+// DefaultExtensions are the file extensions recognized as notes unless
+// WithExtensions says otherwise.
+var DefaultExtensions = []string{"md"}
+
+// FileSystemVault is a Vault backed by a directory of Markdown files. It is
+// safe for concurrent use.
+type FileSystemVault struct {
+	root           string
+	daily          DailyTemplate
+	ignore         []string
+	extensions     []string
+	followSymlinks bool
+}
+
+// Option configures a FileSystemVault.
+type Option func(*FileSystemVault)
+
+// WithDailyTemplate sets the template used to discover and create daily notes.
+func WithDailyTemplate(t DailyTemplate) Option {
+	return func(v *FileSystemVault) {
+		v.daily = t
+	}
+}
+
+// WithIgnorePatterns skips files and directories matching any of the given
+// path.Match globs. A pattern is tried against both the slash-separated path
+// relative to the vault root and the entry's base name, so "templates/*" and
+// "*.excalidraw.md" both work.
+func WithIgnorePatterns(patterns []string) Option {
+	return func(v *FileSystemVault) {
+		v.ignore = patterns
+	}
+}
+
+// WithExtensions sets the file extensions recognized as notes, replacing
+// DefaultExtensions. Extensions are matched case-insensitively, with or
+// without a leading '.'. An empty list keeps the default.
+func WithExtensions(extensions []string) Option {
+	return func(v *FileSystemVault) {
+		if len(extensions) == 0 {
+			return
+		}
+		v.extensions = make([]string, 0, len(extensions))
+		for _, ext := range extensions {
+			v.extensions = append(v.extensions, strings.ToLower(strings.TrimPrefix(ext, ".")))
+		}
+	}
+}
+
+// WithFollowSymlinks sets whether symbolic links inside the vault are
+// followed when listing notes. It is on by default; when off, symlinked
+// files and directories are skipped.
+func WithFollowSymlinks(follow bool) Option {
+	return func(v *FileSystemVault) {
+		v.followSymlinks = follow
+	}
+}
+
+// NewFileSystemVault returns a vault rooted at root.
+func NewFileSystemVault(root string, opts ...Option) *FileSystemVault {
+	v := &FileSystemVault{
+		root:           filepath.Clean(root),
+		daily:          DefaultDailyTemplate(),
+		extensions:     DefaultExtensions,
+		followSymlinks: true,
+	}
+	for _, opt := range opts {
+		opt(v)
+	}
+	return v
+}
+
+// RootPath implements Vault.
+func (v *FileSystemVault) RootPath() string {
+	return v.root
+}
+
+// ListNotePaths implements Vault. Only files with a recognized extension are
+// listed; hidden and ignored files and directories are skipped. A directory
+// reached again through a symlink, as in a link to one of its ancestors, is
+// only walked once.
+func (v *FileSystemVault) ListNotePaths() ([]string, error) {
+	var paths []string
+	if err := v.gatherNoteFiles(v.root, map[string]bool{}, &paths); err != nil {
+		return nil, err
+	}
+	sort.Strings(paths)
+	return paths, nil
+}
+
+// gatherNoteFiles appends the notes under dir to paths. visited holds the
+// resolved paths of the directories already walked.
+func (v *FileSystemVault) gatherNoteFiles(dir string, visited map[string]bool, paths *[]string) error {
+	resolved, err := filepath.EvalSymlinks(dir)
+	if err != nil {
+		return fmt.Errorf("resolve vault dir %s: %w", dir, err)
+	}
+	if visited[resolved] {
+		return nil
+	}
+	visited[resolved] = true
+
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return fmt.Errorf("read vault dir %s: %w", dir, err)
+	}
+	for _, entry := range entries {
+		if strings.HasPrefix(entry.Name(), ".") {
+			continue
+		}
+		fullPath := filepath.Join(dir, entry.Name())
+		if v.ignored(fullPath) {
+			continue
+		}
+		if !v.followSymlinks && entry.Type()&fs.ModeSymlink != 0 {
+			continue
+		}
+		info, err := os.Stat(fullPath)
+		if err != nil {
+			return fmt.Errorf("stat %s: %w", fullPath, err)
+		}
+		if info.IsDir() {
+			if err := v.gatherNoteFiles(fullPath, visited, paths); err != nil {
+				return err
+			}
+			continue
+		}
+		if v.recognized(fullPath) {
+			*paths = append(*paths, fullPath)
+		}
+	}
+	return nil
+}
+
+// Indexable implements Vault: path must lie under the root, carry a
+// configured extension, and have no hidden or ignored component.
+func (v *FileSystemVault) Indexable(fullPath string) bool {
+	rel, err := filepath.Rel(v.root, fullPath)
+	if err != nil || !filepath.IsLocal(rel) || !v.recognized(fullPath) {
+		return false
+	}
+	prefix := v.root
+	for _, name := range strings.Split(rel, string(filepath.Separator)) {
+		prefix = filepath.Join(prefix, name)
+		if strings.HasPrefix(name, ".") || v.ignored(prefix) {
+			return false
+		}
+	}
+	return true
+}
+
+func (v *FileSystemVault) recognized(fullPath string) bool {
+	ext := strings.ToLower(strings.TrimPrefix(filepath.Ext(fullPath), "."))
+	return ext != "" && slices.Contains(v.extensions, ext)
+}
+
+func (v *FileSystemVault) ignored(fullPath string) bool {
+	rel, err := filepath.Rel(v.root, fullPath)
+	if err != nil {
+		return false
+	}
+	rel = filepath.ToSlash(rel)
+	base := path.Base(rel)
+	for _, pattern := range v.ignore {
+		if ok, _ := path.Match(pattern, rel); ok {
+			return true
+		}
+		if ok, _ := path.Match(pattern, base); ok {
+			return true
+		}
+	}
+	return false
+}
+
+// ReadNote implements Vault. A "date" field in the note's frontmatter takes
+// precedence over a date derived from its path, and the title is chosen as
+// deriveTitle describes. Aliases come from the frontmatter "aliases" list.
+// Files that are not valid UTF-8 are still read, with each invalid byte
+// sequence replaced by U+FFFD and the note marked Lossy.
+func (v *FileSystemVault) ReadNote(path string) (model.Note, error) {
+	raw, err := os.ReadFile(path)
+	if err != nil {
+		return model.Note{}, fmt.Errorf("read note %s: %w", path, err)
+	}
+	content, lossy := string(raw), !utf8.Valid(raw)
+	if lossy {
+		content = strings.ToValidUTF8(content, "\uFFFD")
+	}
+	info, err := os.Stat(path)
+	if err != nil {
+		return model.Note{}, fmt.Errorf("stat note %s: %w", path, err)
+	}
+	dateRange := frontmatterDateRange(content)
+	date := frontmatterDate(content)
+	if date == nil && dateRange != nil {
+		start := dateRange.Start
+		date = &start
+	}
+	if date == nil {
+		date = v.deriveDate(path)
+	}
+	return model.Note{
+		ID:         v.NoteID(path),
+		Path:       model.FilePath(path),
+		Title:      deriveTitle(path, content),
+		Aliases:    frontmatterList(content, "aliases"),
+		Date:       date,
+		DateRange:  dateRange,
+		ModifiedAt: info.ModTime().UTC(),
+		Content:    content,
+		Lossy:      lossy,
+	}, nil
+}
+
+// NoteID implements Vault. IDs are relative to the vault root so they do not
+// depend on where the vault lives. Paths outside the root keep their
+// absolute form.
+func (v *FileSystemVault) NoteID(path string) model.NoteID {
+	rel, err := filepath.Rel(v.root, path)
+	if err != nil || rel == ".." || strings.HasPrefix(rel, ".."+string(filepath.Separator)) {
+		return model.NoteID(filepath.ToSlash(path))
+	}
+	return model.NoteID(filepath.ToSlash(rel))
+}
+
+// NotePath implements Vault.
+func (v *FileSystemVault) NotePath(id model.NoteID) string {
+	p := filepath.FromSlash(string(id))
+	if filepath.IsAbs(p) {
+		return p
+	}
+	return filepath.Join(v.root, p)
+}
+
+// WriteNote implements Vault.
+func (v *FileSystemVault) WriteNote(path string, content string) error {
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return fmt.Errorf("create note dir for %s: %w", path, err)
+	}
+	// Write to a sibling temp file and rename it into place, so readers never
+	// see a half-written note.
+	tmp, err := os.CreateTemp(filepath.Dir(path), "."+filepath.Base(path)+".*.tmp")
+	if err != nil {
+		return fmt.Errorf("write note %s: %w", path, err)
+	}
+	defer os.Remove(tmp.Name())
+	if _, err := tmp.WriteString(content); err != nil {
+		tmp.Close()
+		return fmt.Errorf("write note %s: %w", path, err)
+	}
+	if err := tmp.Chmod(0o644); err != nil {
+		tmp.Close()
+		return fmt.Errorf("write note %s: %w", path, err)
+	}
+	if err := tmp.Close(); err != nil {
+		return fmt.Errorf("write note %s: %w", path, err)
+	}
+	if err := os.Rename(tmp.Name(), path); err != nil {
+		return fmt.Errorf("write note %s: %w", path, err)
+	}
+	return nil
+}
+
+// DailyNotePath implements Vault.
+func (v *FileSystemVault) DailyNotePath(date time.Time) string {
+	return filepath.Join(v.root, filepath.FromSlash(v.daily.Format(date)))
+}
+
+// WeeklyNotePath implements Vault. Weekly notes live at the vault root as
+// YYYY-Www.md.
+func (v *FileSystemVault) WeeklyNotePath(date time.Time) string {
+	return filepath.Join(v.root, WeeklyNoteStem(date)+".md")
+}
+
+// deriveDate resolves the date of the note at path. Paths matching the daily
+// template take their date from the template and weekly notes take the Monday
+// of their week; anything else falls back to a YYYY-MM-DD or YY-MM-DD file
+// stem.
+func (v *FileSystemVault) deriveDate(path string) *time.Time {
+	if rel, err := filepath.Rel(v.root, path); err == nil {
+		if date, ok := v.daily.Match(filepath.ToSlash(rel)); ok {
+			return &date
+		}
+	}
+	if date, ok := WeeklyNoteDate(path); ok {
+		return &date
+	}
+	stem := strings.TrimSuffix(filepath.Base(path), filepath.Ext(path))
+	for _, layout := range []string{"2006-01-02", "06-01-02"} {
+		if date, err := time.Parse(layout, stem); err == nil {
+			return &date
+		}
+	}
+	return nil
+}
+
+// deriveTitle picks a note's title: a "title" field in its frontmatter, else
+// its first level-1 heading, else the file stem.
+func deriveTitle(path, content string) string {
+	if title, ok := frontmatterField(content, "title"); ok && title != "" {
+		return title
+	}
+	if title, ok := firstH1(content); ok {
+		return title
+	}
+	return strings.TrimSuffix(filepath.Base(path), filepath.Ext(path))
+}
+
+// firstH1 returns the text of the first "# " heading in content outside
+// frontmatter and fenced code blocks, without any closing hashes.
+func firstH1(content string) (string, bool) {
+	lines := strings.Split(content, "\n")
+	var fence markdown.Fence
+	for _, line := range lines[markdown.FrontmatterEnd(lines):] {
+		if fence.Skip(line) {
+			continue
+		}
+		if m := h1RE.FindStringSubmatch(line); m != nil && m[1] != "" {
+			return m[1], true
+		}
+	}
+	return "", false
+}
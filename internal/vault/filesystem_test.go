@@ -0,0 +1,129 @@
+package vault
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+	"time"
+)
+
+// writeVault creates files, keyed by slash-separated path, in a fresh vault
+// directory and returns its root.
+func writeVault(t *testing.T, files map[string]string) string {
+	t.Helper()
+	root := t.TempDir()
+	for rel, content := range files {
+		path := filepath.Join(root, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	return root
+}
+
+// listRel lists the notes of v relative to its root, slash-separated.
+func listRel(t *testing.T, v *FileSystemVault) []string {
+	t.Helper()
+	paths, err := v.ListNotePaths()
+	if err != nil {
+		t.Fatal(err)
+	}
+	var rels []string
+	for _, path := range paths {
+		rels = append(rels, string(v.NoteID(path)))
+	}
+	return rels
+}
+
+func TestListNotePathsMixedExtensions(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"plain.md":               "# Plain\n",
+		"long.markdown":          "# Long\n",
+		"journal/2025-04-01.TXT": "# Journal\n",
+		"scan.pdf":               "%PDF",
+	})
+
+	if got := listRel(t, NewFileSystemVault(root)); !slices.Equal(got, []string{"plain.md"}) {
+		t.Errorf("default extensions list %v, want [plain.md]", got)
+	}
+	v := NewFileSystemVault(root, WithExtensions([]string{"md", ".markdown", "txt"}))
+	if got, want := listRel(t, v), []string{"journal/2025-04-01.TXT", "long.markdown", "plain.md"}; !slices.Equal(got, want) {
+		t.Errorf("listed %v, want %v", got, want)
+	}
+	note, err := v.ReadNote(filepath.Join(root, "journal", "2025-04-01.TXT"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if note.Title != "Journal" {
+		t.Errorf("txt note title = %q, want Journal", note.Title)
+	}
+}
+
+func TestListNotePathsSurvivesSymlinkCycles(t *testing.T) {
+	root := writeVault(t, map[string]string{"top.md": "# Top\n", "notes/a.md": "# A\n"})
+	for link, target := range map[string]string{
+		"notes/loop": "..",
+		"self":       ".",
+		"alias.md":   "top.md",
+	} {
+		if err := os.Symlink(target, filepath.Join(root, filepath.FromSlash(link))); err != nil {
+			t.Skipf("symlinks unsupported: %v", err)
+		}
+	}
+
+	for _, tc := range []struct {
+		follow bool
+		want   []string
+	}{
+		{true, []string{"alias.md", "notes/a.md", "top.md"}},
+		{false, []string{"notes/a.md", "top.md"}},
+	} {
+		v := NewFileSystemVault(root, WithFollowSymlinks(tc.follow))
+		done := make(chan error, 1)
+		go func() {
+			_, err := v.ListNotePaths()
+			done <- err
+		}()
+		select {
+		case err := <-done:
+			if err != nil {
+				t.Fatal(err)
+			}
+			if got := listRel(t, v); !slices.Equal(got, tc.want) {
+				t.Errorf("following symlinks %v: listed %v, want %v", tc.follow, got, tc.want)
+			}
+		case <-time.After(10 * time.Second):
+			t.Fatalf("following symlinks %v: walk did not terminate", tc.follow)
+		}
+	}
+}
+
+func TestReadNoteTitle(t *testing.T) {
+	root := writeVault(t, map[string]string{
+		"2025-04-01.md":  "Intro line.\n\n## Not a title\n\n# Quarterly planning\n\n# Second heading\n",
+		"titled.md":      "---\ntitle: From frontmatter\n---\n# From heading\n",
+		"plain-notes.md": "No headings at all.\n",
+		"fenced.md":      "```\n# not a heading\n```\n",
+		"long-fence.md":  "````\n```\n# still code\n````\n# After the fence\n",
+	})
+	v := NewFileSystemVault(root)
+	for name, want := range map[string]string{
+		"2025-04-01.md":  "Quarterly planning",
+		"titled.md":      "From frontmatter",
+		"plain-notes.md": "plain-notes",
+		"fenced.md":      "fenced",
+		"long-fence.md":  "After the fence",
+	} {
+		note, err := v.ReadNote(filepath.Join(root, name))
+		if err != nil {
+			t.Fatal(err)
+		}
+		if note.Title != want || note.Meta().Title != want {
+			t.Errorf("%s title = %q, want %q", name, note.Title, want)
+		}
+	}
+}
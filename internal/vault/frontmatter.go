@@ -0,0 +1,129 @@
+package vault
+
+import (
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/weakphish/yapper/internal/markdown"
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// frontmatterDate returns the "date" field of a note's YAML frontmatter, the
+// block fenced by "---" lines at the very start of the file. Only the
+// calendar day is used, so "2025-04-01T09:30" and "2025-04-01 09:30" both
+// yield 2025-04-01. It returns nil when there is no frontmatter, no date
+// field, or the date cannot be parsed.
+func frontmatterDate(content string) *time.Time {
+	value, ok := frontmatterField(content, "date")
+	if !ok {
+		return nil
+	}
+	date, ok := parseFrontmatterDay(value)
+	if !ok {
+		return nil
+	}
+	return &date
+}
+
+// frontmatterDateRange returns the span of days a note covers, from either a
+// "dates" list, spanning its earliest to latest date, or "start" and "end"
+// fields. Unparseable dates are ignored, and an end before its start yields
+// no range. It returns nil when the note names no span.
+func frontmatterDateRange(content string) *model.DateRange {
+	var days []time.Time
+	for _, value := range frontmatterList(content, "dates") {
+		if day, ok := parseFrontmatterDay(value); ok {
+			days = append(days, day)
+		}
+	}
+	if len(days) > 0 {
+		return &model.DateRange{
+			Start: slices.MinFunc(days, time.Time.Compare),
+			End:   slices.MaxFunc(days, time.Time.Compare),
+		}
+	}
+
+	startValue, hasStart := frontmatterField(content, "start")
+	endValue, hasEnd := frontmatterField(content, "end")
+	if !hasStart || !hasEnd {
+		return nil
+	}
+	start, ok := parseFrontmatterDay(startValue)
+	if !ok {
+		return nil
+	}
+	end, ok := parseFrontmatterDay(endValue)
+	if !ok || end.Before(start) {
+		return nil
+	}
+	return &model.DateRange{Start: start, End: end}
+}
+
+// parseFrontmatterDay parses the calendar day at the start of a frontmatter
+// value, ignoring any time of day that follows it.
+func parseFrontmatterDay(value string) (time.Time, bool) {
+	if len(value) > len("2006-01-02") {
+		value = value[:len("2006-01-02")]
+	}
+	date, err := time.Parse("2006-01-02", value)
+	return date, err == nil
+}
+
+// frontmatterField returns the value of the first key field in a note's
+// frontmatter, with surrounding quotes removed.
+func frontmatterField(content, key string) (string, bool) {
+	lines := strings.Split(content, "\n")
+	if len(lines) == 0 || strings.TrimSpace(lines[0]) != "---" {
+		return "", false
+	}
+	for _, line := range lines[1:] {
+		line = strings.TrimSpace(line)
+		if line == "---" || line == "..." {
+			return "", false
+		}
+		k, value, ok := strings.Cut(line, ":")
+		if ok && strings.TrimSpace(k) == key {
+			return strings.Trim(strings.TrimSpace(value), `"'`), true
+		}
+	}
+	return "", false
+}
+
+// frontmatterList returns the items of the key field in a note's
+// frontmatter, written inline ("aliases: [Foo, Bar]"), as a block of
+// "- item" lines, or as a single value. Quotes around items are removed.
+func frontmatterList(content, key string) []string {
+	lines := strings.Split(content, "\n")
+	end := markdown.FrontmatterEnd(lines)
+	for i := 1; i < end-1; i++ {
+		k, value, ok := strings.Cut(strings.TrimSpace(lines[i]), ":")
+		if !ok || strings.TrimSpace(k) != key {
+			continue
+		}
+		value = strings.TrimSpace(value)
+		var items []string
+		switch {
+		case strings.HasPrefix(value, "[") && strings.HasSuffix(value, "]"):
+			items = strings.Split(value[1:len(value)-1], ",")
+		case value != "":
+			items = []string{value}
+		default:
+			for _, line := range lines[i+1 : end-1] {
+				item, ok := strings.CutPrefix(strings.TrimSpace(line), "-")
+				if !ok {
+					break
+				}
+				items = append(items, item)
+			}
+		}
+		var list []string
+		for _, item := range items {
+			if item = strings.Trim(strings.TrimSpace(item), `"'`); item != "" {
+				list = append(list, item)
+			}
+		}
+		return list
+	}
+	return nil
+}
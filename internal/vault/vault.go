@@ -0,0 +1,35 @@
+package vault
+
+import (
+	"time"
+
+	"github.com/weakphish/yapper/internal/model"
+)
+
+// Vault abstracts access to the Markdown files that make up a note vault.
+// Implementations used with index.VaultIndexManager.ConcurrentReindex, or
+// with a manager given index.WithReindexWorkers, must allow ReadNote to be
+// called from several goroutines at once.
+type Vault interface {
+	// RootPath returns the directory the vault is rooted at.
+	RootPath() string
+	// ListNotePaths returns the absolute paths of every note in the vault.
+	ListNotePaths() ([]string, error)
+	// ReadNote loads the note stored at path.
+	ReadNote(path string) (model.Note, error)
+	// NoteID returns the ID of the note stored at path.
+	NoteID(path string) model.NoteID
+	// NotePath resolves a note ID back to the absolute path of the note.
+	NotePath(id model.NoteID) string
+	// Indexable reports whether a note stored at path would be listed by
+	// ListNotePaths, ignoring whether the file exists.
+	Indexable(path string) bool
+	// WriteNote replaces the contents of the note at path, creating the file
+	// and any missing parent directories. The replacement is atomic.
+	WriteNote(path string, content string) error
+	// DailyNotePath returns the absolute path of the daily note for date.
+	DailyNotePath(date time.Time) string
+	// WeeklyNotePath returns the absolute path of the weekly note for the
+	// ISO week containing date.
+	WeeklyNotePath(date time.Time) string
+}
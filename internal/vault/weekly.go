@@ -0,0 +1,38 @@
+package vault
+
+import (
+	"fmt"
+	"path/filepath"
+	"regexp"
+	"strconv"
+	"strings"
+	"time"
+)
+
+var weeklyStemRE = regexp.MustCompile(`^(\d{4})-W(\d{2})$`)
+
+// WeeklyNoteStem returns the file stem of the weekly note for the ISO week
+// containing date, e.g. "2025-W14".
+func WeeklyNoteStem(date time.Time) string {
+	year, week := date.ISOWeek()
+	return fmt.Sprintf("%04d-W%02d", year, week)
+}
+
+// WeeklyNoteDate reports whether path names a weekly note, a YYYY-Www file
+// stem, and returns the Monday of that ISO week.
+func WeeklyNoteDate(path string) (time.Time, bool) {
+	stem := strings.TrimSuffix(filepath.Base(path), filepath.Ext(path))
+	m := weeklyStemRE.FindStringSubmatch(stem)
+	if m == nil {
+		return time.Time{}, false
+	}
+	year, _ := strconv.Atoi(m[1])
+	week, _ := strconv.Atoi(m[2])
+	// January 4th always falls in week 1.
+	jan4 := time.Date(year, time.January, 4, 0, 0, 0, 0, time.UTC)
+	monday := jan4.AddDate(0, 0, -((int(jan4.Weekday())+6)%7)+7*(week-1))
+	if y, w := monday.ISOWeek(); y != year || w != week {
+		return time.Time{}, false
+	}
+	return monday, true
+}
@@ -0,0 +1,36 @@
+package vault
+
+import (
+	"testing"
+	"time"
+)
+
+func TestWeeklyNoteAcrossYearBoundaries(t *testing.T) {
+	for _, tc := range []struct {
+		date   time.Time
+		stem   string
+		monday time.Time
+	}{
+		// 2024-12-30 is a Monday in the first ISO week of 2025.
+		{time.Date(2024, time.December, 30, 0, 0, 0, 0, time.UTC), "2025-W01", time.Date(2024, time.December, 30, 0, 0, 0, 0, time.UTC)},
+		{time.Date(2025, time.January, 5, 23, 0, 0, 0, time.UTC), "2025-W01", time.Date(2024, time.December, 30, 0, 0, 0, 0, time.UTC)},
+		{time.Date(2025, time.January, 6, 0, 0, 0, 0, time.UTC), "2025-W02", time.Date(2025, time.January, 6, 0, 0, 0, 0, time.UTC)},
+		// 2021-01-03 is a Sunday still in the last ISO week of 2020.
+		{time.Date(2021, time.January, 3, 0, 0, 0, 0, time.UTC), "2020-W53", time.Date(2020, time.December, 28, 0, 0, 0, 0, time.UTC)},
+		{time.Date(2025, time.April, 2, 0, 0, 0, 0, time.UTC), "2025-W14", time.Date(2025, time.March, 31, 0, 0, 0, 0, time.UTC)},
+	} {
+		stem := WeeklyNoteStem(tc.date)
+		if stem != tc.stem {
+			t.Errorf("WeeklyNoteStem(%s) = %q, want %q", tc.date.Format(time.DateOnly), stem, tc.stem)
+		}
+		monday, ok := WeeklyNoteDate("notes/" + stem + ".md")
+		if !ok || !monday.Equal(tc.monday) {
+			t.Errorf("WeeklyNoteDate(%s) = %v, %v, want %v", stem, monday, ok, tc.monday)
+		}
+	}
+	for _, bad := range []string{"2025-W53.md", "2025-W00.md", "2025-W1.md", "2025-w14.md", "notes.md"} {
+		if monday, ok := WeeklyNoteDate(bad); ok {
+			t.Errorf("WeeklyNoteDate(%q) = %v, want no match", bad, monday)
+		}
+	}
+}